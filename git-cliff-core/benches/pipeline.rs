@@ -0,0 +1,103 @@
+use criterion::{
+	criterion_group,
+	criterion_main,
+	BenchmarkId,
+	Criterion,
+};
+use git_cliff_core::config::Config;
+use git_cliff_core::embed::EmbeddedConfig;
+use git_cliff_core::release::Release;
+use git_cliff_core::template::Template;
+use git_cliff_core::test_support::SampleRepository;
+
+/// Sizes of the synthetic repositories (number of commits).
+const SIZES: [usize; 2] = [100, 1000];
+
+/// Number of the commits in each release of the synthetic repositories.
+const RELEASE_SIZE: usize = 50;
+
+/// Messages of the synthetic commits (`{}` is replaced with the index).
+const MESSAGES: [&str; 6] = [
+	"feat(core): add the feature #{}",
+	"fix(cli): fix the crash #{}",
+	"docs: update the usage of #{}",
+	"refactor(core)!: rename the module #{}",
+	"chore: bump the dependency #{}",
+	"update the code of #{}",
+];
+
+/// Returns the releases of a synthetic repository with the given number of
+/// commits.
+fn sample_releases(commits: usize) -> Vec<Release<'static>> {
+	let mut repository =
+		SampleRepository::new().expect("failed to create the repository");
+	for i in 0..commits {
+		repository
+			.commit(&MESSAGES[i % MESSAGES.len()].replace("{}", &i.to_string()))
+			.expect("failed to create the commit");
+		if (i + 1) % RELEASE_SIZE == 0 {
+			repository
+				.tag(&format!("v0.{}.0", i / RELEASE_SIZE))
+				.expect("failed to create the tag");
+		}
+	}
+	repository.releases().expect("failed to read the releases")
+}
+
+/// Processes the commits of the releases with the git configuration.
+fn process_releases<'a>(
+	releases: &[Release<'a>],
+	config: &Config,
+) -> Vec<Release<'a>> {
+	releases
+		.iter()
+		.map(|release| Release {
+			commits: release
+				.commits
+				.iter()
+				.filter_map(|commit| commit.process(&config.git, None).ok())
+				.collect(),
+			..release.clone()
+		})
+		.collect()
+}
+
+/// Benchmarks the parsing, grouping and rendering of the commits.
+fn pipeline(c: &mut Criterion) {
+	let config = EmbeddedConfig::parse().expect("failed to parse the config");
+	let template = Template::new(config.changelog.body.clone().unwrap_or_default())
+		.expect("failed to parse the template");
+	let mut group = c.benchmark_group("pipeline");
+	for size in SIZES {
+		let releases = sample_releases(size);
+		let processed = process_releases(&releases, &config);
+		group.bench_with_input(
+			BenchmarkId::new("parse", size),
+			&releases,
+			|b, v| b.iter(|| process_releases(v, &config)),
+		);
+		group.bench_with_input(
+			BenchmarkId::new("group", size),
+			&processed,
+			|b, v| {
+				b.iter(|| v.iter().map(Release::group_counts).collect::<Vec<_>>())
+			},
+		);
+		group.bench_with_input(
+			BenchmarkId::new("render", size),
+			&processed,
+			|b, v| {
+				b.iter(|| {
+					v.iter()
+						.map(|release| template.render(release))
+						.collect::<Result<String, _>>()
+						.expect("failed to render the template")
+				})
+			},
+		);
+	}
+	group.finish();
+}
+
+criterion_group!(benches, pipeline);
+criterion_main!(benches);
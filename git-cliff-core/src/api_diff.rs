@@ -0,0 +1,45 @@
+use crate::command;
+use crate::config::ApiDiffConfig;
+use crate::error::Result;
+use crate::release::ApiChanges;
+
+/// Default command for diffing the public API.
+///
+/// Uses [cargo-public-api](https://github.com/Enselic/cargo-public-api) which
+/// builds the rustdoc JSON of both revisions.
+const DEFAULT_COMMAND: &str =
+	"cargo public-api diff \"$API_DIFF_FROM..$API_DIFF_TO\"";
+
+/// Returns the changes of the public API between the given revisions.
+///
+/// `API_DIFF_FROM` and `API_DIFF_TO` environment variables are set for the
+/// configured command and its output is parsed with [`ApiChanges::parse`].
+pub fn diff(config: &ApiDiffConfig, from: &str, to: &str) -> Result<ApiChanges> {
+	let command = config.command.as_deref().unwrap_or(DEFAULT_COMMAND);
+	let output = command::run(command, None, vec![
+		("API_DIFF_FROM", from),
+		("API_DIFF_TO", to),
+	])?;
+	Ok(ApiChanges::parse(&output))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	#[test]
+	#[cfg(target_family = "unix")]
+	fn diff_api() -> Result<()> {
+		let changes = diff(
+			&ApiDiffConfig {
+				command: Some(String::from(
+					"printf 'Added items\\n+pub fn %s()\\n' \"$API_DIFF_TO\"",
+				)),
+			},
+			"v1.0.0",
+			"v1.1.0",
+		)?;
+		assert_eq!(vec![String::from("pub fn v1.1.0()")], changes.added);
+		Ok(())
+	}
+}
@@ -0,0 +1,326 @@
+use crate::config::{
+	CacheBackend,
+	CacheConfig,
+};
+use crate::error::Result;
+use crate::metrics::Counter;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{
+	Digest,
+	Sha256,
+};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs::{
+	self,
+	DirBuilder,
+};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::{
+	Arc,
+	Mutex,
+	PoisonError,
+};
+use std::time::{
+	SystemTime,
+	UNIX_EPOCH,
+};
+
+/// Default time to live of the cached lookups in seconds (i.e. a week).
+pub const DEFAULT_TTL: u64 = 604_800;
+
+/// Storage backend for the data that is cached between the API requests or
+/// the changelog generations.
+///
+/// Values are grouped by namespaces (e.g. `tickets`) so that the different
+/// kinds of data do not collide.
+pub trait Cache: Debug + Send + Sync {
+	/// Returns the cached value of the key.
+	fn get(&self, namespace: &str, key: &str) -> Result<Option<String>>;
+
+	/// Caches the value of the key.
+	fn set(&self, namespace: &str, key: &str, value: &str) -> Result<()>;
+
+	/// Removes the cached value of the key.
+	fn remove(&self, namespace: &str, key: &str) -> Result<()>;
+}
+
+impl dyn Cache {
+	/// Returns the cached value of the key as JSON.
+	///
+	/// Values that cannot be deserialized (e.g. written by another version)
+	/// are treated as missing.
+	pub fn get_json<T: DeserializeOwned>(
+		&self,
+		namespace: &str,
+		key: &str,
+	) -> Result<Option<T>> {
+		let value = self
+			.get(namespace, key)?
+			.and_then(|value| serde_json::from_str(&value).ok());
+		match value {
+			Some(_) => Counter::CacheHits.increment(),
+			None => Counter::CacheMisses.increment(),
+		}
+		Ok(value)
+	}
+
+	/// Caches the value of the key as JSON.
+	pub fn set_json<T: Serialize>(
+		&self,
+		namespace: &str,
+		key: &str,
+		value: &T,
+	) -> Result<()> {
+		self.set(namespace, key, &serde_json::to_string(value)?)
+	}
+
+	/// Returns the cached value of the key as JSON if it is cached at most
+	/// the given number of seconds ago.
+	pub fn get_fresh_json<T: DeserializeOwned>(
+		&self,
+		namespace: &str,
+		key: &str,
+		ttl: u64,
+	) -> Result<Option<T>> {
+		Ok(self
+			.get_json::<Timestamped<T>>(namespace, key)?
+			.filter(|cached| now().saturating_sub(cached.timestamp) <= ttl)
+			.map(|cached| cached.value))
+	}
+
+	/// Caches the value of the key as JSON along with the current time.
+	pub fn set_fresh_json<T: Serialize>(
+		&self,
+		namespace: &str,
+		key: &str,
+		value: &T,
+	) -> Result<()> {
+		self.set_json(namespace, key, &Timestamped {
+			timestamp: now(),
+			value,
+		})
+	}
+}
+
+/// Value in the cache with the time that it is cached at.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct Timestamped<T> {
+	/// Unix timestamp of the time that the value is cached at.
+	timestamp: u64,
+	/// Cached value.
+	value:     T,
+}
+
+/// Returns the seconds since the Unix epoch.
+fn now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |v| v.as_secs())
+}
+
+/// Returns the cache of the configured backend.
+pub fn new(config: &CacheConfig) -> Result<Arc<dyn Cache>> {
+	Ok(match config.backend.unwrap_or(CacheBackend::File) {
+		CacheBackend::File => Arc::new(FileCache::new(match config.path.as_ref() {
+			Some(path) => PathBuf::from(path),
+			None => dirs_next::cache_dir()
+				.ok_or_else(|| {
+					crate::error::Error::ArgumentError(String::from(
+						"Cannot determine the cache directory, please set \
+						 cache.path",
+					))
+				})?
+				.join("git-cliff"),
+		})),
+		CacheBackend::Memory => Arc::new(MemoryCache::default()),
+		#[cfg(feature = "redis")]
+		CacheBackend::Redis => Arc::new(RedisCache::new(
+			config.url.as_deref().unwrap_or("redis://127.0.0.1/"),
+		)?),
+		#[cfg(not(feature = "redis"))]
+		CacheBackend::Redis => {
+			return Err(crate::error::Error::ArgumentError(String::from(
+				"Redis cache requires the 'redis' feature to be enabled",
+			)))
+		}
+	})
+}
+
+/// Cache that stores the values as files in a directory.
+#[derive(Debug, Clone)]
+pub struct FileCache {
+	dir: PathBuf,
+}
+
+impl FileCache {
+	/// Constructs a new instance that stores the values in the given
+	/// directory.
+	pub fn new(dir: PathBuf) -> Self {
+		Self { dir }
+	}
+
+	/// Returns the path of the file for the given key.
+	fn path(&self, namespace: &str, key: &str) -> PathBuf {
+		self.dir
+			.join(namespace)
+			.join(format!("{:x}", Sha256::digest(key.as_bytes())))
+	}
+}
+
+impl Cache for FileCache {
+	fn get(&self, namespace: &str, key: &str) -> Result<Option<String>> {
+		match fs::read_to_string(self.path(namespace, key)) {
+			Ok(value) => Ok(Some(value)),
+			Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(e.into()),
+		}
+	}
+
+	fn set(&self, namespace: &str, key: &str, value: &str) -> Result<()> {
+		let path = self.path(namespace, key);
+		if let Some(parent) = path.parent() {
+			// Keep the cached responses private to the current user.
+			let mut builder = DirBuilder::new();
+			builder.recursive(true);
+			#[cfg(unix)]
+			std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+			builder.create(parent)?;
+		}
+		fs::write(path, value)?;
+		Ok(())
+	}
+
+	fn remove(&self, namespace: &str, key: &str) -> Result<()> {
+		match fs::remove_file(self.path(namespace, key)) {
+			Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+			_ => Ok(()),
+		}
+	}
+}
+
+/// Cache that keeps the values in memory.
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+	entries: Mutex<HashMap<(String, String), String>>,
+}
+
+impl Cache for MemoryCache {
+	fn get(&self, namespace: &str, key: &str) -> Result<Option<String>> {
+		Ok(self
+			.entries
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.get(&(namespace.to_string(), key.to_string()))
+			.cloned())
+	}
+
+	fn set(&self, namespace: &str, key: &str, value: &str) -> Result<()> {
+		self.entries
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.insert((namespace.to_string(), key.to_string()), value.to_string());
+		Ok(())
+	}
+
+	fn remove(&self, namespace: &str, key: &str) -> Result<()> {
+		self.entries
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.remove(&(namespace.to_string(), key.to_string()));
+		Ok(())
+	}
+}
+
+/// Cache that stores the values in a Redis server.
+#[cfg(feature = "redis")]
+#[derive(Debug, Clone)]
+pub struct RedisCache {
+	client: redis::Client,
+}
+
+#[cfg(feature = "redis")]
+impl RedisCache {
+	/// Constructs a new instance that connects to the given URL.
+	pub fn new(url: &str) -> Result<Self> {
+		Ok(Self {
+			client: redis::Client::open(url)?,
+		})
+	}
+
+	/// Returns the Redis key for the given key.
+	fn key(namespace: &str, key: &str) -> String {
+		format!("git-cliff:{namespace}:{key}")
+	}
+}
+
+#[cfg(feature = "redis")]
+impl Cache for RedisCache {
+	fn get(&self, namespace: &str, key: &str) -> Result<Option<String>> {
+		use redis::Commands;
+		Ok(self
+			.client
+			.get_connection()?
+			.get(Self::key(namespace, key))?)
+	}
+
+	fn set(&self, namespace: &str, key: &str, value: &str) -> Result<()> {
+		use redis::Commands;
+		self.client
+			.get_connection()?
+			.set::<_, _, ()>(Self::key(namespace, key), value)?;
+		Ok(())
+	}
+
+	fn remove(&self, namespace: &str, key: &str) -> Result<()> {
+		use redis::Commands;
+		self.client
+			.get_connection()?
+			.del::<_, ()>(Self::key(namespace, key))?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	fn assert_cache(cache: Arc<dyn Cache>) -> Result<()> {
+		cache.remove("test", "key")?;
+		assert_eq!(None, cache.get("test", "key")?);
+		cache.set("test", "key", "value")?;
+		assert_eq!(Some(String::from("value")), cache.get("test", "key")?);
+		assert_eq!(None, cache.get("other", "key")?);
+		cache.set_json("test", "json", &vec![1, 2])?;
+		assert_eq!(Some(vec![1, 2]), cache.get_json::<Vec<u8>>("test", "json")?);
+		assert_eq!(None, cache.get_json::<Vec<u8>>("test", "key")?);
+		cache.remove("test", "key")?;
+		assert_eq!(None, cache.get("test", "key")?);
+		Ok(())
+	}
+
+	#[test]
+	fn file_cache() -> Result<()> {
+		let dir = std::env::temp_dir()
+			.join(format!("git-cliff-cache-test-{}", std::process::id()));
+		assert_cache(Arc::new(FileCache::new(dir.clone())))?;
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::PermissionsExt;
+			assert_eq!(
+				0o700,
+				fs::metadata(dir.join("test"))?.permissions().mode() & 0o777
+			);
+		}
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+
+	#[test]
+	fn memory_cache() -> Result<()> {
+		assert_cache(Arc::new(MemoryCache::default()))
+	}
+}
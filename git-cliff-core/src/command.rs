@@ -1,4 +1,5 @@
 use crate::error::Result;
+use std::env;
 use std::io::{
 	Error as IoError,
 	ErrorKind as IoErrorKind,
@@ -11,29 +12,59 @@ use std::process::{
 use std::str;
 use std::thread;
 
+/// Environment variable for overriding the shell that runs the commands.
+const SHELL_ENV: &str = "GIT_CLIFF_SHELL";
+
+/// Returns the shell for running the commands.
+///
+/// Defaults to `cmd` on Windows and `sh` elsewhere. The shell can be
+/// overridden via the `GIT_CLIFF_SHELL` environment variable, e.g. `pwsh` or
+/// `bash` (Git Bash) on Windows.
+fn shell() -> String {
+	env::var(SHELL_ENV)
+		.ok()
+		.filter(|v| !v.trim().is_empty())
+		.unwrap_or_else(|| {
+			String::from(if cfg!(target_os = "windows") {
+				"cmd"
+			} else {
+				"sh"
+			})
+		})
+}
+
+/// Returns the argument of the shell for running a command string.
+fn shell_argument(shell: &str) -> &'static str {
+	let name = shell
+		.rsplit(['/', '\\'])
+		.next()
+		.unwrap_or(shell)
+		.to_lowercase();
+	let name = name.strip_suffix(".exe").unwrap_or(&name);
+	match name {
+		"cmd" => "/C",
+		"pwsh" | "powershell" => "-Command",
+		_ => "-c",
+	}
+}
+
 /// Runs the given OS command and returns the output as string.
 ///
 /// Use `input` parameter to specify a text to write to stdin.
 /// Environment variables are set accordingly to `envs`.
+/// Line endings of the output are normalized to `\n`.
 pub fn run(
 	command: &str,
 	input: Option<String>,
 	envs: Vec<(&str, &str)>,
 ) -> Result<String> {
-	let mut child = if cfg!(target_os = "windows") {
-		Command::new("cmd")
-			.args(["/C", command])
-			.stdin(Stdio::piped())
-			.stdout(Stdio::piped())
-			.spawn()
-	} else {
-		Command::new("sh")
-			.envs(envs)
-			.args(["-c", command])
-			.stdin(Stdio::piped())
-			.stdout(Stdio::piped())
-			.spawn()
-	}?;
+	let shell = shell();
+	let mut child = Command::new(&shell)
+		.envs(envs)
+		.args([shell_argument(&shell), command])
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.spawn()?;
 	if let Some(input) = input {
 		let mut stdin = child.stdin.take().ok_or_else(|| {
 			IoError::new(IoErrorKind::Other, "stdin is not captured")
@@ -46,7 +77,7 @@ pub fn run(
 	}
 	let output = child.wait_with_output()?;
 	if output.status.success() {
-		Ok(str::from_utf8(&output.stdout)?.to_string())
+		Ok(str::from_utf8(&output.stdout)?.replace("\r\n", "\n"))
 	} else {
 		Err(IoError::new(
 			IoErrorKind::Other,
@@ -78,4 +109,18 @@ mod test {
 		assert!(run("some_command", None, vec![]).is_err());
 		Ok(())
 	}
+
+	#[test]
+	fn shell_arguments() {
+		for (shell, argument) in [
+			("sh", "-c"),
+			("/usr/bin/bash", "-c"),
+			("cmd", "/C"),
+			("C:\\Windows\\System32\\cmd.exe", "/C"),
+			("pwsh", "-Command"),
+			("powershell.exe", "-Command"),
+		] {
+			assert_eq!(argument, shell_argument(shell));
+		}
+	}
 }
@@ -1,10 +1,31 @@
-use std::collections::HashMap;
-use crate::{command, github};
-use crate::config::{CommitParser, CommitPreprocessor, GitConfig, GithubConfig, LinkParser};
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::iter;
+use crate::{command, emoji, gerrit, github, gitlab, sourcehut};
+use crate::config::{CommitParser, CommitPreprocessor, EmailRedaction, EmojiMode, EntryNormalizer, FieldParser, GerritConfig, GitConfig, GithubConfig, GitlabConfig, IssueParser, LinkParser, ParserMatch, TrailingPeriod};
 use crate::error::{
 	Error as AppError,
 	Result,
 };
+use crate::gerrit::Change as GerritChange;
+use crate::intern::Symbol;
+use crate::provider::{
+	GithubProvider,
+	PullRequest,
+	RemoteProvider,
+};
+use crate::signature::CryptoSignature;
+use crate::sourcehut::Patchset;
+use crate::tracker::{
+	self,
+	Ticket,
+};
+use crate::translation::TranslationMemory;
+use encoding_rs::{
+	Encoding,
+	UTF_8,
+};
 use git2::{
 	Commit as GitCommit,
 	Signature as CommitSignature,
@@ -18,16 +39,51 @@ use lazy_regex::{
 	Lazy,
 	Regex,
 };
+use log::warn;
 use serde::ser::{
 	Serialize,
 	SerializeStruct,
 	Serializer,
 };
+use tokio::sync::Mutex;
+
+/// Usernames of the coauthors on the remote, keyed by their names and emails.
+pub type CoauthorUsernames = HashMap<Vec<(String, String)>, Vec<String>>;
 
 /// Regular expression for matching SHA1 and a following commit message
 /// separated by a whitespace.
 static SHA1_REGEX: Lazy<Regex> = lazy_regex!(r#"^\b([a-f0-9]{40})\b (.*)$"#);
 
+/// Regular expression for matching the co-authors in the trailers.
+static COAUTHOR_REGEX: Lazy<Regex> =
+	lazy_regex!(r#"(?mi)^Co-authored-by:\s*(?P<name>.+)(<(?P<email>.+)>)"#);
+
+/// Regular expression for matching the email addresses (optionally in angle
+/// brackets) in the commit messages.
+static EMAIL_REGEX: Lazy<Regex> = lazy_regex!(
+	r#"(?P<open>[ \t]*<)?(?P<email>[\w.%+-]+@[\w-]+(?:\.[\w-]+)+)(?P<close>>)?"#
+);
+
+/// Regular expression for matching the ID of a reverted commit.
+static REVERT_ID_REGEX: Lazy<Regex> =
+	lazy_regex!(r#"This reverts commit ([a-f0-9]{7,40})"#);
+
+/// Regular expression for matching the subject of a reverted commit.
+static REVERT_SUBJECT_REGEX: Lazy<Regex> = lazy_regex!(r#"^Revert "(.+)"$"#);
+
+/// Regular expression for matching the subject of a reverted commit in a
+/// conventional `revert` commit, e.g. `revert: feat: add xyz`.
+static REVERT_TYPE_REGEX: Lazy<Regex> =
+	lazy_regex!(r#"^revert(?:\([^)]*\))?!?: (.+)$"#);
+
+/// Regular expression for matching the subject of a fixed up commit.
+static FIXUP_SUBJECT_REGEX: Lazy<Regex> =
+	lazy_regex!(r#"^(?:(?:fixup|squash|amend)! )+(.+)$"#);
+
+/// Regular expression for matching the words to look up in the dictionary of
+/// the [`EntryNormalizer`].
+static WORD_REGEX: Lazy<Regex> = lazy_regex!(r#"[\w']+"#);
+
 /// Object representing a link
 #[derive(Debug, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -73,9 +129,9 @@ impl<'a> From<&'a ConventionalFooter<'a>> for Footer<'a> {
 )]
 pub struct Signature {
 	/// Name on the signature.
-	name:            Option<String>,
+	name:            Option<Symbol>,
 	/// Email on the signature.
-	email:           Option<String>,
+	email:           Option<Symbol>,
 	/// Time of the signature.
 	timestamp: i64,
 }
@@ -83,45 +139,93 @@ pub struct Signature {
 impl<'a> From<CommitSignature<'a>> for Signature {
 	fn from(signature: CommitSignature<'a>) -> Self {
 		Self {
-			name:      signature.name().map(String::from),
-			email:     signature.email().map(String::from),
+			name:      signature.name().map(Symbol::from),
+			email:     signature.email().map(Symbol::from),
 			timestamp: signature.when().seconds(),
 		}
 	}
 }
 
+impl Signature {
+	/// Returns the name on the signature.
+	pub fn name(&self) -> Option<&str> {
+		self.name.as_deref()
+	}
+
+	/// Returns the email on the signature.
+	pub fn email(&self) -> Option<&str> {
+		self.email.as_deref()
+	}
+
+	/// Returns the email (or the name if not set) for identifying the person.
+	pub fn key(&self) -> Option<&str> {
+		self.email().or_else(|| self.name())
+	}
+}
+
 /// Common commit object that is parsed from a repository.
 #[derive(Debug, Default, Clone, PartialEq, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Commit<'a> {
 	/// Commit ID.
-	pub id:               String,
+	pub id:                String,
 	/// Commit message including title, description and summary.
-	pub message:          String,
+	pub message:           String,
 	/// Conventional commit.
 	#[serde(skip_deserializing)]
-	pub conv:             Option<ConventionalCommit<'a>>,
+	pub conv:              Option<ConventionalCommit<'a>>,
 	/// Commit group based on a commit parser or its conventional type.
-	pub group:            Option<String>,
-	/// Default commit scope based on (inherited from) conventional type or a
-	/// commit parser.
-	pub default_scope:    Option<String>,
+	pub group:             Option<Symbol>,
+	/// Groups that are set by the commit parsers, including the appended
+	/// ones.
+	pub groups:            Vec<Symbol>,
+	/// Default commit scope based on (inherited from) conventional type, a
+	/// commit parser or the changed paths.
+	pub default_scope:     Option<Symbol>,
 	/// Commit scope for overriding the default one.
-	pub scope:            Option<String>,
+	pub scope:             Option<Symbol>,
 	/// A list of links found in the commit
-	pub links:            Vec<Link>,
+	pub links:             Vec<Link>,
+	/// Issues of the trackers that are found by the issue parsers.
+	pub issues:            Vec<Ticket>,
+	/// Custom fields that are extracted by the field parsers.
+	pub fields:            BTreeMap<String, String>,
 	/// Commit author.
-	pub author:           Signature,
+	pub author:            Signature,
 	/// Commit coauthors.
-	pub coauthors:        Vec<Signature>,
+	pub coauthors:         Vec<Signature>,
 	/// Committer.
-	pub committer:        Signature,
-	/// Github username of commit author.
-	pub github_author:    Option<String>,
-	/// Github usernames of commit coauthors.
-	pub github_coauthors: Option<Vec<String>>,
+	pub committer:         Signature,
+	/// Username of commit author on the remote (e.g. Github).
+	pub github_author:     Option<String>,
+	/// Usernames of commit coauthors on the remote (e.g. Github).
+	pub github_coauthors:  Option<Vec<String>>,
 	/// Associated pull request numbers.
-	pub pull_requests:    Option<Vec<u32>>,
+	pub pull_requests:     Option<Vec<u32>>,
+	/// Associated pull request with its comment and reaction counts.
+	pub pr:                Option<PullRequest>,
+	/// GitLab username of commit author.
+	pub gitlab_author:     Option<String>,
+	/// Associated GitLab merge request IDs.
+	pub merge_requests:    Option<Vec<u32>>,
+	/// GitLab usernames of the participants of the merge requests.
+	pub mr_participants:   Option<Vec<String>>,
+	/// Patchsets of lists.sr.ht that are referenced by the commit.
+	pub patchsets:         Vec<Patchset>,
+	/// Gerrit change that is referenced by the `Change-Id` trailer.
+	pub gerrit_change:     Option<GerritChange>,
+	/// ID of the released commit that this commit is cherry-picked from.
+	pub cherry_pick_of:    Option<String>,
+	/// IDs of the parent commits.
+	pub parents:           Vec<String>,
+	/// IDs of the skipped commits that are referenced by this commit.
+	pub hidden_references: Vec<String>,
+	/// IDs of the fixup commits that are folded into this commit.
+	pub fixups:            Vec<String>,
+	/// Tickets of the issue trackers that are referenced by this commit.
+	pub tickets:           Vec<Ticket>,
+	/// Cryptographic signature of the commit.
+	pub signature:         Option<CryptoSignature>,
 }
 
 impl<'a> From<String> for Commit<'a> {
@@ -146,32 +250,66 @@ impl<'a> From<String> for Commit<'a> {
 	}
 }
 
+/// Returns the message of the given commit in UTF-8.
+pub(crate) fn message_of(commit: &GitCommit) -> String {
+	decode_message(
+		&commit.id().to_string(),
+		commit.message_bytes(),
+		commit.message_encoding(),
+	)
+}
+
+/// Decodes the commit message with the encoding of its `encoding` header
+/// (UTF-8 if not set).
+///
+/// Unknown encodings fall back to UTF-8 and the malformed sequences are
+/// replaced with the replacement character, with a warning.
+fn decode_message(id: &str, bytes: &[u8], label: Option<&str>) -> String {
+	let encoding = match label {
+		Some(label) => {
+			Encoding::for_label(label.trim().as_bytes()).unwrap_or_else(|| {
+				warn!("Unknown encoding of the commit {}: {}", id, label);
+				UTF_8
+			})
+		}
+		None => UTF_8,
+	};
+	let (message, had_errors) = encoding.decode_without_bom_handling(bytes);
+	if had_errors {
+		warn!(
+			"The message of the commit {} is not valid {}, replacing the invalid \
+			 characters",
+			id,
+			encoding.name()
+		);
+	}
+	message.into_owned()
+}
+
 impl<'a> From<&GitCommit<'a>> for Commit<'a> {
 	fn from(commit: &GitCommit<'a>) -> Self {
 		let mut coauthors = Vec::new();
-		let message = commit.message().unwrap_or_default().to_string();
-		Regex::new(r#"(?mi)^Co-authored-by:\s*(?P<name>.+)(<(?P<email>.+)>)"#)
-			.unwrap()
-			.captures_iter(&message)
-			.for_each(|captures| {
-				if let (Some(name), Some(email)) = (
-					captures.name("name").map(|v| v.as_str()),
-					captures.name("email").map(|v| v.as_str()),
-				) {
-					coauthors.push(Signature {
-						name: Some(name.to_string()),
-						email: Some(email.to_string()),
-						timestamp: commit.author().when().seconds(),
-						..Default::default()
-					});
-				}
-			});
+		let message = message_of(commit);
+		COAUTHOR_REGEX.captures_iter(&message).for_each(|captures| {
+			if let (Some(name), Some(email)) = (
+				captures.name("name").map(|v| v.as_str()),
+				captures.name("email").map(|v| v.as_str()),
+			) {
+				coauthors.push(Signature {
+					name: Some(Symbol::from(name)),
+					email: Some(Symbol::from(email)),
+					timestamp: commit.author().when().seconds(),
+					..Default::default()
+				});
+			}
+		});
 		Commit {
 			message,
 			coauthors,
 			id: commit.id().to_string(),
 			author: commit.author().into(),
 			committer: commit.committer().into(),
+			parents: commit.parent_ids().map(|id| id.to_string()).collect(),
 			..Default::default()
 		}
 	}
@@ -187,15 +325,112 @@ impl Commit<'_> {
 		}
 	}
 
+	/// Returns whether the commit reverts or fixes up the given commit.
+	///
+	/// Fixups (i.e. `fixup!`, `squash!` and `amend!`) are matched by the
+	/// subject.
+	pub fn references(&self, commit: &Commit) -> bool {
+		self.reverts(commit) ||
+			self.fixup_of()
+				.map_or(false, |referenced| referenced == commit.subject())
+	}
+
+	/// Returns whether the commit is a revert, i.e. has a `This reverts commit
+	/// …` line, a `Revert "…"` subject or the conventional `revert` type.
+	pub fn is_revert(&self) -> bool {
+		let subject = self.subject();
+		REVERT_ID_REGEX.is_match(&self.message) ||
+			REVERT_SUBJECT_REGEX.is_match(subject) ||
+			REVERT_TYPE_REGEX.is_match(subject)
+	}
+
+	/// Returns whether the commit reverts the given commit.
+	///
+	/// Reverts are matched by the ID in `This reverts commit …` or the subject
+	/// of the reverted commit in the quotes (`Revert "…"`) or after the
+	/// conventional `revert` type (`revert: …`).
+	pub fn reverts(&self, commit: &Commit) -> bool {
+		if REVERT_ID_REGEX
+			.captures_iter(&self.message)
+			.any(|captures| commit.id.starts_with(&captures[1]))
+		{
+			return true;
+		}
+		let subject = self.subject();
+		REVERT_SUBJECT_REGEX
+			.captures(subject)
+			.or_else(|| REVERT_TYPE_REGEX.captures(subject))
+			.map_or(false, |captures| &captures[1] == commit.subject())
+	}
+
+	/// Returns the subject (i.e. the trimmed first line of the message).
+	pub fn subject(&self) -> &str {
+		self.message.lines().next().unwrap_or_default().trim()
+	}
+
+	/// Returns the subject of the commit that this commit fixes up, if this is
+	/// a `fixup!`, `squash!` or `amend!` commit.
+	pub fn fixup_of(&self) -> Option<&str> {
+		FIXUP_SUBJECT_REGEX
+			.captures(self.subject())
+			.and_then(|captures| captures.get(1))
+			.map(|subject| subject.as_str())
+	}
+
+	/// Returns the deprecations that the commit introduces.
+	///
+	/// These are the description of a conventional commit with the `deprecate`
+	/// (or `deprecated`) type and the values of its `Deprecated` footers.
+	pub fn deprecations(&self) -> Vec<String> {
+		let mut deprecations = Vec::new();
+		if let Some(conv) = &self.conv {
+			if ["deprecate", "deprecated"]
+				.iter()
+				.any(|v| conv.type_().as_str().eq_ignore_ascii_case(v))
+			{
+				deprecations.push(conv.description().to_string());
+			}
+		}
+		deprecations.extend(
+			self.footers()
+				.filter(|footer| footer.token.eq_ignore_ascii_case("deprecated"))
+				.map(|footer| footer.value.to_string()),
+		);
+		deprecations
+	}
+
+	/// Returns whether the commit is a merge commit, i.e. has multiple parents.
+	pub fn is_merge(&self) -> bool {
+		self.parents.len() > 1
+	}
+
 	/// Processes the commit.
 	///
+	/// * replaces or strips the emojis
 	/// * converts commit to a conventional commit
 	/// * sets the group for the commit
 	/// * extacts links and generates URLs
-	pub fn process(&self, config: &GitConfig) -> Result<Self> {
+	/// * normalizes the subject
+	///
+	/// Outputs of the preprocessor commands are looked up from and stored in
+	/// the given [`TranslationMemory`].
+	pub fn process(
+		&self,
+		config: &GitConfig,
+		memory: Option<&mut TranslationMemory>,
+	) -> Result<Self> {
 		let mut commit = self.clone();
+		match config.emojis {
+			Some(EmojiMode::Replace) => {
+				commit.message = emoji::replace_shortcodes(&commit.message);
+			}
+			Some(EmojiMode::Strip) => {
+				commit.message = emoji::strip(&commit.message);
+			}
+			None => {}
+		}
 		if let Some(preprocessors) = &config.commit_preprocessors {
-			commit = commit.preprocess(preprocessors)?;
+			commit = commit.preprocess(preprocessors, memory)?;
 		}
 		if config.conventional_commits.unwrap_or(true) {
 			if config.filter_unconventional.unwrap_or(true) {
@@ -209,14 +444,52 @@ impl Commit<'_> {
 				parsers,
 				config.protect_breaking_commits.unwrap_or(false),
 				config.filter_commits.unwrap_or(false),
+				config.parser_match.unwrap_or(ParserMatch::First),
 			)?;
 		}
 		if let Some(parsers) = &config.link_parsers {
 			commit = commit.parse_links(parsers)?;
 		}
+		commit.patchsets = sourcehut::patchsets(&commit.message);
+		if let Some(parsers) = &config.issue_parsers {
+			commit = commit.parse_issues(parsers);
+		}
+		if let Some(parsers) = &config.field_parsers {
+			commit = commit.parse_fields(parsers)?;
+		}
+		if let Some(normalizer) = &config.entry_normalizer {
+			commit = commit.normalize(normalizer)?;
+		}
 		Ok(commit)
 	}
 
+	/// Validates the commit against the conventional commits specification
+	/// and the commit parsers, and returns the problems.
+	///
+	/// The commit is [processed](Commit::process) in the same way as for
+	/// generating the changelog, so the commits that are skipped by the commit
+	/// parsers and the merge commits are valid. The commits that do not match
+	/// any commit parser are reported regardless of `filter_commits`.
+	pub fn lint(&self, config: &GitConfig) -> Vec<String> {
+		if self.is_merge() {
+			return Vec::new();
+		}
+		let config = GitConfig {
+			filter_commits: Some(true),
+			..config.clone()
+		};
+		match self.process(&config, None) {
+			Ok(_) | Err(AppError::SkippedCommit) => Vec::new(),
+			Err(AppError::GroupError(_)) => {
+				vec![String::from("Does not match any commit parser")]
+			}
+			Err(AppError::ParseError(e)) => {
+				vec![format!("Not a conventional commit: {e}")]
+			}
+			Err(e) => vec![e.to_string()],
+		}
+	}
+
 	/// Returns the commit with its conventional type set.
 	pub fn into_conventional(mut self) -> Result<Self> {
 		match ConventionalCommit::parse(Box::leak(
@@ -233,11 +506,14 @@ impl Commit<'_> {
 	/// Preprocesses the commit using [`CommitPreprocessor`]s.
 	///
 	/// Modifies the commit [`message`] using regex or custom OS command.
+	/// Commands are not run again for the commits that have their output in
+	/// the [`TranslationMemory`].
 	///
 	/// [`message`]: Commit::message
 	pub fn preprocess(
 		mut self,
 		preprocessors: &[CommitPreprocessor],
+		mut memory: Option<&mut TranslationMemory>,
 	) -> Result<Self> {
 		preprocessors.iter().try_for_each(|preprocessor| {
 			if let Some(text) = &preprocessor.replace {
@@ -247,11 +523,27 @@ impl Commit<'_> {
 					.to_string();
 			} else if let Some(command) = &preprocessor.replace_command {
 				if preprocessor.pattern.is_match(&self.message) {
-					self.message = command::run(
-						command,
-						Some(self.message.to_string()),
-						vec![("COMMIT_SHA", &self.id)],
-					)?;
+					let memorized = memory
+						.as_deref()
+						.filter(|_| !self.id.is_empty())
+						.and_then(|memory| {
+							memory.get(&self.id, command, &self.message)
+						})
+						.map(String::from);
+					let output = match memorized {
+						Some(output) => output,
+						None => command::run(
+							command,
+							Some(self.message.to_string()),
+							vec![("COMMIT_SHA", &self.id)],
+						)?,
+					};
+					if let Some(memory) = memory.as_deref_mut() {
+						if !self.id.is_empty() {
+							memory.insert(&self.id, command, &self.message, &output);
+						}
+					}
+					self.message = output;
 				}
 			}
 			Ok::<(), AppError>(())
@@ -259,6 +551,28 @@ impl Commit<'_> {
 		Ok(self)
 	}
 
+	/// States if the provided `CommitParser` matches the commit.
+	///
+	/// The parsers without a regex match the commits by the `breaking` and
+	/// `merge` flags.
+	fn is_matched_by(&self, parser: &CommitParser) -> bool {
+		let breaking = self.conv.as_ref().map(|c| c.breaking()).unwrap_or(false);
+		if parser.breaking.map_or(false, |v| v != breaking) ||
+			parser.merge.map_or(false, |v| v != self.is_merge())
+		{
+			return false;
+		}
+		if parser.message.is_none() && parser.body.is_none() {
+			return parser.breaking.is_some() || parser.merge.is_some();
+		}
+		let body = self.conv.as_ref().and_then(|v| v.body());
+		match (&parser.message, &parser.body, body) {
+			(Some(regex), _, _) if regex.is_match(&self.message) => true,
+			(_, Some(regex), Some(body)) => regex.is_match(body),
+			_ => false,
+		}
+	}
+
 	/// States if the commit is skipped in the provided `CommitParser`.
 	///
 	/// Returns `false` if `protect_breaking_commits` is enabled in the config
@@ -272,7 +586,8 @@ impl Commit<'_> {
 
 	/// Parses the commit using [`CommitParser`]s.
 	///
-	/// Sets the [`group`] and [`scope`] of the commit.
+	/// Sets the [`group`] and [`scope`] of the commit from the matching
+	/// parsers based on the given [`ParserMatch`] mode.
 	///
 	/// [`group`]: Commit::group
 	/// [`scope`]: Commit::scope
@@ -281,39 +596,74 @@ impl Commit<'_> {
 		parsers: &[CommitParser],
 		protect_breaking: bool,
 		filter: bool,
+		mode: ParserMatch,
 	) -> Result<Self> {
+		let mut matched_parsers = Vec::new();
+		let mut appending_parsers = Vec::new();
 		for parser in parsers {
-			let mut regex_checks = Vec::new();
-			if let Some(message_regex) = parser.message.as_ref() {
-				regex_checks.push((message_regex, self.message.to_string()))
+			let append = parser.append.unwrap_or(false);
+			if (mode == ParserMatch::First && !append && !matched_parsers.is_empty()) ||
+				!self.is_matched_by(parser)
+			{
+				continue;
 			}
-			if let (Some(body_regex), Some(body)) = (
-				parser.body.as_ref(),
-				self.conv.as_ref().and_then(|v| v.body()),
-			) {
-				regex_checks.push((body_regex, body.to_string()))
-			}
-			for (regex, text) in regex_checks {
-				if regex.is_match(&text) {
-					if self.skip_commit(parser, protect_breaking) {
-						return Err(AppError::GroupError(String::from(
-							"Skipping commit",
-						)));
-					} else {
-						self.group = parser.group.as_ref().cloned();
-						self.scope = parser.scope.as_ref().cloned();
-						self.default_scope = parser.default_scope.as_ref().cloned();
-						return Ok(self);
-					}
-				}
+			if append {
+				appending_parsers.push(parser);
+			} else {
+				matched_parsers.push(parser);
 			}
 		}
-		if !filter {
-			Ok(self)
+		if appending_parsers
+			.iter()
+			.any(|parser| self.skip_commit(parser, protect_breaking))
+		{
+			return Err(AppError::SkippedCommit);
+		}
+		// The appending parsers set the group if no other parser matches.
+		if matched_parsers.is_empty() {
+			matched_parsers.extend(appending_parsers.first());
+		}
+		// Stable sort keeps the order of the parsers with the same priority.
+		matched_parsers
+			.sort_by_key(|parser| Reverse(parser.priority.unwrap_or_default()));
+		let parser = match (mode, matched_parsers.first()) {
+			(_, None) if !filter => return Ok(self),
+			(_, None) => {
+				return Err(AppError::GroupError(String::from(
+					"Commit does not belong to any group",
+				)));
+			}
+			(ParserMatch::All, Some(_)) => CommitParser {
+				group: matched_parsers.iter().find_map(|v| v.group.clone()),
+				default_scope: matched_parsers
+					.iter()
+					.find_map(|v| v.default_scope.clone()),
+				scope: matched_parsers.iter().find_map(|v| v.scope.clone()),
+				skip: matched_parsers.iter().find_map(|v| v.skip),
+				..(*matched_parsers[0]).clone()
+			},
+			(_, Some(parser)) => (*parser).clone(),
+		};
+		if self.skip_commit(&parser, protect_breaking) {
+			Err(AppError::SkippedCommit)
 		} else {
-			Err(AppError::GroupError(String::from(
-				"Commit does not belong to any group",
-			)))
+			self.groups = Vec::new();
+			for group in parser
+				.group
+				.iter()
+				.chain(appending_parsers.iter().filter_map(|v| v.group.as_ref()))
+			{
+				if !self.groups.contains(&Symbol::from(group)) {
+					self.groups.push(Symbol::from(group));
+				}
+			}
+			self.group = parser.group.map(Symbol::from);
+			self.scope = parser.scope.map(Symbol::from);
+			self.default_scope = parser
+				.default_scope
+				.map(Symbol::from)
+				.or(self.default_scope);
+			Ok(self)
 		}
 	}
 
@@ -343,6 +693,125 @@ impl Commit<'_> {
 		Ok(self)
 	}
 
+	/// Parses the commit using [`IssueParser`]s.
+	///
+	/// Sets the [`issues`] of the commit without the duplicates.
+	///
+	/// [`issues`]: Commit::issues
+	pub fn parse_issues(mut self, parsers: &[IssueParser]) -> Self {
+		for parser in parsers {
+			for (key, captures) in tracker::keys(&parser.pattern, &self.message) {
+				if self.issues.iter().any(|issue| issue.key == key) {
+					continue;
+				}
+				let mut url = String::new();
+				captures.expand(&parser.url, &mut url);
+				self.issues.push(Ticket {
+					tracker: parser.tracker.clone(),
+					key: key.to_string(),
+					url,
+					..Ticket::default()
+				});
+			}
+		}
+		self
+	}
+
+	/// Parses the commit using [`FieldParser`]s.
+	///
+	/// Sets the [`fields`] of the commit from the footers or the message.
+	/// The footers of the non-conventional commits are the lines in the
+	/// `Token: value` format.
+	///
+	/// [`fields`]: Commit::fields
+	pub fn parse_fields(
+		mut self,
+		parsers: &HashMap<String, FieldParser>,
+	) -> Result<Self> {
+		for (name, parser) in parsers {
+			let footer =
+				parser.footer.as_deref().and_then(|token| match &self.conv {
+					Some(_) => self
+						.footers()
+						.find(|footer| footer.token.eq_ignore_ascii_case(token))
+						.map(|footer| footer.value.trim().to_string()),
+					None => self.message.lines().find_map(|line| {
+						line.split_once(':')
+							.filter(|(key, _)| {
+								key.trim().eq_ignore_ascii_case(token)
+							})
+							.map(|(_, value)| value.trim().to_string())
+					}),
+				});
+			let value = footer.or_else(|| {
+				let captures = parser.pattern.as_ref()?.captures(&self.message)?;
+				captures
+					.name("value")
+					.or_else(|| captures.get(1))
+					.or_else(|| captures.get(0))
+					.map(|v| v.as_str().trim().to_string())
+			});
+			if let Some(value) = value {
+				self.fields.insert(name.to_string(), value);
+			}
+		}
+		Ok(self)
+	}
+
+	/// Normalizes the subject of the commit using the [`EntryNormalizer`].
+	///
+	/// The subject is the description of a conventional commit or the first
+	/// line of the commit message otherwise.
+	pub fn normalize(mut self, normalizer: &EntryNormalizer) -> Result<Self> {
+		let header = self.message.lines().next().unwrap_or_default();
+		let subject = match &self.conv {
+			Some(conv) if header.ends_with(conv.description()) => conv.description(),
+			Some(_) => return Ok(self),
+			None => header,
+		};
+		let mut normalized = subject.to_string();
+		if let Some(dictionary) = &normalizer.dictionary {
+			normalized = WORD_REGEX
+				.replace_all(&normalized, |captures: &regex::Captures| {
+					match dictionary.get(&captures[0]) {
+						Some(word) => word.to_string(),
+						None => captures[0].to_string(),
+					}
+				})
+				.to_string();
+		}
+		if normalizer.capitalize.unwrap_or(false) {
+			let mut chars = normalized.chars();
+			if let Some(first) = chars.next() {
+				normalized =
+					first.to_uppercase().collect::<String>() + chars.as_str();
+			}
+		}
+		match normalizer.trailing_period {
+			Some(TrailingPeriod::Remove) if !normalized.ends_with("..") => {
+				if let Some(trimmed) = normalized.strip_suffix('.') {
+					normalized = trimmed.to_string();
+				}
+			}
+			Some(TrailingPeriod::Add)
+				if !normalized.ends_with(&['.', '!', '?'][..]) =>
+			{
+				normalized.push('.');
+			}
+			_ => {}
+		}
+		if normalized == subject {
+			return Ok(self);
+		}
+		let prefix = &header[..header.len() - subject.len()];
+		self.message =
+			format!("{prefix}{normalized}{}", &self.message[header.len()..]);
+		if self.conv.is_some() {
+			self = self.into_conventional()?;
+		}
+		Ok(self)
+	}
+
 	/// Returns an iterator over this commit's [`Footer`]s, if this is a
 	/// conventional commit.
 	///
@@ -353,24 +822,119 @@ impl Commit<'_> {
 			.flat_map(|conv| conv.footers().iter().map(Footer::from))
 	}
 
+	/// Redacts the email addresses of the authors, co-authors and committer,
+	/// and the ones in the message (e.g. `Co-authored-by` trailers) and the
+	/// custom fields.
+	///
+	/// The salt is used for hashing the email addresses.
+	pub fn redact_emails(&mut self, redaction: EmailRedaction, salt: &str) {
+		for signature in [&mut self.author, &mut self.committer]
+			.into_iter()
+			.chain(self.coauthors.iter_mut())
+		{
+			signature.email = signature
+				.email
+				.as_deref()
+				.and_then(|email| redaction.redact(email, salt))
+				.map(Symbol::from);
+		}
+		let redact_text = |text: &str| {
+			EMAIL_REGEX
+				.replace_all(text, |captures: &regex::Captures| {
+					match redaction.redact(&captures["email"], salt) {
+						Some(hash) => format!(
+							"{}{hash}{}",
+							captures.name("open").map_or("", |v| v.as_str()),
+							captures.name("close").map_or("", |v| v.as_str())
+						),
+						None => String::new(),
+					}
+				})
+				.to_string()
+		};
+		for value in self.fields.values_mut() {
+			*value = redact_text(value);
+		}
+		let message = redact_text(&self.message);
+		if message != self.message {
+			self.message = message;
+			if self.conv.is_some() {
+				self.conv = ConventionalCommit::parse(Box::leak(
+					self.message.clone().into_boxed_str(),
+				))
+				.ok();
+			}
+		}
+	}
+
 	/// Resolves the Github information of this commit.
 	pub async fn resolve_github(
 		&mut self,
 		config: &GithubConfig,
 		token: &Option<String>,
 		github_repo: &str,
-		github_usernames: &mut HashMap<String, String>,
-		github_coauthors: &mut HashMap<Vec<(String, String)>, Vec<String>>,
+		github_usernames: &Mutex<HashMap<String, String>>,
+		github_coauthors: &Mutex<CoauthorUsernames>,
 	) -> Result<()> {
-		if config.resolve_authors.is_some() {
-			if let Some(email) = &self.author.email {
-				if let Some(author) = github_usernames.get(email) {
-					self.github_author = Some(author.to_string());
-				} else {
-					let author = github::get_commit_author(token, github_repo, &self.id).await?;
+		let provider = GithubProvider::new(
+			github::api_url(config),
+			token.clone(),
+			github_repo.to_string(),
+		);
+		self.resolve_remote(
+			&provider,
+			config.resolve_authors.unwrap_or(true),
+			github_usernames,
+			github_coauthors,
+		)
+		.await?;
+		if config.resolve_reactions.unwrap_or(false) {
+			self.resolve_pull_request(&provider).await?;
+		}
+		Ok(())
+	}
+
+	/// Resolves the associated pull request with its comment and reaction
+	/// counts.
+	pub async fn resolve_pull_request(
+		&mut self,
+		provider: &dyn RemoteProvider,
+	) -> Result<()> {
+		if self.pull_requests.is_none() {
+			self.pull_requests =
+				Some(provider.commit_pull_requests(&self.id).await?);
+		}
+		if let Some(number) = self.pull_requests.iter().flatten().next() {
+			self.pr = provider.pull_request(*number).await?;
+		}
+		Ok(())
+	}
+
+	/// Resolves the information of this commit on the remote repository of
+	/// the given provider.
+	///
+	/// The usernames and coauthors are shared between the commits that are
+	/// resolved concurrently, the locks are not held during the requests.
+	pub async fn resolve_remote(
+		&mut self,
+		provider: &dyn RemoteProvider,
+		resolve_authors: bool,
+		github_usernames: &Mutex<HashMap<String, String>>,
+		github_coauthors: &Mutex<CoauthorUsernames>,
+	) -> Result<()> {
+		if resolve_authors {
+			if let Some(email) = self.author.email.as_deref() {
+				let author = github_usernames.lock().await.get(email).cloned();
+				if let Some(author) = author {
+					self.github_author = Some(author);
+				} else if let Some(author) = provider.commit_author(&self.id).await?
+				{
 					self.github_author = Some(author.clone());
-					// Cache github username
-					github_usernames.insert(email.to_string(), author);
+					// Cache username
+					github_usernames
+						.lock()
+						.await
+						.insert(email.to_string(), author);
 				}
 			}
 		}
@@ -383,49 +947,45 @@ impl Commit<'_> {
 			.map(|c| vec![c.as_str().parse::<u32>().unwrap()]);
 
 		if !self.coauthors.is_empty() {
+			let usernames = github_usernames.lock().await;
 			let result = self.coauthors.iter()
-				.flat_map(|c| c.email.as_ref())
-				.flat_map(|e| github_usernames.get(e))
+				.flat_map(|c| c.email.as_deref())
+				.flat_map(|e| usernames.get(e))
 				.cloned()
 				.collect::<Vec<_>>();
+			drop(usernames);
 
 			if result.len() == self.coauthors.len() {
 				self.github_coauthors = Some(result);
 			} else {
 				// This means that we need to get coauthors from PR
 				if self.pull_requests.is_none() {
-					self.pull_requests = Some(
-						github::get_prs_associated_with_commit(
-							token,
-							github_repo,
-							&self.id
-						).await?
-					);
+					self.pull_requests =
+						Some(provider.commit_pull_requests(&self.id).await?);
 				}
 
 				let key = self.coauthors.iter()
 					.filter(|c| c.name.is_some() && c.email.is_some())
-					.map(|c| (c.name.clone().unwrap(), c.email.clone().unwrap()))
+					.map(|c| {
+						(
+							c.name.as_deref().unwrap().to_string(),
+							c.email.as_deref().unwrap().to_string(),
+						)
+					})
 					.collect::<Vec<_>>();
 
-				let coauthors = github_coauthors.get(&key);
+				let coauthors = github_coauthors.lock().await.get(&key).cloned();
 				if coauthors.is_none() {
 					if let Some(prs) = &self.pull_requests {
 						let mut res = Vec::new();
 						for pr in prs.iter() {
-							res.extend(
-								github::get_pr_authors(
-									token,
-									github_repo,
-									pr
-								).await?
-							)
+							res.extend(provider.pull_request_authors(*pr).await?)
 						}
-						github_coauthors.insert(key, res.clone());
+						github_coauthors.lock().await.insert(key, res.clone());
 						self.github_coauthors = Some(res);
 					}
 				} else {
-					self.github_coauthors = coauthors.cloned();
+					self.github_coauthors = coauthors;
 				};
 			}
 		}
@@ -433,6 +993,84 @@ impl Commit<'_> {
 		Ok(())
 	}
 
+	/// Resolves the GitLab information of this commit.
+	pub async fn resolve_gitlab(
+		&mut self,
+		config: &GitlabConfig,
+		project: &str,
+		gitlab_usernames: &mut HashMap<String, Option<String>>,
+		mr_participants: &mut HashMap<u32, Vec<String>>,
+	) -> Result<()> {
+		if config.resolve_authors.unwrap_or(false) {
+			if let Some(email) = self.author.email.as_deref() {
+				self.gitlab_author = match gitlab_usernames.get(email) {
+					Some(username) => username.clone(),
+					None => {
+						let username =
+							gitlab::get_username_by_email(config, email).await?;
+						gitlab_usernames.insert(email.to_string(), username.clone());
+						username
+					}
+				};
+			}
+		}
+		if config.resolve_mrs.unwrap_or(false) {
+			let merge_requests = gitlab::get_merge_requests_associated_with_commit(
+				config, project, &self.id,
+			)
+			.await?;
+			let mut participants = Vec::new();
+			for merge_request in &merge_requests {
+				let usernames = match mr_participants.entry(merge_request.iid) {
+					Entry::Occupied(entry) => entry.into_mut(),
+					Entry::Vacant(entry) => entry.insert(
+						gitlab::get_merge_request_participants(
+							config,
+							project,
+							merge_request.iid,
+						)
+						.await?,
+					),
+				};
+				for username in usernames.iter() {
+					if !participants.contains(username) {
+						participants.push(username.to_string());
+					}
+				}
+			}
+			self.merge_requests =
+				Some(merge_requests.iter().map(|v| v.iid).collect());
+			self.mr_participants = Some(participants);
+		}
+		Ok(())
+	}
+
+	/// Returns the signatures of the author and co-authors.
+	pub fn signatures(&self) -> impl Iterator<Item = &Signature> {
+		iter::once(&self.author).chain(&self.coauthors)
+	}
+
+	/// Resolves the Gerrit change of this commit from its `Change-Id`
+	/// trailer.
+	pub async fn resolve_gerrit(&mut self, config: &GerritConfig) -> Result<()> {
+		let (base_url, change_id) =
+			match (&config.url, gerrit::change_id(&self.message)) {
+				(Some(base_url), Some(change_id)) => (base_url, change_id),
+				_ => return Ok(()),
+			};
+		let owner = if config.resolve_owners.unwrap_or(false) {
+			gerrit::get_change_owner(config, change_id).await?
+		} else {
+			None
+		};
+		self.gerrit_change = Some(GerritChange {
+			id: change_id.to_string(),
+			url: gerrit::review_url(base_url, change_id),
+			owner,
+		});
+		Ok(())
+	}
+
 	pub fn authors(&self) -> Vec<String> {
 		let mut authors = Vec::new();
 		if let Some(github_author) = &self.github_author {
@@ -455,6 +1093,22 @@ impl Commit<'_> {
 		authors
 	}
 
+	/// Returns the group of the commit followed by the groups appended by the
+	/// commit parsers.
+	pub fn groups(&self) -> Vec<Symbol> {
+		let mut groups = self.groups.clone();
+		if let Some(group) = self
+			.group
+			.clone()
+			.or_else(|| self.conv.as_ref().map(|v| Symbol::from(v.type_().as_str())))
+		{
+			if !groups.contains(&group) {
+				groups.insert(0, group);
+			}
+		}
+		groups
+	}
+
 	pub fn pull_requests(&self) -> Vec<u32> {
 		self.pull_requests.clone().unwrap_or_default()
 	}
@@ -481,6 +1135,31 @@ impl Serialize for Commit<'_> {
 			}
 		}
 
+		/// A wrapper to serialize the issues with their `id`, `url` and `type`.
+		struct SerializeIssues<'a>(&'a [Ticket]);
+		impl Serialize for SerializeIssues<'_> {
+			fn serialize<S>(
+				&self,
+				serializer: S,
+			) -> std::result::Result<S::Ok, S::Error>
+			where
+				S: Serializer,
+			{
+				#[derive(serde::Serialize)]
+				struct Issue<'a> {
+					id:   &'a str,
+					url:  &'a str,
+					#[serde(rename = "type")]
+					kind: Option<&'a str>,
+				}
+				serializer.collect_seq(self.0.iter().map(|issue| Issue {
+					id:   &issue.key,
+					url:  &issue.url,
+					kind: issue.kind.as_deref(),
+				}))
+			}
+		}
+
 		let mut commit = serializer.serialize_struct("Commit", 9)?;
 		commit.serialize_field("id", &self.id)?;
 		match &self.conv {
@@ -490,7 +1169,7 @@ impl Serialize for Commit<'_> {
 				commit.serialize_field("footers", &SerializeFooters(self))?;
 				commit.serialize_field(
 					"group",
-					self.group.as_ref().unwrap_or(&conv.type_().to_string()),
+					self.group.as_deref().unwrap_or(conv.type_().as_str()),
 				)?;
 				commit.serialize_field(
 					"breaking_description",
@@ -515,11 +1194,27 @@ impl Serialize for Commit<'_> {
 				)?;
 			}
 		}
+		commit.serialize_field("groups", &self.groups())?;
 		commit.serialize_field("links", &self.links)?;
+		commit.serialize_field("issues", &SerializeIssues(&self.issues))?;
+		commit.serialize_field("fields", &self.fields)?;
 		commit.serialize_field("author", &self.author)?;
 		commit.serialize_field("coauthors", &self.coauthors)?;
 		commit.serialize_field("committer", &self.committer)?;
 		commit.serialize_field("pull_requests", &self.pull_requests)?;
+		commit.serialize_field("pr", &self.pr)?;
+		commit.serialize_field("gitlab_author", &self.gitlab_author)?;
+		commit.serialize_field("merge_requests", &self.merge_requests)?;
+		commit.serialize_field("mr_participants", &self.mr_participants)?;
+		commit.serialize_field("patchsets", &self.patchsets)?;
+		commit.serialize_field("gerrit_change", &self.gerrit_change)?;
+		commit.serialize_field("cherry_pick_of", &self.cherry_pick_of)?;
+		commit.serialize_field("parents", &self.parents)?;
+		commit.serialize_field("hidden_references", &self.hidden_references)?;
+		commit.serialize_field("fixups", &self.fixups)?;
+		commit.serialize_field("tickets", &self.tickets)?;
+		commit.serialize_field("signature", &self.signature)?;
+		commit.serialize_field("is_merge", &self.is_merge())?;
 		commit.serialize_field("conventional", &self.conv.is_some())?;
 		commit.end()
 	}
@@ -551,16 +1246,412 @@ mod test {
 			&[CommitParser {
 				message:       Regex::new("test*").ok(),
 				body:          None,
+				breaking:      None,
+				merge:         None,
 				group:         Some(String::from("test_group")),
 				default_scope: Some(String::from("test_scope")),
 				scope:         None,
 				skip:          None,
+				priority:      None,
+				append:        None,
+			}],
+			false,
+			false,
+			ParserMatch::First,
+		)?;
+		assert_eq!(Some(Symbol::from("test_group")), commit.group);
+		assert_eq!(Some(Symbol::from("test_scope")), commit.default_scope);
+		let mut commit = test_cases[0].0.clone();
+		commit.default_scope = Some(Symbol::from("core"));
+		let commit = commit.parse(
+			&[CommitParser {
+				message:       Regex::new("test*").ok(),
+				body:          None,
+				breaking:      None,
+				merge:         None,
+				group:         Some(String::from("test_group")),
+				default_scope: None,
+				scope:         None,
+				skip:          None,
+				priority:      None,
+				append:        None,
 			}],
 			false,
 			false,
+			ParserMatch::First,
 		)?;
-		assert_eq!(Some(String::from("test_group")), commit.group);
-		assert_eq!(Some(String::from("test_scope")), commit.default_scope);
+		assert_eq!(Some(Symbol::from("core")), commit.default_scope);
+		Ok(())
+	}
+
+	#[test]
+	fn parse_breaking() -> Result<()> {
+		let parsers = [
+			CommitParser {
+				message:       Regex::new("^feat").ok(),
+				body:          None,
+				breaking:      Some(true),
+				merge:         None,
+				group:         Some(String::from("Breaking Features")),
+				default_scope: None,
+				scope:         None,
+				skip:          None,
+				priority:      None,
+				append:        None,
+			},
+			CommitParser {
+				message:       None,
+				body:          None,
+				breaking:      Some(true),
+				merge:         None,
+				group:         Some(String::from("Breaking Changes")),
+				default_scope: None,
+				scope:         None,
+				skip:          None,
+				priority:      None,
+				append:        None,
+			},
+			CommitParser {
+				message:       Regex::new("^feat").ok(),
+				body:          None,
+				breaking:      None,
+				merge:         None,
+				group:         Some(String::from("Features")),
+				default_scope: None,
+				scope:         None,
+				skip:          None,
+				priority:      None,
+				append:        None,
+			},
+		];
+		for (message, group) in [
+			("feat!: add xyz", Some("Breaking Features")),
+			("fix!: fix xyz", Some("Breaking Changes")),
+			("feat: add abc", Some("Features")),
+			("fix: fix abc", None),
+		] {
+			let commit = Commit::new(String::from("123123"), String::from(message))
+				.into_conventional()?
+				.parse(&parsers, false, false, ParserMatch::First)?;
+			assert_eq!(group.map(Symbol::from), commit.group);
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn parse_merge() -> Result<()> {
+		let parsers = [CommitParser {
+			message:       None,
+			body:          None,
+			breaking:      None,
+			merge:         Some(true),
+			group:         Some(String::from("Merges")),
+			default_scope: None,
+			scope:         None,
+			skip:          None,
+			priority:      None,
+			append:        None,
+		}];
+		let commit = Commit {
+			parents: vec![String::from("abc123"), String::from("def456")],
+			..Commit::new(String::from("123123"), String::from("Merge branch 'xyz'"))
+		};
+		assert!(commit.is_merge());
+		assert_eq!(
+			Some(Symbol::from("Merges")),
+			commit
+				.parse(&parsers, false, true, ParserMatch::First)?
+				.group
+		);
+		let commit = Commit {
+			parents: vec![String::from("abc123")],
+			..Commit::new(String::from("123123"), String::from("Merge branch 'xyz'"))
+		};
+		assert!(!commit.is_merge());
+		assert!(commit
+			.parse(&parsers, false, true, ParserMatch::First)
+			.is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn parse_match_modes() -> Result<()> {
+		let parsers = [
+			CommitParser {
+				message:       Regex::new("^feat").ok(),
+				body:          None,
+				breaking:      None,
+				merge:         None,
+				group:         Some(String::from("Features")),
+				default_scope: None,
+				scope:         None,
+				skip:          None,
+				priority:      None,
+				append:        None,
+			},
+			CommitParser {
+				message:       Regex::new("button").ok(),
+				body:          None,
+				breaking:      None,
+				merge:         None,
+				group:         None,
+				default_scope: None,
+				scope:         Some(String::from("ui")),
+				skip:          None,
+				priority:      Some(1),
+				append:        None,
+			},
+			CommitParser {
+				message:       Regex::new("button").ok(),
+				body:          None,
+				breaking:      None,
+				merge:         None,
+				group:         Some(String::from("Components")),
+				default_scope: None,
+				scope:         Some(String::from("components")),
+				skip:          None,
+				priority:      Some(2),
+				append:        None,
+			},
+		];
+		let commit =
+			Commit::new(String::from("123123"), String::from("feat: button"));
+		let parse = |mode| -> Result<(Option<Symbol>, Option<Symbol>)> {
+			let commit = commit.clone().parse(&parsers, false, false, mode)?;
+			Ok((commit.group, commit.scope))
+		};
+		assert_eq!(
+			(Some(Symbol::from("Features")), None),
+			parse(ParserMatch::First)?
+		);
+		assert_eq!(
+			(
+				Some(Symbol::from("Components")),
+				Some(Symbol::from("components"))
+			),
+			parse(ParserMatch::HighestPriority)?
+		);
+		assert_eq!(
+			(
+				Some(Symbol::from("Components")),
+				Some(Symbol::from("components"))
+			),
+			parse(ParserMatch::All)?
+		);
+		let commit = Commit::new(String::from("123123"), String::from("feat: xyz"));
+		assert_eq!(
+			(Some(Symbol::from("Features")), None),
+			commit
+				.parse(&parsers[..2], false, false, ParserMatch::All)
+				.map(|v| (v.group, v.scope))?
+		);
+		let commit =
+			Commit::new(String::from("123123"), String::from("feat: add button"));
+		assert_eq!(
+			(Some(Symbol::from("Features")), Some(Symbol::from("ui"))),
+			commit
+				.parse(&parsers[..2], false, false, ParserMatch::All)
+				.map(|v| (v.group, v.scope))?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn parse_appending_groups() -> Result<()> {
+		let parsers = [
+			CommitParser {
+				message:       None,
+				body:          None,
+				breaking:      Some(true),
+				merge:         None,
+				group:         Some(String::from("Breaking")),
+				default_scope: None,
+				scope:         None,
+				skip:          None,
+				priority:      None,
+				append:        Some(true),
+			},
+			CommitParser {
+				message:       Regex::new("^feat").ok(),
+				body:          None,
+				breaking:      None,
+				merge:         None,
+				group:         Some(String::from("Features")),
+				default_scope: None,
+				scope:         None,
+				skip:          None,
+				priority:      None,
+				append:        None,
+			},
+		];
+		let commit = Commit::new(String::from("123123"), String::from("feat!: xyz"))
+			.into_conventional()?
+			.parse(&parsers, false, true, ParserMatch::First)?;
+		assert_eq!(Some(Symbol::from("Features")), commit.group);
+		assert_eq!(vec!["Features", "Breaking"], commit.groups());
+		assert_eq!(
+			serde_json::json!(["Features", "Breaking"]),
+			serde_json::to_value(&commit)?["groups"]
+		);
+		let commit = Commit::new(String::from("123123"), String::from("fix!: xyz"))
+			.into_conventional()?
+			.parse(&parsers, false, true, ParserMatch::First)?;
+		assert_eq!(Some(Symbol::from("Breaking")), commit.group);
+		assert_eq!(vec!["Breaking"], commit.groups());
+		Ok(())
+	}
+
+	#[test]
+	fn lint_commits() {
+		let config = GitConfig {
+			conventional_commits: Some(true),
+			commit_parsers: Some(vec![
+				CommitParser {
+					message:       Regex::new("^feat").ok(),
+					body:          None,
+					breaking:      None,
+					merge:         None,
+					group:         Some(String::from("Features")),
+					default_scope: None,
+					scope:         None,
+					skip:          None,
+					priority:      None,
+					append:        None,
+				},
+				CommitParser {
+					message:       Regex::new("^chore").ok(),
+					body:          None,
+					breaking:      None,
+					merge:         None,
+					group:         None,
+					default_scope: None,
+					scope:         None,
+					skip:          Some(true),
+					priority:      None,
+					append:        None,
+				},
+			]),
+			..GitConfig::default()
+		};
+		for (message, problems) in [
+			("feat: add xyz", 0),
+			("chore: bump xyz", 0),
+			("fix: fix xyz", 1),
+			("update xyz", 1),
+		] {
+			assert_eq!(
+				problems,
+				Commit::new(String::from("123123"), String::from(message))
+					.lint(&config)
+					.len(),
+				"{message}"
+			);
+		}
+		let mut merge_commit =
+			Commit::new(String::from("123123"), String::from("Merge branch 'xyz'"));
+		merge_commit.parents = vec![String::from("1"), String::from("2")];
+		assert!(merge_commit.lint(&config).is_empty());
+		for (config, message, problems) in [
+			(
+				GitConfig {
+					filter_commits: Some(false),
+					..config.clone()
+				},
+				"fix: fix xyz",
+				vec!["Does not match any commit parser"],
+			),
+			(
+				GitConfig {
+					filter_unconventional: Some(false),
+					..config.clone()
+				},
+				"update xyz",
+				vec![],
+			),
+			(
+				GitConfig {
+					emojis: Some(EmojiMode::Strip),
+					..config.clone()
+				},
+				"\u{2728} feat: add xyz",
+				vec![],
+			),
+		] {
+			assert_eq!(
+				problems,
+				Commit::new(String::from("123123"), String::from(message))
+					.lint(&config),
+				"{message}"
+			);
+		}
+	}
+
+	#[test]
+	fn references() {
+		let commit =
+			Commit::new(String::from("1231231"), String::from("feat: add xyz"));
+		for message in [
+			"Revert \"feat: add xyz\"",
+			"revert: undo xyz\n\nThis reverts commit 1231231.",
+			"fixup! feat: add xyz",
+			"squash! feat: add xyz",
+		] {
+			assert!(Commit::new(String::from("124124"), String::from(message))
+				.references(&commit));
+		}
+		assert!(!Commit::new(
+			String::from("124124"),
+			String::from("fixup! feat: add abc")
+		)
+		.references(&commit));
+		for message in [
+			"Revert \"feat: add xyz\"",
+			"revert: feat: add xyz",
+			"revert(app)!: undo xyz\n\nThis reverts commit 1231231.",
+		] {
+			let revert = Commit::new(String::from("124124"), String::from(message));
+			assert!(revert.is_revert());
+			assert!(revert.reverts(&commit));
+		}
+		let fixup = Commit::new(
+			String::from("124124"),
+			String::from("fixup! feat: add xyz"),
+		);
+		assert!(!fixup.is_revert());
+		assert!(!fixup.reverts(&commit));
+	}
+
+	#[test]
+	fn normalize() -> Result<()> {
+		let normalizer = EntryNormalizer {
+			capitalize:      Some(true),
+			trailing_period: Some(TrailingPeriod::Remove),
+			dictionary:      Some(HashMap::from([(
+				String::from("teh"),
+				String::from("the"),
+			)])),
+		};
+		let commit = Commit::new(
+			String::from("123123"),
+			String::from("feat(app)!: fix teh xyz.\n\nfix teh abc."),
+		)
+		.into_conventional()?
+		.normalize(&normalizer)?;
+		assert_eq!("feat(app)!: Fix the xyz\n\nfix teh abc.", commit.message);
+		assert_eq!(
+			Some("Fix the xyz"),
+			commit.conv.as_ref().map(|conv| conv.description())
+		);
+		assert!(commit.conv.as_ref().map_or(false, |conv| conv.breaking()));
+		let commit = Commit::new(String::from("124124"), String::from("wait..."))
+			.normalize(&normalizer)?;
+		assert_eq!("Wait...", commit.message);
+		let commit = Commit::new(String::from("125125"), String::from("Add abc"))
+			.normalize(&EntryNormalizer {
+				trailing_period: Some(TrailingPeriod::Add),
+				..EntryNormalizer::default()
+			})?;
+		assert_eq!("Add abc.", commit.message);
 		Ok(())
 	}
 
@@ -611,11 +1702,66 @@ mod test {
 			),
 		];
 		for (commit, footers) in &test_cases {
-			let commit = commit.process(&cfg).expect("commit should process");
+			let commit =
+				commit.process(&cfg, None).expect("commit should process");
 			assert_eq!(&commit.footers().collect::<Vec<_>>(), footers);
 		}
 	}
 
+	#[test]
+	fn redact_emails() -> Result<()> {
+		let cfg = crate::config::GitConfig {
+			conventional_commits: Some(true),
+			..Default::default()
+		};
+		let commit = Commit {
+			author: Signature {
+				name: Some(Symbol::from("Test User")),
+				email: Some(Symbol::from("test@example.com")),
+				..Default::default()
+			},
+			fields: BTreeMap::from([(
+				String::from("reviewer"),
+				String::from("reviewer@example.com"),
+			)]),
+			..Commit::new(
+				String::from("123123"),
+				String::from(
+					"feat: add xyz\n\nContact me at \
+					 test@example.com\n\nCo-authored-by: Other User \
+					 <other@example.com>",
+				),
+			)
+		}
+		.process(&cfg, None)?;
+
+		let mut stripped = commit.clone();
+		stripped.redact_emails(EmailRedaction::Strip, "");
+		assert!(!serde_json::to_string(&stripped)?.contains('@'));
+		assert!(!stripped.message.contains('@'));
+		assert_eq!(
+			vec![Footer {
+				token:     "Co-authored-by",
+				separator: ": ",
+				value:     "Other User",
+				breaking:  false,
+			}],
+			stripped.footers().collect::<Vec<_>>()
+		);
+
+		let mut hashed = commit;
+		hashed.redact_emails(EmailRedaction::Hash, "salt");
+		let hash = EmailRedaction::Hash
+			.redact("other@example.com", "salt")
+			.expect("email should be hashed");
+		assert!(!serde_json::to_string(&hashed)?.contains('@'));
+		assert_eq!(
+			Some(format!("Other User <{hash}>").as_str()),
+			hashed.footers().next().map(|footer| footer.value)
+		);
+		Ok(())
+	}
+
 	#[test]
 	fn parse_link() -> Result<()> {
 		let test_cases = vec![
@@ -671,6 +1817,104 @@ mod test {
 		Ok(())
 	}
 
+	#[test]
+	fn parse_issues() -> Result<()> {
+		let commit = Commit::new(
+			String::from("123123"),
+			String::from("feat: add xyz (PROJ-12)\n\nRefs PROJ-12, OPS-7, sc-5"),
+		)
+		.parse_issues(&[
+			IssueParser {
+				pattern: Regex::new(r"\b[A-Z]+-\d+\b")?,
+				url:     String::from("https://jira.example.com/browse/$0"),
+				tracker: Some(String::from("jira")),
+			},
+			IssueParser {
+				pattern: Regex::new(r"\bsc-(\d+)\b")?,
+				url:     String::from("https://app.shortcut.com/acme/story/$1"),
+				tracker: None,
+			},
+		]);
+		assert_eq!(
+			vec![
+				("PROJ-12", "https://jira.example.com/browse/PROJ-12"),
+				("OPS-7", "https://jira.example.com/browse/OPS-7"),
+				("5", "https://app.shortcut.com/acme/story/5"),
+			],
+			commit
+				.issues
+				.iter()
+				.map(|v| (v.key.as_str(), v.url.as_str()))
+				.collect::<Vec<(&str, &str)>>()
+		);
+		assert_eq!(Some(String::from("jira")), commit.issues[0].tracker);
+		assert_eq!(
+			serde_json::json!({
+				"id": "5",
+				"url": "https://app.shortcut.com/acme/story/5",
+				"type": null,
+			}),
+			serde_json::to_value(&commit)?["issues"][2]
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn parse_fields() -> Result<()> {
+		let parsers = HashMap::from([
+			(String::from("ticket"), FieldParser {
+				footer:  Some(String::from("Ticket")),
+				pattern: Some(Regex::new("\\[(?P<value>[A-Z]+-\\d+)\\]")?),
+			}),
+			(String::from("risk"), FieldParser {
+				footer:  Some(String::from("risk-level")),
+				pattern: None,
+			}),
+		]);
+		let commit = Commit::new(
+			String::from("123123"),
+			String::from("feat: add xyz\n\nTicket: ABC-1\nRisk-Level: high"),
+		)
+		.into_conventional()?
+		.parse_fields(&parsers)?;
+		assert_eq!(
+			BTreeMap::from([
+				(String::from("risk"), String::from("high")),
+				(String::from("ticket"), String::from("ABC-1")),
+			]),
+			commit.fields
+		);
+		let commit = Commit::new(
+			String::from("123123"),
+			String::from("[ABC-2] Update xyz\n\nrisk-level : low"),
+		)
+		.parse_fields(&parsers)?;
+		assert_eq!(
+			Some("ABC-2"),
+			commit.fields.get("ticket").map(|v| v.as_str())
+		);
+		assert_eq!(Some("low"), commit.fields.get("risk").map(|v| v.as_str()));
+		Ok(())
+	}
+
+	#[test]
+	fn deprecations() -> Result<()> {
+		for (message, expected) in [
+			("deprecate: deprecate `xyz`", vec!["deprecate `xyz`"]),
+			(
+				"feat(api): add abc\n\nDeprecated: `xyz` is replaced by `abc`",
+				vec!["`xyz` is replaced by `abc`"],
+			),
+			("Deprecated: xyz", vec!["xyz"]),
+			("chore: remove deprecated xyz", vec![]),
+		] {
+			let commit = Commit::new(String::from("abc"), message.to_string());
+			let commit = commit.clone().into_conventional().unwrap_or(commit);
+			assert_eq!(expected, commit.deprecations());
+		}
+		Ok(())
+	}
+
 	#[test]
 	fn parse_commit() {
 		assert_eq!(
@@ -703,4 +1947,134 @@ mod test {
 			Commit::from(String::from("thisisinvalidsha1 style: add formatting"))
 		);
 	}
+
+	#[test]
+	fn decode_messages() {
+		assert_eq!(
+			"feat: add café",
+			decode_message("abc", b"feat: add caf\xe9", Some("ISO-8859-1"))
+		);
+		assert_eq!(
+			"feat: add café",
+			decode_message("abc", "feat: add café".as_bytes(), None)
+		);
+		assert_eq!(
+			"feat: add caf\u{FFFD}",
+			decode_message("abc", b"feat: add caf\xe9", Some("xyz"))
+		);
+	}
+
+	#[tokio::test]
+	async fn resolve_pull_request() -> Result<()> {
+		use crate::provider::BoxFuture;
+		#[derive(Debug)]
+		struct Provider;
+		impl RemoteProvider for Provider {
+			fn name(&self) -> &'static str {
+				"test"
+			}
+			fn repository(&self) -> &str {
+				"owner/repo"
+			}
+			fn api_url(&self) -> &str {
+				"https://example.com"
+			}
+			fn commit_author<'a>(
+				&'a self,
+				_: &'a str,
+			) -> BoxFuture<'a, Option<String>> {
+				Box::pin(async { Ok(None) })
+			}
+			fn commit_pull_requests<'a>(
+				&'a self,
+				_: &'a str,
+			) -> BoxFuture<'a, Vec<u32>> {
+				Box::pin(async { Ok(vec![42]) })
+			}
+			fn pull_request_authors(&self, _: u32) -> BoxFuture<'_, Vec<String>> {
+				Box::pin(async { Ok(Vec::new()) })
+			}
+			fn pull_request(
+				&self,
+				number: u32,
+			) -> BoxFuture<'_, Option<PullRequest>> {
+				Box::pin(async move {
+					Ok(Some(PullRequest {
+						number,
+						comments: 3,
+						reactions: 7,
+						..PullRequest::default()
+					}))
+				})
+			}
+		}
+		let mut commit =
+			Commit::new(String::from("123123"), String::from("feat: add xyz"));
+		commit.resolve_pull_request(&Provider).await?;
+		assert_eq!(Some(vec![42]), commit.pull_requests);
+		let pr = commit.pr.expect("pull request not found");
+		assert_eq!((42, 3, 7), (pr.number, pr.comments, pr.reactions));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn resolve_remote_concurrently() -> Result<()> {
+		use crate::provider::BoxFuture;
+		#[derive(Debug)]
+		struct Provider;
+		impl RemoteProvider for Provider {
+			fn name(&self) -> &'static str {
+				"test"
+			}
+			fn repository(&self) -> &str {
+				"owner/repo"
+			}
+			fn api_url(&self) -> &str {
+				"https://example.com"
+			}
+			fn commit_author<'a>(
+				&'a self,
+				commit_sha: &'a str,
+			) -> BoxFuture<'a, Option<String>> {
+				Box::pin(async move { Ok(Some(format!("user-{commit_sha}"))) })
+			}
+			fn commit_pull_requests<'a>(
+				&'a self,
+				_: &'a str,
+			) -> BoxFuture<'a, Vec<u32>> {
+				Box::pin(async { Ok(Vec::new()) })
+			}
+			fn pull_request_authors(&self, _: u32) -> BoxFuture<'_, Vec<String>> {
+				Box::pin(async { Ok(Vec::new()) })
+			}
+		}
+		let usernames = Mutex::new(HashMap::new());
+		let coauthors = Mutex::new(HashMap::new());
+		let mut commits =
+			[("a", "a@example.com"), ("b", "b@example.com")].map(|(id, email)| {
+				let mut commit =
+					Commit::new(String::from(id), String::from("feat: add xyz"));
+				commit.author.email = Some(Symbol::from(email));
+				commit
+			});
+		let [first, second] = &mut commits;
+		let (first, second) = tokio::join!(
+			first.resolve_remote(&Provider, true, &usernames, &coauthors),
+			second.resolve_remote(&Provider, true, &usernames, &coauthors),
+		);
+		first?;
+		second?;
+		assert_eq!(
+			vec![Some("user-a"), Some("user-b")],
+			commits
+				.iter()
+				.map(|v| v.github_author.as_deref())
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			Some(&String::from("user-b")),
+			usernames.lock().await.get("b@example.com")
+		);
+		Ok(())
+	}
 }
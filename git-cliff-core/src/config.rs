@@ -1,41 +1,369 @@
-use crate::error::Result;
+use crate::error::{
+	Error,
+	Result,
+};
+use crate::retry::SendWithRetry;
+use glob::Pattern;
+use log::warn;
 use regex::{
 	Regex,
 	RegexBuilder,
 };
+use sha2::{
+	Digest,
+	Sha256,
+};
+use std::collections::HashMap;
+use std::env;
 use std::ffi::OsStr;
 use std::fs;
-use std::path::Path;
+use std::path::{
+	Path,
+	PathBuf,
+};
 
 /// Regex for matching the metadata in Cargo.toml
 const CARGO_METADATA_REGEX: &str =
 	r"^\[(?:workspace|package)\.metadata\.git\-cliff\.";
 
 /// Configuration values.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Config {
 	/// Configuration values about changelog generation.
 	#[serde(default)]
-	pub changelog: ChangelogConfig,
+	pub changelog:     ChangelogConfig,
 	/// Configuration values about git.
 	#[serde(default)]
-	pub git:       GitConfig,
+	pub git:           GitConfig,
 	/// Configuration values about github.
 	#[serde(default)]
-	pub github:    GithubConfig,
+	pub github:        GithubConfig,
+	/// Configuration values about GitLab.
+	#[serde(default)]
+	pub gitlab:        GitlabConfig,
+	/// Configuration values about Gitea.
+	#[serde(default)]
+	pub gitea:         GiteaConfig,
+	/// Configuration values about Gerrit.
+	#[serde(default)]
+	pub gerrit:        GerritConfig,
+	/// Configuration values about issue trackers.
+	#[serde(default)]
+	pub trackers:      TrackersConfig,
+	/// Configuration values about the remote repository.
+	#[serde(default)]
+	pub remote:        RemoteConfig,
+	/// Configuration values about the cache.
+	#[serde(default)]
+	pub cache:         CacheConfig,
+	/// Configuration values about the template.
+	#[serde(default)]
+	pub template:      TemplateConfig,
+	/// Packages of the monorepo.
+	#[serde(default)]
+	pub packages:      Vec<PackageConfig>,
+	/// Base config to extend (a path, a URL or `github:<owner>/<repo>/<path>`).
+	pub extends:       Option<String>,
+	/// URL of the base config file that is pinned to `config_sha256` (e.g.
+	/// the shared config of an organization).
+	pub config_url:    Option<String>,
+	/// SHA-256 checksum of the config file at `config_url`.
+	pub config_sha256: Option<String>,
+}
+
+/// Package of a monorepo that has its own changelog.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PackageConfig {
+	/// Name of the package.
+	pub name:          String,
+	/// Glob patterns of the paths that belong to the package.
+	pub include_paths: Option<Vec<String>>,
+	/// Glob patterns of the paths to exclude from the package.
+	pub exclude_paths: Option<Vec<String>>,
+	/// Glob pattern(s) for the git tags of the package.
+	pub tag_pattern:   Option<TagPatterns>,
+	/// Path of the changelog of the package.
+	pub output:        Option<String>,
 }
 
 /// Changelog configuration.
 #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ChangelogConfig {
 	/// Changelog header.
-	pub header: Option<String>,
+	pub header:   Option<String>,
 	/// Changelog body, template.
-	pub body:   Option<String>,
+	pub body:     Option<String>,
 	/// Changelog footer.
-	pub footer: Option<String>,
+	pub footer:   Option<String>,
 	/// Trim the template.
-	pub trim:   Option<bool>,
+	pub trim:     Option<bool>,
+	/// PDF export configuration.
+	pub pdf:      Option<PdfConfig>,
+	/// Configuration of the commits without scope in the default layout.
+	pub unscoped: Option<UnscopedConfig>,
+
+	/// Maximum number of commits to list in each group.
+	pub max_entries_per_group:    Option<usize>,
+	/// Regexes for prioritizing the commits to list in each group.
+	#[serde(with = "serde_regex", default)]
+	pub entry_priority:           Option<Vec<Regex>>,
+	/// Additional variables to merge into the template context.
+	pub extra_context:            Option<HashMap<String, serde_json::Value>>,
+	/// Glob patterns of the environment variables that are allowed to be read
+	/// in the template.
+	pub allowed_env:              Option<Vec<String>>,
+	/// Whether to include the number of commits in each group of the previous
+	/// release.
+	pub previous_summary:         Option<bool>,
+	/// Whether to render the unreleased changes even if there are none.
+	pub render_always_unreleased: Option<bool>,
+	/// Glob patterns of the artifact files of the releases for computing
+	/// their checksums.
+	pub artifacts:                Option<Vec<String>>,
+	/// Line ending of the output.
+	pub line_ending:              Option<LineEnding>,
+	/// Depth of the headings of the nested scopes (e.g. `ui/button`) in the
+	/// default layout.
+	pub scope_depth:              Option<usize>,
+	/// Fields of the commits to serialize into the context output.
+	pub context_fields:           Option<ContextFields>,
+	/// Whether to redact the email addresses of the commit authors.
+	pub redact_emails:            Option<bool>,
+	/// Redaction of the email addresses.
+	pub email_redaction:          Option<EmailRedaction>,
+	/// Salt that is prepended to the email addresses before hashing them.
+	pub email_salt:               Option<String>,
+	/// Changelog fragments of the contributors.
+	pub fragments:                Option<FragmentsConfig>,
+	/// Path of the changelog header file.
+	pub header_path:              Option<String>,
+	/// Path of the changelog body (template) file.
+	pub body_path:                Option<String>,
+	/// Path of the changelog footer file.
+	pub footer_path:              Option<String>,
+	/// SHA-256 checksum of the header file of a remote config.
+	pub header_sha256:            Option<String>,
+	/// SHA-256 checksum of the body file of a remote config.
+	pub body_sha256:              Option<String>,
+	/// SHA-256 checksum of the footer file of a remote config.
+	pub footer_sha256:            Option<String>,
+	/// Layout of the default template.
+	pub layout:                   Option<Layout>,
+	/// Configuration of the groups that are rendered as tables in the
+	/// default template.
+	pub tables:                   Option<TablesConfig>,
+	/// Format of the changelog.
+	pub format:                   Option<ChangelogFormat>,
+	/// Markers of the migration notes in the commit bodies.
+	pub migration_markers:        Option<Vec<String>>,
+	/// Whether to render the cumulative appendix of the deprecations.
+	pub deprecations:             Option<bool>,
+	/// Configuration of the public API diff of the releases.
+	pub api_diff:                 Option<ApiDiffConfig>,
+	/// Commands for computing the metrics of the releases (e.g. binary size).
+	pub metric_commands:          Option<HashMap<String, String>>,
+	/// Whether to flag the releases whose regenerated notes differ from the
+	/// published ones.
+	pub detect_edits:             Option<bool>,
+	/// Locale of the dates and the lists (e.g. `ja`), requires the `icu`
+	/// feature.
+	pub locale:                   Option<String>,
+}
+
+impl ChangelogConfig {
+	/// Reads the header, body and footer from their files unless they are set
+	/// inline.
+	///
+	/// Relative paths are resolved against the given directory.
+	pub fn read_templates(&mut self, dir: &Path) -> Result<()> {
+		for (template, path) in [
+			(&mut self.header, &self.header_path),
+			(&mut self.body, &self.body_path),
+			(&mut self.footer, &self.footer_path),
+		] {
+			if let (None, Some(path)) = (&template, path) {
+				*template = Some(fs::read_to_string(dir.join(path))?);
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Redaction of the email addresses.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailRedaction {
+	/// Remove the email addresses.
+	Strip,
+	/// Replace the email addresses with their SHA-256 hashes.
+	Hash,
+}
+
+impl EmailRedaction {
+	/// Returns the redacted email address.
+	///
+	/// The hash is computed from the salt and the lowercase email so that it
+	/// is stable but cannot be looked up without the salt.
+	pub fn redact(&self, email: &str, salt: &str) -> Option<String> {
+		match self {
+			Self::Strip => None,
+			Self::Hash => {
+				let mut hasher = Sha256::new();
+				hasher.update(salt.as_bytes());
+				hasher.update(email.trim().to_lowercase().as_bytes());
+				Some(format!("{:x}", hasher.finalize()))
+			}
+		}
+	}
+}
+
+/// Configuration of the changelog fragments (e.g. `changes.d/123.feature.md`).
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FragmentsConfig {
+	/// Directory of the fragments.
+	pub path:  Option<String>,
+	/// Commit types of the fragment types.
+	pub types: Option<HashMap<String, String>>,
+}
+
+/// Selection of the commit fields in the context output.
+///
+/// Nested fields are separated by dots, e.g. `author.email`.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ContextFields {
+	/// Fields to include (all fields if not set).
+	pub include: Option<Vec<String>>,
+	/// Fields to omit.
+	pub exclude: Option<Vec<String>>,
+}
+
+/// Line ending of the generated changelog.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+	/// `\n`
+	Lf,
+	/// `\r\n`
+	Crlf,
+	/// Line ending of the current platform.
+	Native,
+}
+
+impl LineEnding {
+	/// Returns the text with its line endings converted.
+	pub fn apply(&self, text: &str) -> String {
+		let text = text.replace("\r\n", "\n");
+		match self {
+			Self::Crlf => text.replace('\n', "\r\n"),
+			Self::Native if cfg!(target_os = "windows") => {
+				text.replace('\n', "\r\n")
+			}
+			_ => text,
+		}
+	}
+}
+
+/// Configuration of the bucket for the commits without scope.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UnscopedConfig {
+	/// Heading of the bucket.
+	pub name:       Option<String>,
+	/// Position of the bucket among the scopes.
+	pub position:   Option<BucketPosition>,
+	/// Scope to merge the commits without scope into.
+	pub merge_into: Option<String>,
+}
+
+/// Layout of the default template.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Layout {
+	/// Sections of the groups (i.e. the commit types) with the scopes nested
+	/// inside.
+	TypeFirst,
+	/// Sections of the scopes (i.e. the components) with the groups nested
+	/// inside.
+	ScopeFirst,
+}
+
+/// Configuration of the tables in the default template.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TablesConfig {
+	/// Groups to render as tables (all groups if not set).
+	pub groups: Option<Vec<String>>,
+	/// Format of the tables.
+	pub format: Option<TableFormat>,
+}
+
+impl TablesConfig {
+	/// Returns whether the given group is rendered as a table.
+	///
+	/// The group is matched with or without its ordering prefix (e.g. `1. `).
+	pub fn contains(&self, group: &str) -> bool {
+		let name = group
+			.trim_start_matches(|c: char| c.is_numeric())
+			.trim_start_matches(". ");
+		self.groups.as_ref().map_or(true, |groups| {
+			groups.iter().any(|v| v == group || v == name)
+		})
+	}
+}
+
+/// Format of the tables.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TableFormat {
+	/// Markdown table.
+	Markdown,
+	/// HTML table.
+	Html,
+}
+
+/// Format of the changelog.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub enum ChangelogFormat {
+	/// [Keep a Changelog](https://keepachangelog.com/en/1.1.0/) with the
+	/// standard sections and the link references of the releases.
+	#[serde(rename = "keepachangelog")]
+	KeepAChangelog,
+}
+
+/// Position of a bucket.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum BucketPosition {
+	/// Before the other buckets.
+	First,
+	/// After the other buckets.
+	Last,
+}
+
+/// PDF export configuration.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PdfConfig {
+	/// Command for typesetting the Markdown changelog into PDF.
+	pub command:    Option<String>,
+	/// Path of the stylesheet to apply to the document.
+	pub stylesheet: Option<String>,
+}
+
+/// Public API diff configuration.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ApiDiffConfig {
+	/// Command for diffing the public API between two revisions.
+	pub command: Option<String>,
 }
 
 /// Git configuration.
@@ -49,46 +377,524 @@ pub struct GitConfig {
 	/// commit.
 	pub split_commits:         Option<bool>,
 
+	/// How to handle the emojis in commit messages.
+	pub emojis:                     Option<EmojiMode>,
 	/// Git commit preprocessors.
-	pub commit_preprocessors:     Option<Vec<CommitPreprocessor>>,
+	pub commit_preprocessors:       Option<Vec<CommitPreprocessor>>,
+	/// Path of the translation memory for the preprocessor commands.
+	pub translation_memory:         Option<String>,
 	/// Git commit parsers.
-	pub commit_parsers:           Option<Vec<CommitParser>>,
+	pub commit_parsers:             Option<Vec<CommitParser>>,
+	/// How to handle the commits that are matched by multiple parsers.
+	#[serde(rename = "match")]
+	pub parser_match:               Option<ParserMatch>,
 	/// Whether to protect all breaking changes from being skipped by a commit
 	/// parser.
-	pub protect_breaking_commits: Option<bool>,
+	pub protect_breaking_commits:   Option<bool>,
+	/// How to protect the skipped commits that are referenced by the reverts
+	/// or fixups of the kept commits.
+	pub protect_referenced_commits: Option<ReferenceProtection>,
+	/// How to handle the `fixup!` and `squash!` commits that are not
+	/// autosquashed.
+	pub fixups:                     Option<FixupMode>,
+	/// Whether to remove the reverted commits along with their reverts from
+	/// the releases.
+	pub filter_reverted:            Option<bool>,
 	/// Link parsers.
-	pub link_parsers:             Option<Vec<LinkParser>>,
+	pub link_parsers:               Option<Vec<LinkParser>>,
+	/// Parsers for extracting the issues of the trackers.
+	pub issue_parsers:              Option<Vec<IssueParser>>,
+	/// Parsers of the custom fields of the commits by their names.
+	pub field_parsers:              Option<HashMap<String, FieldParser>>,
 	/// Whether to filter out commits.
-	pub filter_commits:           Option<bool>,
-	/// Blob pattern for git tags.
-	pub tag_pattern:              Option<String>,
-	#[serde(with = "serde_regex", default)]
-	/// Regex to skip matched tags.
-	pub skip_tags:                Option<Regex>,
-	#[serde(with = "serde_regex", default)]
-	/// Regex to ignore matched tags.
-	pub ignore_tags:              Option<Regex>,
+	pub filter_commits:             Option<bool>,
+	/// Normalizer for the subjects of the entries.
+	pub entry_normalizer:           Option<EntryNormalizer>,
+	/// Blob pattern(s) for git tags.
+	pub tag_pattern:                Option<TagPatterns>,
+	/// Regex or glob pattern to skip matched tags.
+	pub skip_tags:                  Option<TagMatcher>,
+	/// How to handle the commits of the skipped tags.
+	pub skip_tags_mode:             Option<SkipTagsMode>,
+	/// Regex or glob pattern to ignore matched tags.
+	pub ignore_tags:                Option<TagMatcher>,
 	/// Whether to sort tags chronologically.
-	pub date_order:               Option<bool>,
+	pub date_order:                 Option<bool>,
+	/// Whether to use the creation date of tags as the release timestamp.
+	pub tag_date:                   Option<bool>,
+	/// Options for the signatures of the commits and tags.
+	pub signatures:                 Option<SignaturesConfig>,
+	/// How to handle the cherry-picks of the commits released on another
+	/// branch.
+	pub cherry_picks:               Option<CherryPickMode>,
+	/// Branches of the release lines for linking the releases to the previous
+	/// ones on the same branch.
+	pub release_lines:              Option<Vec<String>>,
+	/// How to handle the merge commits.
+	pub merge_commits:              Option<MergeCommits>,
+	/// Date to sort the commits and tags by and to use as the release
+	/// timestamp.
+	pub sort_by:                    Option<SortBy>,
 	/// Sorting of the commits inside sections.
-	pub sort_commits:             Option<String>,
+	pub sort_commits:               Option<String>,
 	/// Limit the number of commits included in the changelog.
-	pub limit_commits:            Option<usize>,
+	pub limit_commits:              Option<usize>,
+	/// Rules for inferring the scopes of the commits from the changed paths.
+	pub scope_from_paths:           Option<Vec<ScopePath>>,
+	/// Whether to compute the changed files and lines of the releases.
+	pub diff_stats:                 Option<bool>,
+}
+
+/// Signature configuration.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SignaturesConfig {
+	/// Command for verifying the signatures.
+	pub verify_command: Option<String>,
+}
+
+/// Rule for inferring the scope of the commits that change the matching
+/// paths.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScopePath {
+	/// Glob pattern of the paths.
+	pub path:  String,
+	/// Scope of the commits.
+	pub scope: String,
+}
+
+/// Handling of the commits that are matched by multiple commit parsers.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ParserMatch {
+	/// Use the first matching parser.
+	First,
+	/// Use all the matching parsers. Each field is set by the matching parser
+	/// with the highest priority that sets it.
+	All,
+	/// Use the matching parser with the highest priority.
+	HighestPriority,
+}
+
+/// Handling of the cherry-picked commits.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CherryPickMode {
+	/// Keep the commits and set the ID of the original commit.
+	Mark,
+	/// Drop the commits from the changelog.
+	Drop,
+}
+
+/// Handling of the commits that belong to the skipped tags.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SkipTagsMode {
+	/// Drop the commits from the changelog.
+	Drop,
+	/// Merge the commits into the next release that is not skipped.
+	Merge,
+}
+
+/// Handling of the merge commits.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeCommits {
+	/// Process the merge commits along with the merged commits.
+	Include,
+	/// Drop the merge commits.
+	Skip,
+	/// Process only the first-parent history, i.e. the merge commits instead
+	/// of the merged commits.
+	FirstParentOnly,
+}
+
+/// Protection of the skipped commits that are referenced by other commits.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferenceProtection {
+	/// Keep a stub entry of the referenced commit in the group of the
+	/// referencing commit.
+	Keep,
+	/// Add the ID of the referenced commit to the `hidden_references` of the
+	/// referencing commit.
+	Annotate,
+}
+
+/// Handling of the `fixup!`, `squash!` and `amend!` commits.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum FixupMode {
+	/// Remove the commits and add their IDs to the `fixups` of the commits
+	/// that they fix up.
+	Fold,
+	/// Remove the commits.
+	Drop,
+}
+
+/// Date of the commits.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+	/// Date when the commit was originally authored.
+	AuthorDate,
+	/// Date when the commit was committed (e.g. rebased) the last time.
+	CommitDate,
+}
+
+impl SortBy {
+	/// Returns the date of the given commit in seconds, from epoch.
+	pub fn timestamp(&self, commit: &git2::Commit) -> i64 {
+		match self {
+			Self::AuthorDate => commit.author().when().seconds(),
+			Self::CommitDate => commit.time().seconds(),
+		}
+	}
+}
+
+/// Handling of the emojis in commit messages.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum EmojiMode {
+	/// Replace the emoji shortcodes (e.g. `:sparkles:`) with emojis.
+	Replace,
+	/// Remove the emojis and emoji shortcodes.
+	Strip,
+}
+
+/// Glob pattern(s) for matching git tags.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum TagPatterns {
+	/// A single pattern.
+	Single(String),
+	/// Multiple patterns with optional labels.
+	Multiple(Vec<TagPattern>),
+}
+
+/// Glob pattern for matching git tags of a release channel.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TagPattern {
+	/// Glob pattern.
+	pub pattern: String,
+	/// Label of the matched releases, such as `stable` or `lts`.
+	pub channel: Option<String>,
+}
+
+impl TagPatterns {
+	/// Returns the glob patterns.
+	pub fn patterns(&self) -> Vec<&str> {
+		match self {
+			Self::Single(pattern) => vec![pattern.as_str()],
+			Self::Multiple(patterns) => {
+				patterns.iter().map(|v| v.pattern.as_str()).collect()
+			}
+		}
+	}
+
+	/// Returns the channel label of the first pattern that matches the tag.
+	pub fn channel(&self, tag: &str) -> Option<&str> {
+		match self {
+			Self::Single(_) => None,
+			Self::Multiple(patterns) => patterns
+				.iter()
+				.find(|v| {
+					Pattern::new(&v.pattern)
+						.map(|pattern| pattern.matches(tag))
+						.unwrap_or_default()
+				})
+				.and_then(|v| v.channel.as_deref()),
+		}
+	}
+}
+
+/// Matcher for git tags.
+///
+/// Deserialized from a regex string or a `{ glob = "..." }` table.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "TagMatcherDef", into = "TagMatcherDef")]
+pub enum TagMatcher {
+	/// Regular expression.
+	Regex(Regex),
+	/// Glob pattern.
+	Glob(Pattern),
+}
+
+/// Serialized representation of [`TagMatcher`].
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum TagMatcherDef {
+	/// Regular expression.
+	Regex(String),
+	/// Glob pattern.
+	Glob {
+		/// Pattern string.
+		glob: String,
+	},
+}
+
+impl TryFrom<TagMatcherDef> for TagMatcher {
+	type Error = Error;
+	fn try_from(value: TagMatcherDef) -> Result<Self> {
+		Ok(match value {
+			TagMatcherDef::Regex(regex) => Self::Regex(Regex::new(&regex)?),
+			TagMatcherDef::Glob { glob } => Self::Glob(Pattern::new(&glob)?),
+		})
+	}
+}
+
+impl From<TagMatcher> for TagMatcherDef {
+	fn from(value: TagMatcher) -> Self {
+		match value {
+			TagMatcher::Regex(regex) => Self::Regex(regex.to_string()),
+			TagMatcher::Glob(glob) => Self::Glob {
+				glob: glob.to_string(),
+			},
+		}
+	}
+}
+
+impl TagMatcher {
+	/// Returns `true` if the matcher has an empty pattern.
+	pub fn is_empty(&self) -> bool {
+		match self {
+			Self::Regex(regex) => regex.as_str().trim().is_empty(),
+			Self::Glob(glob) => glob.as_str().trim().is_empty(),
+		}
+	}
+
+	/// Returns `true` if the given tag is matched.
+	pub fn is_match(&self, tag: &str) -> bool {
+		match self {
+			Self::Regex(regex) => regex.is_match(tag),
+			Self::Glob(glob) => glob.matches(tag),
+		}
+	}
 }
 
 /// Github configuration.
 #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct GithubConfig {
+	/// URL of the Github API. For example, https://github.example.com/api/v3
+	pub api_url:           Option<String>,
 	/// Github repository name with owner. For example, torvalds/linux.
-	pub repository:      Option<String>,
+	pub repository:        Option<String>,
 	/// Whether to try to resolve the Github informations associated with the
 	/// authors of the commits.
-	pub resolve_authors: Option<bool>,
+	pub resolve_authors:   Option<bool>,
 	/// Whether to try to resolve the Github pull request links associated with
 	/// the commits.
+	pub resolve_prs:       Option<bool>,
+	/// Whether to fetch the comment and reaction counts of the pull requests.
+	pub resolve_reactions: Option<bool>,
+	/// Whether to fetch the published releases with their assets.
+	pub resolve_releases:  Option<bool>,
+}
+
+/// GitLab configuration.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GitlabConfig {
+	/// URL of the GitLab instance. For example, https://gitlab.example.com
+	pub url:             Option<String>,
+	/// Path of the project. For example, group/subgroup/project.
+	pub project:         Option<String>,
+	/// Access token for the GitLab API.
+	pub token:           Option<String>,
+	/// Whether to try to resolve the GitLab usernames of the commit authors.
+	pub resolve_authors: Option<bool>,
+	/// Whether to try to resolve the merge requests and their participants
+	/// associated with the commits.
+	pub resolve_mrs:     Option<bool>,
+}
+
+/// Gitea (or Forgejo) configuration.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GiteaConfig {
+	/// URL of the Gitea instance. For example, https://codeberg.org
+	pub url:             Option<String>,
+	/// Repository name with owner. For example, owner/repo.
+	pub repository:      Option<String>,
+	/// Access token for the Gitea API.
+	pub token:           Option<String>,
+	/// Whether to try to resolve the Gitea usernames of the commit authors.
+	pub resolve_authors: Option<bool>,
+	/// Whether to try to resolve the pull requests associated with the
+	/// commits.
 	pub resolve_prs:     Option<bool>,
 }
 
+/// Gerrit configuration.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GerritConfig {
+	/// URL of the Gerrit instance. For example, https://review.example.com
+	pub url:            Option<String>,
+	/// Username for the authenticated requests.
+	pub username:       Option<String>,
+	/// HTTP password (or access token) for the authenticated requests.
+	pub token:          Option<String>,
+	/// Whether to try to resolve the owners of the changes.
+	pub resolve_owners: Option<bool>,
+}
+
+/// Remote repository configuration.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RemoteConfig {
+	/// Web URL of the repository. For example, https://codeberg.org/owner/repo
+	pub url:         Option<String>,
+	/// Forge of the repository for the default link templates.
+	pub forge:       Option<Forge>,
+	/// Template of the commit links, e.g. `{url}/commit/{id}`.
+	pub commit_url:  Option<String>,
+	/// Template of the links for comparing two references, e.g.
+	/// `{url}/compare/{from}..{to}`.
+	pub compare_url: Option<String>,
+	/// Template of the links for the history of a reference, e.g.
+	/// `{url}/commits/{ref}`.
+	pub log_url:     Option<String>,
+	/// Retry policy of the requests to the remote APIs.
+	pub retry:       Option<RetryConfig>,
+}
+
+/// Retry policy of the requests.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RetryConfig {
+	/// Maximum number of the attempts of a request.
+	pub max_attempts: Option<u32>,
+	/// Base of the exponential backoff between the attempts in milliseconds.
+	pub backoff:      Option<u64>,
+	/// Status codes of the responses to retry.
+	pub retry_on:     Option<Vec<u16>>,
+}
+
+/// Software forge that hosts a repository.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Forge {
+	/// GitHub.
+	Github,
+	/// GitLab.
+	Gitlab,
+	/// Gitea, Forgejo and Codeberg.
+	Gitea,
+	/// SourceHut.
+	Sourcehut,
+	/// cgit.
+	Cgit,
+	/// Bitbucket.
+	Bitbucket,
+}
+
+/// Cache configuration.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CacheConfig {
+	/// Storage backend of the cache.
+	pub backend: Option<CacheBackend>,
+	/// Directory of the file cache.
+	pub path:    Option<String>,
+	/// URL of the Redis server.
+	pub url:     Option<String>,
+	/// Time to live of the cached lookups of the remote providers (e.g. the
+	/// Github usernames) and the issue trackers in seconds.
+	pub ttl:     Option<u64>,
+}
+
+/// Storage backend of the cache.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+	/// Files in a directory.
+	File,
+	/// Memory of the current process.
+	Memory,
+	/// Redis server.
+	Redis,
+}
+
+/// Template configuration.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TemplateConfig {
+	/// Commands of the external filters by their names.
+	pub functions:       Option<HashMap<String, String>>,
+	/// Partial templates by their names.
+	pub partials:        Option<HashMap<String, String>>,
+	/// Timeout of the rendering of a release in seconds (60 by default).
+	pub render_timeout:  Option<u64>,
+	/// Maximum size of the rendered output of a release in bytes (16 MiB by
+	/// default).
+	pub max_output_size: Option<usize>,
+}
+
+/// Issue trackers configuration.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrackersConfig {
+	/// Jira configuration.
+	pub jira:     Option<JiraConfig>,
+	/// Linear configuration.
+	pub linear:   Option<LinearConfig>,
+	/// Shortcut configuration.
+	pub shortcut: Option<ShortcutConfig>,
+}
+
+/// Jira configuration.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct JiraConfig {
+	/// Base URL of the Jira instance. For example, https://example.atlassian.net
+	pub url:     String,
+	/// Regex for matching the ticket keys such as `PROJ-123`.
+	#[serde(with = "serde_regex", default)]
+	pub pattern: Option<Regex>,
+	/// Whether to fetch the summaries of the tickets via the REST API.
+	pub fetch:   Option<bool>,
+	/// User (i.e. email) for the basic authentication of Jira Cloud.
+	pub user:    Option<String>,
+	/// API token or personal access token.
+	pub token:   Option<String>,
+}
+
+/// Linear configuration.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LinearConfig {
+	/// Workspace (i.e. URL key of the organization) for the ticket links.
+	pub workspace: String,
+	/// Regex for matching the ticket identifiers such as `ENG-123`.
+	#[serde(with = "serde_regex", default)]
+	pub pattern:   Option<Regex>,
+	/// API key for fetching the titles and states of the tickets.
+	pub token:     Option<String>,
+}
+
+/// Shortcut configuration.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ShortcutConfig {
+	/// Workspace (i.e. URL slug of the organization) for the ticket links.
+	pub workspace: String,
+	/// Regex for matching the story IDs such as `sc-123`. The first capture
+	/// group must be the numeric ID.
+	#[serde(with = "serde_regex", default)]
+	pub pattern:   Option<Regex>,
+	/// API token for fetching the titles and states of the stories.
+	pub token:     Option<String>,
+}
+
 /// Parser for grouping commits.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CommitParser {
@@ -98,6 +904,10 @@ pub struct CommitParser {
 	/// Regex for matching the commit body.
 	#[serde(with = "serde_regex", default)]
 	pub body:          Option<Regex>,
+	/// Whether to match only the breaking (or non-breaking) commits.
+	pub breaking:      Option<bool>,
+	/// Whether to match only the merge (or non-merge) commits.
+	pub merge:         Option<bool>,
 	/// Group of the commit.
 	pub group:         Option<String>,
 	/// Default scope of the commit.
@@ -106,6 +916,11 @@ pub struct CommitParser {
 	pub scope:         Option<String>,
 	/// Whether to skip this commit group.
 	pub skip:          Option<bool>,
+	/// Priority of the parser when multiple parsers match a commit.
+	pub priority:      Option<i64>,
+	/// Whether to append the group to the groups of the commit instead of
+	/// overwriting it.
+	pub append:        Option<bool>,
 }
 
 /// Preprocessor for modifying commit messages.
@@ -120,6 +935,29 @@ pub struct CommitPreprocessor {
 	pub replace_command: Option<String>,
 }
 
+/// Normalizer for the subjects of the changelog entries.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EntryNormalizer {
+	/// Whether to capitalize the first letter.
+	pub capitalize:      Option<bool>,
+	/// How to handle the trailing period.
+	pub trailing_period: Option<TrailingPeriod>,
+	/// Corrections of the (misspelled) words.
+	pub dictionary:      Option<HashMap<String, String>>,
+}
+
+/// Handling of the trailing period of the entry subjects.
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingPeriod {
+	/// Remove the trailing period.
+	Remove,
+	/// Add a trailing period if there is no sentence-ending punctuation.
+	Add,
+}
+
 /// Parser for extracting links in commits.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct LinkParser {
@@ -132,9 +970,151 @@ pub struct LinkParser {
 	pub text:    Option<String>,
 }
 
+/// Parser for extracting the issues (e.g. `PROJ-123`) in commits.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct IssueParser {
+	/// Regex for finding the issues in the commit message.
+	///
+	/// The ID of the issue is the first capture group (if any) or the whole
+	/// match.
+	#[serde(with = "serde_regex")]
+	pub pattern: Regex,
+	/// The string used to generate the issue URL.
+	pub url:     String,
+	/// Name of the configured tracker (`jira`, `linear` or `shortcut`) for
+	/// fetching the type of the issue.
+	pub tracker: Option<String>,
+}
+
+/// Parser for extracting a custom field of the commits.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FieldParser {
+	/// Token of the footer to extract the value from (case-insensitive).
+	pub footer:  Option<String>,
+	/// Regex for extracting the value from the commit message if there is no
+	/// such footer.
+	///
+	/// The value is the `value` group, the first group or the whole match.
+	#[serde(with = "serde_regex", default)]
+	pub pattern: Option<Regex>,
+}
+
 impl Config {
 	/// Parses the config file and returns the values.
+	///
+	/// The remote base configs are saved to the user cache directory. See
+	/// [`Config::parse_async`] for the details, which should be used instead
+	/// in an asynchronous context since this blocks the current thread.
+	///
+	/// # Panics
+	///
+	/// Panics if called from an asynchronous execution context.
 	pub fn parse(path: &Path) -> Result<Config> {
+		let dir = dirs_next::cache_dir()
+			.unwrap_or_else(env::temp_dir)
+			.join("git-cliff")
+			.join("configs");
+		tokio::runtime::Builder::new_current_thread()
+			.enable_all()
+			.build()?
+			.block_on(Self::parse_async(path, &dir))
+	}
+
+	/// Parses the config file and returns the values.
+	///
+	/// The format of the file (TOML, YAML or JSON) is detected from its
+	/// extension and defaults to TOML.
+	///
+	/// The config file can extend a base config via `extends` which is a
+	/// path relative to the config file, a URL or a file of a GitHub
+	/// repository (e.g. `github:orhun/git-cliff/cliff.toml`). The tables are
+	/// merged recursively with the values of the extending config taking
+	/// precedence and the remote base configs are saved to the given
+	/// directory.
+	///
+	/// The values can be overridden via the environment variables that are
+	/// prefixed with `PRETTY_CHANGELOG` and separated with double underscores
+	/// so that the keys can contain underscores (e.g.
+	/// `PRETTY_CHANGELOG__GIT__TAG_PATTERN`). The legacy `CLIFF_` prefix
+	/// (e.g. `CLIFF_CHANGELOG_FOOTER`) is still supported.
+	pub async fn parse_async(path: &Path, dir: &Path) -> Result<Config> {
+		Self::parse_from(
+			path,
+			&fs::canonicalize(path)?.to_string_lossy(),
+			false,
+			dir,
+		)
+		.await
+	}
+
+	/// Parses the config file that is loaded from the given location (path
+	/// or URL) which the relative base configs are resolved against.
+	///
+	/// The config at `config_url` is used as a base config that is pinned to
+	/// `config_sha256`, i.e. the values of the local config take precedence.
+	async fn parse_from(
+		path: &Path,
+		location: &str,
+		is_pinned: bool,
+		dir: &Path,
+	) -> Result<Config> {
+		let mut locations = vec![location.to_string()];
+		let mut pins = vec![is_pinned];
+		let mut sources = vec![Self::source(path)?];
+		while let Some((extends, sha256)) =
+			sources.last().map(Self::base_of).transpose()?.flatten()
+		{
+			let location =
+				Self::base_location(locations.last().map_or("", |v| v), &extends)?;
+			if locations.contains(&location) {
+				return Err(Error::ConfigError(config::ConfigError::Message(
+					format!("{location} is extended recursively"),
+				)));
+			}
+			sources.push(if is_remote(&location) {
+				Self::source(
+					&Self::download_to(&location, sha256.as_deref(), dir).await?,
+				)?
+			} else {
+				Self::source(Path::new(&location))?
+			});
+			pins.push(sha256.is_some());
+			locations.push(location);
+		}
+		// Resolve the template paths relative to the declaring config.
+		let mut resolved_sources = Vec::new();
+		for ((source, location), is_pinned) in
+			sources.into_iter().zip(&locations).zip(pins)
+		{
+			resolved_sources.push(
+				Self::resolve_template_paths(source, location, is_pinned, dir)
+					.await?,
+			);
+		}
+		let config_builder = resolved_sources
+			.into_iter()
+			.rev()
+			.fold(config::Config::builder(), |builder, source| {
+				builder.add_source(source)
+			});
+		let mut config: Config = config_builder
+			.add_source(config::Environment::with_prefix("CLIFF").separator("_"))
+			.add_source(
+				config::Environment::with_prefix("PRETTY_CHANGELOG")
+					.prefix_separator("__")
+					.separator("__"),
+			)
+			.build()?
+			.try_deserialize()?;
+		config
+			.changelog
+			.read_templates(path.parent().unwrap_or_else(|| Path::new("")))?;
+		Ok(config)
+	}
+
+	/// Returns the values of the config file without the base configs and
+	/// the overrides.
+	fn source(path: &Path) -> Result<config::Config> {
 		let config_builder = if path.file_name() == Some(OsStr::new("Cargo.toml")) {
 			let contents = fs::read_to_string(path)?;
 			let metadata_regex = RegexBuilder::new(CARGO_METADATA_REGEX)
@@ -146,21 +1126,244 @@ impl Config {
 				config::FileFormat::Toml,
 			))
 		} else {
-			config::Config::builder().add_source(config::File::from(path))
+			config::Config::builder()
+				.add_source(config::File::from(path).format(Self::file_format(path)))
 		};
-		Ok(config_builder
-			.add_source(config::Environment::with_prefix("CLIFF").separator("_"))
-			.build()?
-			.try_deserialize()?)
+		Ok(config_builder.build()?)
+	}
+
+	/// Returns the base config of the given values (i.e. `extends` or
+	/// `config_url`) and its SHA-256 checksum to pin it to.
+	fn base_of(source: &config::Config) -> Result<Option<(String, Option<String>)>> {
+		match (
+			source.get_string("extends").ok(),
+			source.get_string("config_url").ok(),
+		) {
+			(Some(_), Some(_)) => {
+				Err(Error::ConfigError(config::ConfigError::Message(
+					String::from("extends and config_url cannot be used together"),
+				)))
+			}
+			(Some(extends), None) => Ok(Some((extends, None))),
+			(None, Some(url)) => {
+				Ok(Some((url, source.get_string("config_sha256").ok())))
+			}
+			(None, None) => Ok(None),
+		}
+	}
+
+	/// Returns the values of the config file at the given location with the
+	/// template paths (e.g. `changelog.body_path`) resolved relative to it.
+	///
+	/// The templates of the remote config files are downloaded to the given
+	/// directory and verified against their SHA-256 checksums (e.g.
+	/// `changelog.body_sha256`), which are required if the config is pinned.
+	async fn resolve_template_paths(
+		source: config::Config,
+		location: &str,
+		is_pinned: bool,
+		dir: &Path,
+	) -> Result<config::Config> {
+		let mut config_builder =
+			config::Config::builder().add_source(source.clone());
+		for (key, sha256_key) in [
+			("changelog.header_path", "changelog.header_sha256"),
+			("changelog.body_path", "changelog.body_sha256"),
+			("changelog.footer_path", "changelog.footer_sha256"),
+		] {
+			if let Ok(path) = source.get_string(key) {
+				let path = if is_remote(location) {
+					let sha256 = source.get_string(sha256_key).ok();
+					if is_pinned && sha256.is_none() {
+						return Err(Error::ConfigError(
+							config::ConfigError::Message(format!(
+								"{key} of the pinned config {location} requires \
+								 {sha256_key}"
+							)),
+						));
+					}
+					let url = location.rsplit_once('/').map_or(location, |v| v.0);
+					Self::download_to(
+						&format!("{url}/{path}"),
+						sha256.as_deref(),
+						dir,
+					)
+					.await?
+				} else {
+					Path::new(location)
+						.parent()
+						.unwrap_or_else(|| Path::new(""))
+						.join(path)
+				};
+				config_builder = config_builder
+					.set_override(key, path.to_string_lossy().as_ref())?;
+			}
+		}
+		Ok(config_builder.build()?)
+	}
+
+	/// Returns the location of the base config that is extended by the
+	/// config at the given location.
+	///
+	/// The base configs of GitHub repositories are fetched from the given
+	/// ref (e.g. `github:org/repo@v1.0.0/cliff.toml`), or from the latest
+	/// version of the default branch if it is omitted.
+	fn base_location(location: &str, extends: &str) -> Result<String> {
+		if let Some(path) = extends.strip_prefix("github:") {
+			match path.splitn(3, '/').collect::<Vec<&str>>()[..] {
+				[owner, repo, path] => {
+					let (repo, reference) = match repo.split_once('@') {
+						Some((repo, reference)) => (repo, reference),
+						None => {
+							warn!(
+								"{extends} is not pinned to a ref, the base config \
+								 can change without notice"
+							);
+							(repo, "HEAD")
+						}
+					};
+					Ok(format!(
+						"https://raw.githubusercontent.com/{owner}/{repo}/\
+						 {reference}/{path}"
+					))
+				}
+				_ => Err(Error::ConfigError(config::ConfigError::Message(format!(
+					"invalid base config: {extends}"
+				)))),
+			}
+		} else if is_remote(extends) {
+			Ok(extends.to_string())
+		} else if is_remote(location) {
+			let url = location.rsplit_once('/').map_or(location, |v| v.0);
+			Ok(format!("{url}/{extends}"))
+		} else {
+			let path = Path::new(location)
+				.parent()
+				.unwrap_or_else(|| Path::new(""))
+				.join(extends);
+			Ok(fs::canonicalize(path)?.to_string_lossy().to_string())
+		}
+	}
+
+	/// Fetches the config file from the given URL and returns the values.
+	///
+	/// The contents are verified against the SHA-256 checksum (if given) and
+	/// saved to the given directory. The saved file is used without fetching
+	/// it again if it matches the checksum, or if the file cannot be fetched
+	/// (e.g. while offline).
+	pub async fn fetch(
+		url: &str,
+		sha256: Option<&str>,
+		dir: &Path,
+	) -> Result<Config> {
+		let path = Self::download_to(url, sha256, dir).await?;
+		Self::parse_from(&path, url, sha256.is_some(), dir).await
+	}
+
+	/// Downloads the config file from the given URL to the given directory
+	/// and returns its path.
+	///
+	/// See [`Config::fetch`] for the verification and caching of the file.
+	async fn download_to(
+		url: &str,
+		sha256: Option<&str>,
+		dir: &Path,
+	) -> Result<PathBuf> {
+		let name = url.split(['?', '#']).next().unwrap_or(url);
+		let extension = Path::new(name)
+			.extension()
+			.and_then(OsStr::to_str)
+			.unwrap_or("toml");
+		let path =
+			dir.join(format!("{:x}.{extension}", Sha256::digest(url.as_bytes())));
+		let is_pinned = |contents: &[u8]| {
+			sha256.map_or(true, |sha256| {
+				sha256
+					.eq_ignore_ascii_case(&format!("{:x}", Sha256::digest(contents)))
+			})
+		};
+		let contents = match (sha256, fs::read(&path).ok()) {
+			(Some(_), Some(contents)) if is_pinned(&contents) => contents,
+			(_, cached) => match (Self::download(url).await, cached) {
+				(Ok(contents), _) => contents,
+				(Err(e), Some(cached)) => {
+					warn!("Cannot fetch {url}, using the cached config: {e}");
+					cached
+				}
+				(Err(e), None) => return Err(e),
+			},
+		};
+		if !is_pinned(&contents) {
+			return Err(Error::ConfigError(config::ConfigError::Message(format!(
+				"checksum of {url} does not match the pinned one"
+			))));
+		}
+		fs::create_dir_all(dir)?;
+		fs::write(&path, contents)?;
+		Ok(path)
 	}
+
+	/// Downloads the config file from the given URL.
+	async fn download(url: &str) -> Result<Vec<u8>> {
+		let response = reqwest::Client::new()
+			.get(url)
+			.header("User-Agent", "git-cliff")
+			.send_with_retry()
+			.await?
+			.error_for_status()?;
+		Ok(response.bytes().await?.to_vec())
+	}
+
+	/// Returns the format of the config file from its extension.
+	fn file_format(path: &Path) -> config::FileFormat {
+		match path
+			.extension()
+			.and_then(OsStr::to_str)
+			.map(str::to_lowercase)
+			.as_deref()
+		{
+			Some("yaml" | "yml") => config::FileFormat::Yaml,
+			Some("json") => config::FileFormat::Json,
+			_ => config::FileFormat::Toml,
+		}
+	}
+}
+
+/// Returns whether the given location of a config file is a URL.
+fn is_remote(location: &str) -> bool {
+	location.starts_with("https://") || location.starts_with("http://")
 }
 
 #[cfg(test)]
 mod test {
 	use super::*;
 	use pretty_assertions::assert_eq;
-	use std::env;
-	use std::path::PathBuf;
+
+	/// Lock of the tests that parse the config files since the values can be
+	/// overridden via the environment variables.
+	static ENV_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+	/// Environment variables that are removed when dropped.
+	struct EnvVars(Vec<&'static str>);
+
+	impl EnvVars {
+		/// Sets the given environment variables.
+		fn set(vars: &[(&'static str, &str)]) -> Self {
+			for (key, value) in vars {
+				env::set_var(key, value);
+			}
+			Self(vars.iter().map(|(key, _)| *key).collect())
+		}
+	}
+
+	impl Drop for EnvVars {
+		fn drop(&mut self) {
+			for key in &self.0 {
+				env::remove_var(key);
+			}
+		}
+	}
+
 	#[test]
 	fn parse_config() -> Result<()> {
 		let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -169,9 +1372,304 @@ mod test {
 			.to_path_buf()
 			.join("config")
 			.join(crate::DEFAULT_CONFIG);
-		env::set_var("CLIFF_CHANGELOG_FOOTER", "test");
+		let _lock = ENV_LOCK.blocking_lock();
+		let _vars = EnvVars::set(&[
+			("CLIFF_CHANGELOG_FOOTER", "test"),
+			("PRETTY_CHANGELOG__GIT__TAG_PATTERN", "v[0-9]*"),
+			("PRETTY_CHANGELOG__CHANGELOG__TRIM", "false"),
+		]);
 		let config = Config::parse(&path)?;
 		assert_eq!(Some(String::from("test")), config.changelog.footer);
+		assert_eq!(
+			Some(String::from("v[0-9]*")),
+			config.git.tag_pattern.map(|v| v.patterns().join(","))
+		);
+		assert_eq!(Some(false), config.changelog.trim);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn parse_template_paths() -> Result<()> {
+		let _lock = ENV_LOCK.lock().await;
+		let dir = env::temp_dir().join("git-cliff-template-paths");
+		fs::create_dir_all(dir.join("templates"))?;
+		fs::write(dir.join("templates").join("body.tera"), "{{ version }}")?;
+		fs::write(
+			dir.join("cliff.toml"),
+			r##"
+			[changelog]
+			header = "# Changelog"
+			header_path = "templates/header.tera"
+			body_path = "templates/body.tera"
+			"##,
+		)?;
+		let config = Config::parse_async(&dir.join("cliff.toml"), &dir).await?;
+		assert_eq!(Some(String::from("# Changelog")), config.changelog.header);
+		assert_eq!(Some(String::from("{{ version }}")), config.changelog.body);
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn parse_formats() -> Result<()> {
+		let _lock = ENV_LOCK.lock().await;
+		let dir = env::temp_dir().join("git-cliff-config-formats");
+		fs::create_dir_all(&dir)?;
+		for (name, contents) in [
+			(
+				"cliff.yaml",
+				"changelog:\n  header: Changelog\ngit:\n  date_order: true\n",
+			),
+			(
+				"cliff.json",
+				r#"{"changelog": {"header": "Changelog"}, "git": {"date_order": true}}"#,
+			),
+		] {
+			fs::write(dir.join(name), contents)?;
+			let config = Config::parse_async(&dir.join(name), &dir).await?;
+			assert_eq!(Some(String::from("Changelog")), config.changelog.header);
+			assert_eq!(Some(true), config.git.date_order);
+		}
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn parse_extended_config() -> Result<()> {
+		let _lock = ENV_LOCK.lock().await;
+		let dir = env::temp_dir().join("git-cliff-extended-config");
+		fs::create_dir_all(dir.join("crates").join("core"))?;
+		fs::write(
+			dir.join("cliff.toml"),
+			r##"
+			[changelog]
+			header_path = "header.md"
+			body = "{{ version }}"
+
+			[git]
+			conventional_commits = true
+			date_order = true
+			"##,
+		)?;
+		fs::write(dir.join("header.md"), "# Changelog")?;
+		let path = dir.join("crates").join("core").join("cliff.toml");
+		fs::write(
+			&path,
+			r#"
+			extends = "../../cliff.toml"
+
+			[changelog]
+			body = "{{ version }} (core)"
+
+			[git]
+			date_order = false
+			"#,
+		)?;
+		let config = Config::parse_async(&path, &dir).await?;
+		assert_eq!(Some(String::from("# Changelog")), config.changelog.header);
+		assert_eq!(
+			Some(String::from("{{ version }} (core)")),
+			config.changelog.body
+		);
+		assert_eq!(Some(true), config.git.conventional_commits);
+		assert_eq!(Some(false), config.git.date_order);
+		fs::write(
+			dir.join("cliff.toml"),
+			"extends = \"crates/core/cliff.toml\"",
+		)?;
+		assert!(Config::parse_async(&path, &dir).await.is_err());
+		assert_eq!(
+			"https://raw.githubusercontent.com/orhun/git-cliff/HEAD/cliff.toml",
+			Config::base_location(
+				"cliff.toml",
+				"github:orhun/git-cliff/cliff.toml"
+			)?
+		);
+		assert_eq!(
+			"https://raw.githubusercontent.com/orhun/git-cliff/v1.0.0/cliff.toml",
+			Config::base_location(
+				"cliff.toml",
+				"github:orhun/git-cliff@v1.0.0/cliff.toml"
+			)?
+		);
+		assert_eq!(
+			"https://xyz/base.toml",
+			Config::base_location("https://xyz/cliff.toml", "base.toml")?
+		);
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn fetch_pinned_config() -> Result<()> {
+		let _lock = ENV_LOCK.lock().await;
+		let dir = env::temp_dir()
+			.join(format!("git-cliff-fetch-config-{}", std::process::id()));
+		let url = "http://127.0.0.1:1/cliff.toml";
+		let contents = "[changelog]\nheader = \"Changelog\"\n";
+		fs::create_dir_all(&dir)?;
+		fs::write(
+			dir.join(format!("{:x}.toml", Sha256::digest(url.as_bytes()))),
+			contents,
+		)?;
+		let sha256 = format!("{:x}", Sha256::digest(contents.as_bytes()));
+		let config = Config::fetch(url, Some(&sha256), &dir).await?;
+		assert_eq!(Some(String::from("Changelog")), config.changelog.header);
+		assert!(Config::fetch(url, None, &dir).await.is_ok());
+		assert!(Config::fetch(url, Some("abc"), &dir).await.is_err());
+		let path = dir.join("cliff.toml");
+		fs::write(
+			&path,
+			format!(
+				"config_url = \"{url}\"\nconfig_sha256 = \
+				 \"{sha256}\"\n[changelog]\nfooter = \"Footer\""
+			),
+		)?;
+		let config = Config::parse_async(&path, &dir).await?;
+		assert_eq!(Some(String::from("Changelog")), config.changelog.header);
+		assert_eq!(Some(String::from("Footer")), config.changelog.footer);
+		fs::write(
+			&path,
+			format!("config_url = \"{url}\"\nconfig_sha256 = \"abc\""),
+		)?;
+		assert!(Config::parse_async(&path, &dir).await.is_err());
+
+		let body_url = "http://127.0.0.1:1/body.tera";
+		let body = "{{ version }}";
+		fs::write(
+			dir.join(format!("{:x}.tera", Sha256::digest(body_url.as_bytes()))),
+			body,
+		)?;
+		let body_sha256 = format!("{:x}", Sha256::digest(body.as_bytes()));
+		let url = "http://127.0.0.1:1/templates.toml";
+		for (contents, is_valid) in [
+			(
+				String::from("[changelog]\nbody_path = \"body.tera\""),
+				false,
+			),
+			(
+				String::from(
+					"[changelog]\nbody_path = \"body.tera\"\nbody_sha256 = \"abc\"",
+				),
+				false,
+			),
+			(
+				format!(
+					"[changelog]\nbody_path = \"body.tera\"\nbody_sha256 = \
+					 \"{body_sha256}\""
+				),
+				true,
+			),
+		] {
+			fs::write(
+				dir.join(format!("{:x}.toml", Sha256::digest(url.as_bytes()))),
+				&contents,
+			)?;
+			let sha256 = format!("{:x}", Sha256::digest(contents.as_bytes()));
+			let config = Config::fetch(url, Some(&sha256), &dir).await;
+			assert_eq!(is_valid, config.is_ok());
+			if let Ok(config) = config {
+				assert_eq!(Some(String::from(body)), config.changelog.body);
+			}
+		}
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+
+	#[test]
+	fn parse_packages() -> Result<()> {
+		let config: Config = toml::from_str(
+			r#"
+			[[packages]]
+			name = "core"
+			include_paths = ["crates/core/**"]
+			tag_pattern = "core-v[0-9]*"
+			output = "crates/core/CHANGELOG.md"
+
+			[[packages]]
+			name = "cli"
+			"#,
+		)?;
+		assert_eq!(
+			vec!["core", "cli"],
+			config
+				.packages
+				.iter()
+				.map(|v| v.name.as_str())
+				.collect::<Vec<&str>>()
+		);
+		assert_eq!(
+			vec!["core-v[0-9]*"],
+			config.packages[0]
+				.tag_pattern
+				.as_ref()
+				.map(|v| v.patterns())
+				.unwrap_or_default()
+		);
+		assert!(config.packages[1].include_paths.is_none());
+		Ok(())
+	}
+
+	#[test]
+	fn tag_matchers() -> Result<()> {
+		let config: GitConfig = toml::from_str(
+			r#"
+			skip_tags = "v0.1.0-beta.*"
+			ignore_tags = { glob = "app/v*.*.*" }
+			"#,
+		)?;
+		let skip_tags = config.skip_tags.expect("skip_tags not found");
+		assert!(skip_tags.is_match("v0.1.0-beta.1"));
+		assert!(!skip_tags.is_match("v0.1.0"));
+		let ignore_tags = config.ignore_tags.expect("ignore_tags not found");
+		assert!(ignore_tags.is_match("app/v1.2.3"));
+		assert!(!ignore_tags.is_match("lib/v1.2.3"));
+		assert!(!ignore_tags.is_empty());
+		let config: GitConfig = toml::from_str("ignore_tags = ''")?;
+		assert!(config
+			.ignore_tags
+			.expect("ignore_tags not found")
+			.is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn redact_emails() {
+		assert_eq!(None, EmailRedaction::Strip.redact("x@example.com", ""));
+		let hash = EmailRedaction::Hash
+			.redact("X@Example.com ", "")
+			.expect("hash not found");
+		assert_eq!(64, hash.len());
+		assert_eq!(
+			Some(hash.clone()),
+			EmailRedaction::Hash.redact("x@example.com", "")
+		);
+		assert_ne!(
+			Some(hash),
+			EmailRedaction::Hash.redact("x@example.com", "salt")
+		);
+	}
+
+	#[test]
+	fn tag_pattern_channels() -> Result<()> {
+		let config: GitConfig = toml::from_str(
+			r#"
+			tag_pattern = [
+				{ pattern = "v*-lts", channel = "lts" },
+				{ pattern = "v[0-9]*", channel = "stable" },
+				{ pattern = "nightly-*" },
+			]
+			"#,
+		)?;
+		let patterns = config.tag_pattern.expect("tag pattern not found");
+		assert_eq!(vec!["v*-lts", "v[0-9]*", "nightly-*"], patterns.patterns());
+		assert_eq!(Some("lts"), patterns.channel("v1.0.0-lts"));
+		assert_eq!(Some("stable"), patterns.channel("v1.1.0"));
+		assert_eq!(None, patterns.channel("nightly-2022"));
+		let config: GitConfig = toml::from_str(r#"tag_pattern = "v[0-9]*""#)?;
+		let patterns = config.tag_pattern.expect("tag pattern not found");
+		assert_eq!(vec!["v[0-9]*"], patterns.patterns());
+		assert_eq!(None, patterns.channel("v1.1.0"));
 		Ok(())
 	}
 }
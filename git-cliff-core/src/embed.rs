@@ -8,6 +8,9 @@ use crate::error::{
 use rust_embed::RustEmbed;
 use std::str;
 
+/// Name of the preset that is the default configuration file.
+pub const DEFAULT_PRESET: &str = "default";
+
 /// Configuration file embedder/extractor.
 ///
 /// Embeds `config/`[`DEFAULT_CONFIG`] into the binary.
@@ -28,10 +31,98 @@ impl EmbeddedConfig {
 		}
 	}
 
+	/// Extracts the embedded templates as (name, content) pairs.
+	///
+	/// Templates are embedded from `config/templates/` and named after their
+	/// file names without the extension, e.g. `default`.
+	pub fn get_templates() -> Result<Vec<(String, String)>> {
+		let mut templates = Vec::new();
+		for path in Self::iter() {
+			if let Some(name) = path
+				.strip_prefix("templates/")
+				.and_then(|v| v.strip_suffix(".tera"))
+			{
+				if let Some(file) = Self::get(&path) {
+					templates.push((
+						name.to_string(),
+						str::from_utf8(&file.data)?.to_string(),
+					));
+				}
+			}
+		}
+		Ok(templates)
+	}
+
 	/// Parses the extracted content into [`Config`].
 	///
 	/// [`Config`]: Config
 	pub fn parse() -> Result<Config> {
 		Ok(toml::from_str(&Self::get_config()?)?)
 	}
+
+	/// Extracts the configuration of the given preset.
+	///
+	/// `default` is the embedded [`DEFAULT_CONFIG`] and the others are
+	/// embedded from `examples/`, e.g. `keepachangelog`.
+	///
+	/// [`DEFAULT_CONFIG`]: crate::DEFAULT_CONFIG
+	pub fn get_preset(name: &str) -> Result<String> {
+		if name == DEFAULT_PRESET {
+			return Self::get_config();
+		}
+		match EmbeddedPresets::get(&format!("{name}.toml")) {
+			Some(v) => Ok(str::from_utf8(&v.data)?.to_string()),
+			None => Err(Error::EmbeddedError(format!(
+				"Preset not found: {name} (available presets: {})",
+				Self::presets().join(", ")
+			))),
+		}
+	}
+
+	/// Returns the names of the available presets.
+	pub fn presets() -> Vec<String> {
+		let mut presets = EmbeddedPresets::iter()
+			.filter_map(|path| path.strip_suffix(".toml").map(String::from))
+			.collect::<Vec<String>>();
+		presets.sort();
+		presets.insert(0, DEFAULT_PRESET.to_string());
+		presets
+	}
+}
+
+/// Preset configuration files embedder/extractor.
+///
+/// Embeds the configuration files in `examples/` into the binary.
+#[derive(Debug, RustEmbed)]
+#[folder = "../examples/"]
+struct EmbeddedPresets;
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn get_presets() -> Result<()> {
+		let presets = EmbeddedConfig::presets();
+		assert_eq!(Some(DEFAULT_PRESET), presets.first().map(String::as_str));
+		for name in [
+			"keepachangelog",
+			"github-release-notes",
+			"minimal",
+			"scoped",
+			"detailed",
+		] {
+			assert!(presets.iter().any(|v| v == name));
+		}
+		for name in &presets {
+			toml::from_str::<Config>(&EmbeddedConfig::get_preset(name)?)?;
+		}
+		assert_eq!(
+			EmbeddedConfig::get_config()?,
+			EmbeddedConfig::get_preset(DEFAULT_PRESET)?
+		);
+		assert!(EmbeddedConfig::get_preset("unknown").is_err());
+		Ok(())
+	}
 }
@@ -0,0 +1,146 @@
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+};
+use regex::{
+	Captures,
+	Regex,
+};
+
+/// Regular expression for matching emoji shortcodes such as `:sparkles:`.
+static SHORTCODE_REGEX: Lazy<Regex> = lazy_regex!(r#":([a-z0-9_+\-]+):"#);
+
+/// Regular expression for matching emojis and the following spaces.
+static EMOJI_REGEX: Lazy<Regex> = lazy_regex!(
+	r#"[\p{Extended_Pictographic}\p{Emoji_Modifier}\p{Regional_Indicator}\x{FE0F}\x{200D}\x{20E3}]+ *"#
+);
+
+/// Emoji shortcodes that are supported by [gitmoji](https://gitmoji.dev).
+///
+/// Sorted by the shortcode for binary search.
+const EMOJIS: &[(&str, &str)] = &[
+	("adhesive_bandage", "\u{1FA79}"),
+	("alembic", "\u{2697}\u{FE0F}"),
+	("alien", "\u{1F47D}\u{FE0F}"),
+	("ambulance", "\u{1F691}\u{FE0F}"),
+	("arrow_down", "\u{2B07}\u{FE0F}"),
+	("arrow_up", "\u{2B06}\u{FE0F}"),
+	("art", "\u{1F3A8}"),
+	("beers", "\u{1F37B}"),
+	("bento", "\u{1F371}"),
+	("bookmark", "\u{1F516}"),
+	("boom", "\u{1F4A5}"),
+	("bricks", "\u{1F9F1}"),
+	("bug", "\u{1F41B}"),
+	("building_construction", "\u{1F3D7}\u{FE0F}"),
+	("bulb", "\u{1F4A1}"),
+	("busts_in_silhouette", "\u{1F465}"),
+	("camera_flash", "\u{1F4F8}"),
+	("card_file_box", "\u{1F5C3}\u{FE0F}"),
+	("chart_with_upwards_trend", "\u{1F4C8}"),
+	("children_crossing", "\u{1F6B8}"),
+	("closed_lock_with_key", "\u{1F510}"),
+	("clown_face", "\u{1F921}"),
+	("coffin", "\u{26B0}\u{FE0F}"),
+	("construction", "\u{1F6A7}"),
+	("construction_worker", "\u{1F477}"),
+	("dizzy", "\u{1F4AB}"),
+	("egg", "\u{1F95A}"),
+	("fire", "\u{1F525}"),
+	("globe_with_meridians", "\u{1F310}"),
+	("goal_net", "\u{1F945}"),
+	("green_heart", "\u{1F49A}"),
+	("hammer", "\u{1F528}"),
+	("heavy_minus_sign", "\u{2796}"),
+	("heavy_plus_sign", "\u{2795}"),
+	("iphone", "\u{1F4F1}"),
+	("label", "\u{1F3F7}\u{FE0F}"),
+	("lipstick", "\u{1F484}"),
+	("lock", "\u{1F512}\u{FE0F}"),
+	("loud_sound", "\u{1F50A}"),
+	("mag", "\u{1F50D}\u{FE0F}"),
+	("memo", "\u{1F4DD}"),
+	("money_with_wings", "\u{1F4B8}"),
+	("monocle_face", "\u{1F9D0}"),
+	("mute", "\u{1F507}"),
+	("necktie", "\u{1F454}"),
+	("package", "\u{1F4E6}\u{FE0F}"),
+	("page_facing_up", "\u{1F4C4}"),
+	("passport_control", "\u{1F6C2}"),
+	("pencil", "\u{1F4DD}"),
+	("pencil2", "\u{270F}\u{FE0F}"),
+	("poop", "\u{1F4A9}"),
+	("pushpin", "\u{1F4CC}"),
+	("recycle", "\u{267B}\u{FE0F}"),
+	("rewind", "\u{23EA}\u{FE0F}"),
+	("rocket", "\u{1F680}"),
+	("rotating_light", "\u{1F6A8}"),
+	("safety_vest", "\u{1F9BA}"),
+	("see_no_evil", "\u{1F648}"),
+	("seedling", "\u{1F331}"),
+	("sparkles", "\u{2728}"),
+	("speech_balloon", "\u{1F4AC}"),
+	("stethoscope", "\u{1FA7A}"),
+	("tada", "\u{1F389}"),
+	("technologist", "\u{1F9D1}\u{200D}\u{1F4BB}"),
+	("test_tube", "\u{1F9EA}"),
+	("thread", "\u{1F9F5}"),
+	("triangular_flag_on_post", "\u{1F6A9}"),
+	("truck", "\u{1F69A}"),
+	("twisted_rightwards_arrows", "\u{1F500}"),
+	("wastebasket", "\u{1F5D1}\u{FE0F}"),
+	("wheelchair", "\u{267F}\u{FE0F}"),
+	("white_check_mark", "\u{2705}"),
+	("wrench", "\u{1F527}"),
+	("zap", "\u{26A1}\u{FE0F}"),
+];
+
+/// Returns the emoji of the given shortcode (without colons).
+pub fn get(shortcode: &str) -> Option<&'static str> {
+	EMOJIS
+		.binary_search_by(|(name, _)| name.cmp(&shortcode))
+		.ok()
+		.map(|index| EMOJIS[index].1)
+}
+
+/// Replaces the known emoji shortcodes in the text with emojis.
+pub fn replace_shortcodes(text: &str) -> String {
+	SHORTCODE_REGEX
+		.replace_all(text, |captures: &Captures| {
+			get(&captures[1]).unwrap_or(&captures[0]).to_string()
+		})
+		.to_string()
+}
+
+/// Removes the emojis and the known emoji shortcodes from the text.
+///
+/// Spaces following the emojis are removed as well so that
+/// `:sparkles: feat: add xyz` becomes `feat: add xyz`.
+pub fn strip(text: &str) -> String {
+	EMOJI_REGEX
+		.replace_all(&replace_shortcodes(text), "")
+		.to_string()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn emojis() {
+		assert!(EMOJIS.windows(2).all(|w| w[0].0 < w[1].0));
+		assert_eq!(Some("\u{2728}"), get("sparkles"));
+		assert_eq!(None, get("unknown"));
+		assert_eq!(
+			"\u{2728} feat: add xyz :unknown:",
+			replace_shortcodes(":sparkles: feat: add xyz :unknown:")
+		);
+		assert_eq!("feat: add xyz", strip(":sparkles: feat: add xyz"));
+		assert_eq!(
+			"fix: fix abc",
+			strip("\u{1F41B} \u{1F9D1}\u{200D}\u{1F4BB} fix: fix abc")
+		);
+		assert_eq!("chore: 10:30 :unknown:", strip("chore: 10:30 :unknown:"));
+	}
+}
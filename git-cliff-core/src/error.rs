@@ -23,6 +23,10 @@ pub enum Error {
 	/// Error that may occur while grouping commits.
 	#[error("Grouping error: `{0}`")]
 	GroupError(String),
+	/// Error that represents the commits that are skipped by the commit
+	/// parsers.
+	#[error("Skipping commit")]
+	SkippedCommit,
 	/// Error that may occur while generating changelog.
 	#[error("Changelog error: `{0}`")]
 	ChangelogError(String),
@@ -38,6 +42,9 @@ pub enum Error {
 	/// Error that may occur while parsing the command line arguments.
 	#[error("Argument error: `{0}`")]
 	ArgumentError(String),
+	/// Error that may occur while localizing the dates and the lists.
+	#[error("Locale error: `{0}`")]
+	LocaleError(String),
 	/// Error that may occur while extracting the embedded content.
 	#[error("Embedded error: `{0}`")]
 	EmbeddedError(String),
@@ -50,12 +57,25 @@ pub enum Error {
 	/// Errors that may occur during parsing or compiling a regular expression.
 	#[error("Cannot parse/compile regex: `{0}`")]
 	RegexError(#[from] regex::Error),
+	/// Errors that may occur while parsing a glob pattern.
+	#[error("Cannot parse glob pattern: `{0}`")]
+	GlobError(#[from] glob::PatternError),
 	#[error("Fmt error: `{0}`")]
 	FmtError(#[from] std::fmt::Error),
 	#[error("Reqwest error: `{0}`")]
 	ReqwestError(#[from] reqwest::Error),
 	#[error("Tokio join error: `{0}`")]
 	JoinError(#[from] tokio::task::JoinError),
+	/// Error that may occur while validating the Github token.
+	#[error("Github token error: {0}")]
+	GithubTokenError(String),
+	/// Error that may occur when the rate limit of the Github API is exceeded.
+	#[error("Github API rate limit is exceeded, resets at {0}")]
+	GithubRateLimit(String),
+	/// Error that may occur while accessing the Redis cache.
+	#[cfg(feature = "redis")]
+	#[error("Redis error: `{0}`")]
+	RedisError(#[from] redis::RedisError),
 }
 
 /// Result type of the core library.
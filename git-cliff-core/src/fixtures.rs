@@ -0,0 +1,377 @@
+use crate::commit::Commit;
+use crate::config::SortBy;
+use crate::error::Result;
+use crate::release::Release;
+use crate::repo::Repository as ChangelogRepository;
+use git2::build::TreeUpdateBuilder;
+use git2::{
+	BranchType,
+	FileMode,
+	ObjectType,
+	Oid,
+	Repository,
+	RepositoryInitOptions,
+	Signature,
+	Time,
+};
+use std::env;
+use std::fs;
+use std::path::{
+	Path,
+	PathBuf,
+};
+use std::process;
+use std::sync::atomic::{
+	AtomicUsize,
+	Ordering,
+};
+
+/// Timestamp of the first commit of the sample repositories
+/// (2021-01-01T00:00:00Z).
+pub const START_TIMESTAMP: i64 = 1_609_459_200;
+
+/// Time between the commits of the sample repositories.
+pub const COMMIT_INTERVAL: i64 = 24 * 60 * 60;
+
+/// Number of the created sample repositories, for unique directory names.
+static REPOSITORY_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Commit to create in a [`SampleRepository`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FixtureCommit {
+	/// Commit message.
+	pub message:          String,
+	/// Name and email of the author. Defaults to `git-cliff`.
+	pub author:           Option<(String, String)>,
+	/// Author date in seconds, from epoch. Defaults to the commit date.
+	pub author_timestamp: Option<i64>,
+	/// Paths and contents of the files to add or update.
+	pub files:            Vec<(String, String)>,
+}
+
+impl From<&str> for FixtureCommit {
+	fn from(message: &str) -> Self {
+		Self {
+			message: message.to_string(),
+			..Self::default()
+		}
+	}
+}
+
+/// Git repository with a synthetic history for testing.
+///
+/// The initial branch is `main`. Commits are created with a fixed committer
+/// and the dates increasing by a day from 2021-01-01, so the generated
+/// changelogs are deterministic. Files are written to the commit trees only
+/// (i.e. not to the working directory). The repository is removed when the
+/// value is dropped.
+pub struct SampleRepository {
+	dir:        PathBuf,
+	repository: Repository,
+	timestamp:  i64,
+}
+
+impl SampleRepository {
+	/// Creates an empty repository in a temporary directory.
+	pub fn new() -> Result<Self> {
+		let dir = env::temp_dir().join(format!(
+			"git-cliff-sample-{}-{}",
+			process::id(),
+			REPOSITORY_COUNT.fetch_add(1, Ordering::SeqCst)
+		));
+		if dir.exists() {
+			fs::remove_dir_all(&dir)?;
+		}
+		Ok(Self {
+			repository: Repository::init_opts(
+				&dir,
+				RepositoryInitOptions::new().initial_head("main"),
+			)?,
+			dir,
+			timestamp: START_TIMESTAMP,
+		})
+	}
+
+	/// Returns the path of the repository.
+	pub fn path(&self) -> &Path {
+		&self.dir
+	}
+
+	/// Returns the ID of the commit that the given revision points to.
+	pub fn rev_parse(&self, revision: &str) -> Result<String> {
+		Ok(self
+			.repository
+			.revparse_single(revision)?
+			.peel_to_commit()?
+			.id()
+			.to_string())
+	}
+
+	/// Creates a commit with the given message on top of `HEAD`.
+	pub fn commit(&mut self, message: &str) -> Result<&mut Self> {
+		self.commit_with(FixtureCommit::from(message))
+	}
+
+	/// Creates the given commit on top of `HEAD`.
+	pub fn commit_with(&mut self, commit: FixtureCommit) -> Result<&mut Self> {
+		let parent = self.head()?;
+		self.create_commit(&commit, parent.into_iter().collect())?;
+		Ok(self)
+	}
+
+	/// Merges the given branch into `HEAD` with a merge commit.
+	///
+	/// The trees are merged without a working directory, conflicts result in
+	/// an error.
+	pub fn merge(&mut self, branch: &str, message: &str) -> Result<&mut Self> {
+		let ours = self.head()?.ok_or_else(|| {
+			git2::Error::from_str("cannot merge into an empty repository")
+		})?;
+		let theirs = self
+			.repository
+			.revparse_single(branch)?
+			.peel_to_commit()?
+			.id();
+		self.create_commit(&FixtureCommit::from(message), vec![ours, theirs])?;
+		Ok(self)
+	}
+
+	/// Creates a branch for `HEAD` without switching to it.
+	pub fn branch(&mut self, name: &str) -> Result<&mut Self> {
+		self.repository.branch(
+			name,
+			&self.repository.head()?.peel_to_commit()?,
+			false,
+		)?;
+		Ok(self)
+	}
+
+	/// Switches `HEAD` to the given branch, or detaches it at the given
+	/// revision if there is no such branch.
+	pub fn checkout(&mut self, name: &str) -> Result<&mut Self> {
+		if self.repository.find_branch(name, BranchType::Local).is_ok() {
+			self.repository.set_head(&format!("refs/heads/{name}"))?;
+		} else {
+			let id = self
+				.repository
+				.revparse_single(name)?
+				.peel_to_commit()?
+				.id();
+			self.repository.set_head_detached(id)?;
+		}
+		Ok(self)
+	}
+
+	/// Creates a lightweight tag for `HEAD`.
+	pub fn tag(&mut self, name: &str) -> Result<&mut Self> {
+		self.repository.tag_lightweight(
+			name,
+			&self.repository.head()?.peel(ObjectType::Commit)?,
+			false,
+		)?;
+		Ok(self)
+	}
+
+	/// Creates an annotated tag for `HEAD` with the given message.
+	///
+	/// The tagger date is the date of the next commit.
+	pub fn annotated_tag(&mut self, name: &str, message: &str) -> Result<&mut Self> {
+		let signature = self.signature(None, self.timestamp)?;
+		self.repository.tag(
+			name,
+			&self.repository.head()?.peel(ObjectType::Commit)?,
+			&signature,
+			message,
+			false,
+		)?;
+		Ok(self)
+	}
+
+	/// Returns the commit that `HEAD` points to (if any).
+	fn head(&self) -> Result<Option<Oid>> {
+		match self.repository.head() {
+			Ok(head) => Ok(Some(head.peel_to_commit()?.id())),
+			Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+			Err(e) => Err(e.into()),
+		}
+	}
+
+	/// Returns the signature with the given name/email and date.
+	fn signature(
+		&self,
+		author: Option<&(String, String)>,
+		timestamp: i64,
+	) -> Result<Signature<'static>> {
+		let (name, email) = author
+			.map(|(name, email)| (name.as_str(), email.as_str()))
+			.unwrap_or(("git-cliff", "git-cliff@localhost"));
+		Ok(Signature::new(name, email, &Time::new(timestamp, 0))?)
+	}
+
+	/// Creates a commit with the given parents and updates `HEAD`.
+	///
+	/// The tree is the merged tree of the parents with the files of the commit.
+	fn create_commit(
+		&mut self,
+		commit: &FixtureCommit,
+		parents: Vec<Oid>,
+	) -> Result<Oid> {
+		let parents = parents
+			.into_iter()
+			.map(|id| self.repository.find_commit(id))
+			.collect::<std::result::Result<Vec<_>, _>>()?;
+		let mut tree_id = match parents.as_slice() {
+			[] => self.repository.treebuilder(None)?.write()?,
+			[parent] => parent.tree_id(),
+			[ours, theirs, ..] => self
+				.repository
+				.merge_commits(ours, theirs, None)?
+				.write_tree_to(&self.repository)?,
+		};
+		if !commit.files.is_empty() {
+			let mut builder = TreeUpdateBuilder::new();
+			for (path, content) in &commit.files {
+				let blob = self.repository.blob(content.as_bytes())?;
+				builder.upsert(path, blob, FileMode::Blob);
+			}
+			tree_id = builder.create_updated(
+				&self.repository,
+				&self.repository.find_tree(tree_id)?,
+			)?;
+		}
+		let committer = self.signature(None, self.timestamp)?;
+		let author = self.signature(
+			commit.author.as_ref(),
+			commit.author_timestamp.unwrap_or(self.timestamp),
+		)?;
+		let id = self.repository.commit(
+			Some("HEAD"),
+			&author,
+			&committer,
+			&commit.message,
+			&self.repository.find_tree(tree_id)?,
+			&parents.iter().collect::<Vec<_>>(),
+		)?;
+		drop(parents);
+		self.timestamp += COMMIT_INTERVAL;
+		Ok(id)
+	}
+
+	/// Returns the releases of the history, newest first.
+	///
+	/// Each tag ends a release and the commits after the latest tag are
+	/// returned as an unreleased release (if any). The history is read with
+	/// [`ChangelogRepository`] like the changelog does, but the commits are
+	/// not processed.
+	pub fn releases(&self) -> Result<Vec<Release<'static>>> {
+		let repository = ChangelogRepository::init(self.dir.clone())?;
+		let tags = repository.tags(&[], false, SortBy::CommitDate)?;
+		let commits = repository.commits(None, None, None, SortBy::CommitDate)?;
+		let mut releases = vec![Release::default()];
+		for git_commit in commits.iter().rev() {
+			// Take the owned fields so that the commit outlives the repository.
+			let commit = Commit::from(git_commit);
+			let commit = Commit {
+				id: commit.id,
+				message: commit.message,
+				author: commit.author,
+				committer: commit.committer,
+				coauthors: commit.coauthors,
+				parents: commit.parents,
+				..Commit::default()
+			};
+			let release = releases.last_mut().expect("no release");
+			if let Some(tag) = tags.get(&commit.id) {
+				release.version = Some(tag.to_string());
+				release.commit_id = Some(commit.id.clone());
+				release.timestamp = git_commit.time().seconds();
+				release.commits.push(commit);
+				let previous = Release {
+					commits: Vec::new(),
+					previous: None,
+					..release.clone()
+				};
+				releases.push(Release {
+					previous: Some(Box::new(previous)),
+					..Release::default()
+				});
+			} else {
+				release.commits.push(commit);
+			}
+		}
+		if releases.last().map_or(false, |v| v.commits.is_empty()) {
+			releases.pop();
+		}
+		releases.reverse();
+		Ok(releases)
+	}
+}
+
+impl Drop for SampleRepository {
+	fn drop(&mut self) {
+		let _ = fs::remove_dir_all(&self.dir);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn merges_and_files() -> Result<()> {
+		let mut repository = SampleRepository::new()?;
+		repository
+			.commit("feat: initial commit")?
+			.branch("feature")?
+			.checkout("feature")?
+			.commit_with(FixtureCommit {
+				message: String::from("feat: add xyz"),
+				author: Some((
+					String::from("orhun"),
+					String::from("orhun@example.com"),
+				)),
+				files: vec![(String::from("src/xyz.rs"), String::from("xyz"))],
+				..FixtureCommit::default()
+			})?
+			.checkout("main")?
+			.commit_with(FixtureCommit {
+				message: String::from("fix: fix abc"),
+				files: vec![(String::from("abc.rs"), String::from("abc"))],
+				..FixtureCommit::default()
+			})?
+			.merge("feature", "Merge branch 'feature'")?
+			.annotated_tag("v1.0.0", "Release v1.0.0")?;
+
+		let releases = repository.releases()?;
+		assert_eq!(1, releases.len());
+		let commits = &releases[0].commits;
+		assert_eq!(
+			vec![
+				"feat: initial commit",
+				"feat: add xyz",
+				"fix: fix abc",
+				"Merge branch 'feature'",
+			],
+			commits
+				.iter()
+				.map(|v| v.message.as_str())
+				.collect::<Vec<&str>>()
+		);
+		assert_eq!(
+			"orhun@example.com",
+			serde_json::to_value(&commits[1].author)?["email"]
+		);
+		assert_eq!(
+			vec![commits[2].id.clone(), commits[1].id.clone()],
+			commits[3].parents
+		);
+		assert_eq!(Some(String::from("v1.0.0")), releases[0].version);
+
+		let repo = Repository::open(repository.path())?;
+		let tree = repo.revparse_single("v1.0.0")?.peel_to_tree()?;
+		assert!(tree.get_path(Path::new("src/xyz.rs")).is_ok());
+		assert!(tree.get_path(Path::new("abc.rs")).is_ok());
+		Ok(())
+	}
+}
@@ -0,0 +1,166 @@
+use crate::config::FragmentsConfig;
+use crate::error::Result;
+use std::fs;
+use std::path::{
+	Path,
+	PathBuf,
+};
+
+/// Default directory of the fragments.
+pub const DEFAULT_PATH: &str = "changes.d";
+
+/// Default commit types of the fragment types.
+pub const DEFAULT_TYPES: [(&str, &str); 5] = [
+	("feature", "feat"),
+	("bugfix", "fix"),
+	("doc", "docs"),
+	("removal", "refactor!"),
+	("misc", "chore"),
+];
+
+/// Changelog fragment that is added by a contributor, e.g.
+/// `changes.d/123.feature.md`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+	/// Path of the fragment file.
+	pub path: PathBuf,
+	/// Identifier of the fragment (e.g. the pull request number).
+	pub id:   String,
+	/// Type of the fragment (e.g. `feature`).
+	pub kind: String,
+	/// Text of the fragment.
+	pub text: String,
+}
+
+impl Fragment {
+	/// Parses the fragment from the given file.
+	///
+	/// The name of the file is `<id>.<type>` with an optional counter and
+	/// extension (e.g. `123.bugfix.1.md`). Returns `None` for the other files.
+	pub fn parse(path: &Path) -> Result<Option<Self>> {
+		let name = match path.file_name().and_then(|v| v.to_str()) {
+			Some(name) if !name.starts_with('.') => name,
+			_ => return Ok(None),
+		};
+		let mut parts = name.split('.');
+		let (id, kind) = match (parts.next(), parts.next()) {
+			(Some(id), Some(kind)) if !id.is_empty() && !kind.is_empty() => {
+				(id.to_string(), kind.to_string())
+			}
+			_ => return Ok(None),
+		};
+		let text = fs::read_to_string(path)?.replace("\r\n", "\n");
+		Ok(Some(Self {
+			path: path.to_path_buf(),
+			id,
+			kind,
+			text: text.trim().to_string(),
+		}))
+	}
+
+	/// Returns the fragment as a conventional commit message.
+	///
+	/// The commit type is looked up from the configured types by the type of
+	/// the fragment and numeric identifiers are appended as references, e.g.
+	/// `feat: add xyz (#123)`.
+	pub fn to_message(&self, config: &FragmentsConfig) -> String {
+		let commit_type = config
+			.types
+			.as_ref()
+			.and_then(|types| types.get(&self.kind).map(String::as_str))
+			.or_else(|| {
+				DEFAULT_TYPES
+					.iter()
+					.find(|(kind, _)| *kind == self.kind)
+					.map(|(_, commit_type)| *commit_type)
+			})
+			.unwrap_or(self.kind.as_str());
+		let mut lines = self.text.splitn(2, '\n');
+		let mut message =
+			format!("{commit_type}: {}", lines.next().unwrap_or_default().trim());
+		if self.id.chars().all(|c| c.is_ascii_digit()) {
+			message += &format!(" (#{})", self.id);
+		}
+		if let Some(body) = lines.next() {
+			message += "\n\n";
+			message += body.trim();
+		}
+		message
+	}
+}
+
+/// Loads the fragments in the configured directory.
+///
+/// The fragments are sorted by their names and an empty list is returned if
+/// the directory does not exist.
+pub fn load(config: &FragmentsConfig) -> Result<Vec<Fragment>> {
+	let dir = Path::new(config.path.as_deref().unwrap_or(DEFAULT_PATH));
+	if !dir.is_dir() {
+		return Ok(Vec::new());
+	}
+	let mut paths = fs::read_dir(dir)?
+		.map(|entry| entry.map(|v| v.path()))
+		.collect::<std::io::Result<Vec<PathBuf>>>()?;
+	paths.sort();
+	let mut fragments = Vec::new();
+	for path in paths.iter().filter(|path| path.is_file()) {
+		if let Some(fragment) = Fragment::parse(path)? {
+			fragments.push(fragment);
+		}
+	}
+	Ok(fragments)
+}
+
+/// Deletes the given (consumed) fragments.
+pub fn remove(fragments: &[Fragment]) -> Result<()> {
+	for fragment in fragments {
+		fs::remove_file(&fragment.path)?;
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::collections::HashMap;
+	use std::env;
+
+	#[test]
+	fn load_fragments() -> Result<()> {
+		let dir = env::temp_dir().join("git-cliff-changes.d");
+		if dir.exists() {
+			fs::remove_dir_all(&dir)?;
+		}
+		fs::create_dir_all(&dir)?;
+		fs::write(dir.join("123.feature.md"), "Add xyz\n")?;
+		fs::write(dir.join("abc.bugfix.1"), "Fix abc\n\nDetails.\r\n")?;
+		fs::write(dir.join("124.security.md"), "Update deps")?;
+		fs::write(dir.join(".gitkeep"), "")?;
+		fs::write(dir.join("README"), "")?;
+		let config = FragmentsConfig {
+			path:  Some(dir.to_string_lossy().to_string()),
+			types: Some(HashMap::from([(
+				String::from("security"),
+				String::from("fix(security)"),
+			)])),
+		};
+		let fragments = load(&config)?;
+		assert_eq!(
+			vec![
+				"feat: Add xyz (#123)",
+				"fix(security): Update deps (#124)",
+				"fix: Fix abc\n\nDetails.",
+			],
+			fragments
+				.iter()
+				.map(|v| v.to_message(&config))
+				.collect::<Vec<String>>()
+		);
+		remove(&fragments)?;
+		assert!(load(&config)?.is_empty());
+		assert!(dir.join("README").exists());
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+}
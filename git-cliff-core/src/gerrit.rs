@@ -0,0 +1,126 @@
+use crate::config::GerritConfig;
+use crate::error::Result;
+use crate::retry::SendWithRetry;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+use reqwest::RequestBuilder;
+use serde::Deserialize;
+
+/// Regex for matching the `Change-Id` trailer of a commit message.
+static CHANGE_ID_REGEX: Lazy<Regex> =
+	lazy_regex!(r"(?m)^Change-Id:\s*(I[0-9a-f]{40})\s*$");
+
+/// Prefix of the JSON responses for preventing XSSI.
+const XSSI_PREFIX: &str = ")]}'";
+
+/// Change on Gerrit that a commit is reviewed in.
+#[derive(
+	Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct Change {
+	/// Change-Id of the change.
+	pub id:    String,
+	/// Link to the review of the change.
+	pub url:   String,
+	/// Username (or name) of the owner of the change.
+	pub owner: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ChangeInfo {
+	owner: Account,
+}
+
+#[derive(Deserialize, Debug)]
+struct Account {
+	username: Option<String>,
+	name:     Option<String>,
+}
+
+/// Returns the Change-Id in the trailers of the given commit message.
+pub fn change_id(message: &str) -> Option<&str> {
+	CHANGE_ID_REGEX
+		.captures_iter(message)
+		.last()
+		.and_then(|captures| captures.get(1))
+		.map(|v| v.as_str())
+}
+
+/// Returns the link to the review of the given change.
+pub fn review_url(base_url: &str, change_id: &str) -> String {
+	format!("{}/q/{change_id}", base_url.trim_end_matches('/'))
+}
+
+/// Returns the username (or name) of the owner of the given change.
+pub async fn get_change_owner(
+	config: &GerritConfig,
+	change_id: &str,
+) -> Result<Option<String>> {
+	let base_url = config.url.as_deref().unwrap_or_default();
+	// Authenticated requests are sent to the endpoints under `/a/`.
+	let url = format!(
+		"{}{}/changes/",
+		base_url.trim_end_matches('/'),
+		if config.token.is_some() { "/a" } else { "" }
+	);
+	let body = get_gerrit(&url, config)
+		.query(&[("q", format!("change:{change_id}").as_str())])
+		.query(&[("o", "DETAILED_ACCOUNTS")])
+		.send_with_retry()
+		.await?
+		.error_for_status()?
+		.text()
+		.await?;
+	Ok(parse_changes(&body)?
+		.into_iter()
+		.next()
+		.and_then(|change| change.owner.username.or(change.owner.name)))
+}
+
+/// Parses the changes in the given response.
+fn parse_changes(body: &str) -> Result<Vec<ChangeInfo>> {
+	let body = body.trim_start();
+	let body = body.strip_prefix(XSSI_PREFIX).unwrap_or(body);
+	Ok(serde_json::from_str(body)?)
+}
+
+fn get_gerrit(url: &str, config: &GerritConfig) -> RequestBuilder {
+	let mut request = reqwest::Client::new()
+		.get(url)
+		.header("User-Agent", "git-cliff");
+	if let Some(token) = &config.token {
+		request = request
+			.basic_auth(config.username.as_deref().unwrap_or_default(), Some(token));
+	}
+	request
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn parse_change() -> Result<()> {
+		let id = "I8473b95934b5732ac55d26311a706c9c2bde9940";
+		assert_eq!(
+			Some(id),
+			change_id(&format!("feat: add xyz\n\nChange-Id: {id}\n"))
+		);
+		assert_eq!(None, change_id("feat: add xyz\n\nChange-Id: I123\n"));
+		assert_eq!(
+			format!("https://review.example.com/q/{id}"),
+			review_url("https://review.example.com/", id)
+		);
+		let changes = parse_changes(
+			r#")]}'
+[{"owner": {"_account_id": 1, "name": "John Doe", "username": "john"}}]"#,
+		)?;
+		assert_eq!(Some(String::from("john")), changes[0].owner.username);
+		assert_eq!(Some(String::from("John Doe")), changes[0].owner.name);
+		Ok(())
+	}
+}
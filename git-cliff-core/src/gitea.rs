@@ -0,0 +1,129 @@
+use crate::config::GiteaConfig;
+use crate::error::Result;
+use crate::retry::SendWithRetry;
+use reqwest::{
+	RequestBuilder,
+	StatusCode,
+};
+use serde::Deserialize;
+
+/// Well-known public instances of Gitea (and Forgejo).
+pub const PUBLIC_INSTANCES: [&str; 2] =
+	["https://codeberg.org", "https://gitea.com"];
+
+#[derive(Deserialize, Debug)]
+struct Commit {
+	author: Option<User>,
+}
+
+#[derive(Deserialize, Debug)]
+struct User {
+	login: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Pr {
+	number: u32,
+}
+
+/// Returns the well-known public instance that hosts the repository with the
+/// given web URL (if any).
+pub fn public_instance(url: &str) -> Option<&'static str> {
+	PUBLIC_INSTANCES.into_iter().find(|instance| {
+		url.strip_prefix(instance)
+			.map_or(false, |path| path.starts_with('/'))
+	})
+}
+
+/// Returns the base URL of the API of the Gitea instance.
+fn api_url(config: &GiteaConfig, repo: &str) -> String {
+	format!(
+		"{}/api/v1/repos/{repo}",
+		config
+			.url
+			.as_deref()
+			.unwrap_or_default()
+			.trim_end_matches('/')
+	)
+}
+
+/// Returns the username of the author of the given commit (if it belongs to a
+/// user of the instance).
+pub async fn get_commit_author(
+	config: &GiteaConfig,
+	repo: &str,
+	commit_sha: &str,
+) -> Result<Option<String>> {
+	let url = format!("{}/git/commits/{commit_sha}", api_url(config, repo));
+	let commit = get_gitea(&url, config)
+		.send_with_retry()
+		.await?
+		.error_for_status()?
+		.json::<Commit>()
+		.await?;
+	Ok(commit.author.map(|author| author.login))
+}
+
+/// Returns the number of the pull request that the given commit is merged by.
+pub async fn get_prs_associated_with_commit(
+	config: &GiteaConfig,
+	repo: &str,
+	commit_sha: &str,
+) -> Result<Vec<u32>> {
+	let url = format!("{}/commits/{commit_sha}/pull", api_url(config, repo));
+	let response = get_gitea(&url, config).send_with_retry().await?;
+	if response.status() == StatusCode::NOT_FOUND {
+		return Ok(Vec::new());
+	}
+	let pr = response.error_for_status()?.json::<Pr>().await?;
+	Ok(vec![pr.number])
+}
+
+/// Returns the usernames of the authors of the commits in the given pull
+/// request.
+pub async fn get_pr_authors(
+	config: &GiteaConfig,
+	repo: &str,
+	pr_number: u32,
+) -> Result<Vec<String>> {
+	let url = format!("{}/pulls/{pr_number}/commits", api_url(config, repo));
+	let commits = get_gitea(&url, config)
+		.send_with_retry()
+		.await?
+		.error_for_status()?
+		.json::<Vec<Commit>>()
+		.await?;
+	Ok(commits
+		.into_iter()
+		.filter_map(|commit| commit.author.map(|author| author.login))
+		.collect())
+}
+
+fn get_gitea(url: &str, config: &GiteaConfig) -> RequestBuilder {
+	let mut request = reqwest::Client::new()
+		.get(url)
+		.header("User-Agent", "git-cliff");
+	if let Some(token) = &config.token {
+		request = request.header("Authorization", format!("token {token}"));
+	}
+	request
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn detect_public_instance() {
+		assert_eq!(
+			Some("https://codeberg.org"),
+			public_instance("https://codeberg.org/owner/repo")
+		);
+		assert_eq!(
+			None,
+			public_instance("https://codeberg.org.example.com/repo")
+		);
+		assert_eq!(None, public_instance("https://git.example.com/owner/repo"));
+	}
+}
@@ -1,6 +1,36 @@
-use reqwest::RequestBuilder;
-use serde::Deserialize;
-use crate::error::Result;
+use reqwest::header::HeaderMap;
+use reqwest::{
+	Method,
+	RequestBuilder,
+	Response,
+	StatusCode,
+};
+use serde::{
+	Deserialize,
+	Serialize,
+};
+use std::time::{
+	Duration,
+	SystemTime,
+	UNIX_EPOCH,
+};
+use crate::config::GithubConfig;
+use crate::error::{
+	Error,
+	Result,
+};
+use crate::provider::{
+	BoxFuture,
+	PullRequest,
+};
+use crate::release::{
+	RemoteAsset,
+	RemoteRelease,
+};
+use crate::retry::{
+	self,
+	SendWithRetry,
+};
 
 #[derive(Deserialize, Debug)]
 struct Commit {
@@ -17,44 +47,702 @@ pub struct Pr {
 	number: u32,
 }
 
+#[derive(Deserialize, Debug)]
+struct Issue {
+	title:     String,
+	comments:  u32,
+	#[serde(default)]
+	reactions: Option<Reactions>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Reactions {
+	total_count: u32,
+}
+
+/// Published release on Github.
+#[derive(Deserialize, Debug)]
+pub struct Release {
+	/// ID of the release.
+	pub id:           u64,
+	/// Name of the tag that the release is created from.
+	pub tag_name:     String,
+	/// Title of the release.
+	#[serde(default)]
+	pub name:         Option<String>,
+	/// Release notes.
+	pub body:         Option<String>,
+	/// URL of the release page.
+	#[serde(default)]
+	pub html_url:     Option<String>,
+	/// Publication date of the release in RFC 3339 format.
+	#[serde(default)]
+	pub published_at: Option<String>,
+	/// Whether the release is a draft.
+	#[serde(default)]
+	pub draft:        bool,
+	/// Files that are uploaded to the release.
+	#[serde(default)]
+	pub assets:       Vec<Asset>,
+}
+
+/// File that is uploaded to a release on Github.
+#[derive(Deserialize, Debug)]
+pub struct Asset {
+	/// File name.
+	pub name:                 String,
+	/// Download URL of the file.
+	pub browser_download_url: String,
+	/// Size of the file in bytes.
+	pub size:                 u64,
+	/// Number of the downloads.
+	pub download_count:       u64,
+}
+
+impl From<Release> for RemoteRelease {
+	fn from(release: Release) -> Self {
+		Self {
+			name:   release.name,
+			body:   release.body,
+			url:    release.html_url,
+			assets: release
+				.assets
+				.into_iter()
+				.map(|asset| RemoteAsset {
+					name:           asset.name,
+					url:            asset.browser_download_url,
+					size:           asset.size,
+					download_count: asset.download_count,
+				})
+				.collect(),
+		}
+	}
+}
+
+#[derive(Deserialize, Debug)]
+struct User {
+	login: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct RateLimit {
+	resources: RateLimitResources,
+}
+
+#[derive(Deserialize, Debug)]
+struct RateLimitResources {
+	core: RateLimitStatus,
+}
+
+#[derive(Deserialize, Debug)]
+struct RateLimitStatus {
+	limit:     u32,
+	remaining: u32,
+	reset:     i64,
+}
+
+/// Status of a validated Github token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenStatus {
+	/// Username of the owner of the token.
+	///
+	/// This is not available for the installation tokens of Github apps (e.g.
+	/// `GITHUB_TOKEN` in Github Actions).
+	pub login:     Option<String>,
+	/// Maximum number of the requests per hour.
+	pub limit:     u32,
+	/// Remaining number of the requests in the current hour.
+	pub remaining: u32,
+}
+
+/// Release to create or update on Github.
+#[derive(Serialize, Debug)]
+pub struct NewRelease<'a> {
+	/// Name of the tag to create the release from.
+	pub tag_name:   &'a str,
+	/// Title of the release.
+	pub name:       &'a str,
+	/// Release notes.
+	pub body:       &'a str,
+	/// Whether the release is a draft.
+	pub draft:      bool,
+	/// Whether the release is a pre-release.
+	pub prerelease: bool,
+}
+
+/// Repository on Github.
+#[derive(Deserialize, Debug)]
+pub struct Repository {
+	/// Name of the repository.
+	pub name:      String,
+	/// Name of the repository with owner.
+	pub full_name: String,
+	/// URL of the repository page.
+	pub html_url:  String,
+	/// Whether the repository is archived.
+	#[serde(default)]
+	pub archived:  bool,
+}
+
+/// Tag of a Github repository.
+#[derive(Deserialize, Debug)]
+pub struct Tag {
+	/// Name of the tag.
+	pub name:   String,
+	/// Commit that the tag points to.
+	pub commit: TagCommit,
+}
+
+/// Commit that a tag points to.
+#[derive(Deserialize, Debug)]
+pub struct TagCommit {
+	/// SHA of the commit.
+	pub sha: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct CommitDetails {
+	commit: CommitData,
+}
+
+#[derive(Deserialize, Debug)]
+struct CommitData {
+	committer: CommitSignature,
+}
+
+#[derive(Deserialize, Debug)]
+struct CommitSignature {
+	date: String,
+}
+
+/// URL of the Github API that is used by default.
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Maximum duration to wait for the rate limit to reset.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Maximum number of the retries after waiting for the rate limit.
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Maximum jitter that is added to the wait for the rate limit.
+const JITTER: Duration = Duration::from_secs(1);
+
+/// Maximum number of items per page of the Github API.
+const MAX_PER_PAGE: usize = 100;
+
+/// Returns the URL of the configured Github API without the trailing slash.
+pub fn api_url(config: &GithubConfig) -> &str {
+	config
+		.api_url
+		.as_deref()
+		.unwrap_or(DEFAULT_API_URL)
+		.trim_end_matches('/')
+}
+
+/// Returns the URL of the Github instance of the configured API, e.g.
+/// `https://github.example.com` for `https://github.example.com/api/v3`.
+pub fn web_url(config: &GithubConfig) -> String {
+	match api_url(config) {
+		DEFAULT_API_URL => String::from("https://github.com"),
+		api_url => api_url.trim_end_matches("/api/v3").to_string(),
+	}
+}
+
+/// Returns the repository name with owner (e.g. `orhun/git-cliff`) if the
+/// given remote URL belongs to the Github instance at the given URL.
+pub fn repository_from_remote(web_url: &str, remote: &str) -> Option<String> {
+	let host = web_url.split("://").last()?.trim_end_matches('/');
+	let remote = remote.trim_end_matches('/');
+	let remote = remote.strip_suffix(".git").unwrap_or(remote);
+	let remote = remote.split("://").last()?;
+	let remote = remote.split_once('@').map(|(_, v)| v).unwrap_or(remote);
+	let path = remote.strip_prefix(host)?.strip_prefix([':', '/'])?;
+	match path.split('/').collect::<Vec<&str>>()[..] {
+		[owner, repo] if !owner.is_empty() && !repo.is_empty() => {
+			Some(format!("{owner}/{repo}"))
+		}
+		_ => None,
+	}
+}
+
 pub async fn get_commit_author(
+	api_url: &str,
 	token: &Option<String>,
 	repo: &str,
 	commit_sha: &str,
 ) -> Result<String> {
-	let url = format!("https://api.github.com/repos/{repo}/commits/{commit_sha}");
-	let commit = get_github(&url, token).send().await?.json::<Commit>().await?;
+	let url = format!("{api_url}/repos/{repo}/commits/{commit_sha}");
+	let commit = get_github(&url, token)
+		.send_github()
+		.await?
+		.json::<Commit>()
+		.await?;
 	Ok(commit.author.login)
 }
 
 pub async fn get_prs_associated_with_commit(
+	api_url: &str,
 	token: &Option<String>,
 	repo: &str,
 	commit_sha: &str,
 ) -> Result<Vec<u32>> {
-	let url = format!("https://api.github.com/repos/{repo}/commits/{commit_sha}/pulls");
-	let prs = get_github(&url, token).send().await?.json::<Vec<Pr>>().await?;
+	let url = format!("{api_url}/repos/{repo}/commits/{commit_sha}/pulls");
+	let prs = get_github(&url, token)
+		.send_github()
+		.await?
+		.json::<Vec<Pr>>()
+		.await?;
 	Ok(prs.into_iter().map(|p| p.number).collect())
 }
 
 pub async fn get_pr_authors(
+	api_url: &str,
 	token: &Option<String>,
 	repo: &str,
 	pr_number: &u32,
 ) -> Result<Vec<String>> {
-	let url = format!("https://api.github.com/repos/{repo}/pulls/{pr_number}/commits");
-	let commits: Vec<Commit> = get_github(&url, token).send().await?.json().await?;
+	let url = format!("{api_url}/repos/{repo}/pulls/{pr_number}/commits");
+	let commits: Vec<Commit> =
+		get_github(&url, token).send_github().await?.json().await?;
 	let authors = commits.into_iter().map(|c| c.author.login).collect();
 	Ok(authors)
 }
 
+/// Returns the pull request with its comment and reaction counts.
+pub async fn get_pull_request(
+	api_url: &str,
+	token: &Option<String>,
+	repo: &str,
+	pr_number: u32,
+) -> Result<PullRequest> {
+	// Pull requests are issues, which include the reactions.
+	let url = format!("{api_url}/repos/{repo}/issues/{pr_number}");
+	let issue = get_github(&url, token)
+		.send_github()
+		.await?
+		.error_for_status()?
+		.json::<Issue>()
+		.await?;
+	Ok(PullRequest {
+		number:    pr_number,
+		title:     Some(issue.title),
+		comments:  issue.comments,
+		reactions: issue.reactions.map_or(0, |v| v.total_count),
+	})
+}
+
+/// Returns the release which is published for the given tag (if any).
+pub async fn get_release_by_tag(
+	api_url: &str,
+	token: &Option<String>,
+	repo: &str,
+	tag: &str,
+) -> Result<Option<Release>> {
+	let url = format!("{api_url}/repos/{repo}/releases/tags/{tag}");
+	let response = get_github(&url, token).send_github().await?;
+	if response.status() == StatusCode::NOT_FOUND {
+		return Ok(None);
+	}
+	Ok(Some(response.error_for_status()?.json::<Release>().await?))
+}
+
+/// Updates the notes of the release with the given ID.
+pub async fn update_release_body(
+	api_url: &str,
+	token: &Option<String>,
+	repo: &str,
+	release_id: u64,
+	body: &str,
+) -> Result<()> {
+	let url = format!("{api_url}/repos/{repo}/releases/{release_id}");
+	request_github(Method::PATCH, &url, token)
+		.json(&serde_json::json!({ "body": body }))
+		.send_github()
+		.await?
+		.error_for_status()?;
+	Ok(())
+}
+
+/// Creates a release for the given tag and returns it.
+pub async fn create_release(
+	api_url: &str,
+	token: &Option<String>,
+	repo: &str,
+	release: &NewRelease<'_>,
+) -> Result<Release> {
+	let url = format!("{api_url}/repos/{repo}/releases");
+	Ok(request_github(Method::POST, &url, token)
+		.json(release)
+		.send_github()
+		.await?
+		.error_for_status()?
+		.json::<Release>()
+		.await?)
+}
+
+/// Updates the release with the given ID and returns it.
+pub async fn update_release(
+	api_url: &str,
+	token: &Option<String>,
+	repo: &str,
+	release_id: u64,
+	release: &NewRelease<'_>,
+) -> Result<Release> {
+	let url = format!("{api_url}/repos/{repo}/releases/{release_id}");
+	Ok(request_github(Method::PATCH, &url, token)
+		.json(release)
+		.send_github()
+		.await?
+		.error_for_status()?
+		.json::<Release>()
+		.await?)
+}
+
+/// Returns the repositories of the given organization.
+pub async fn get_org_repositories(
+	api_url: &str,
+	token: &Option<String>,
+	org: &str,
+) -> Result<Vec<Repository>> {
+	let mut repositories = Vec::new();
+	for page in 1.. {
+		let url = format!(
+			"{api_url}/orgs/{org}/repos?per_page={MAX_PER_PAGE}&page={page}"
+		);
+		let page = get_github(&url, token)
+			.send_github()
+			.await?
+			.error_for_status()?
+			.json::<Vec<Repository>>()
+			.await?;
+		let is_last = page.len() < MAX_PER_PAGE;
+		repositories.extend(page);
+		if is_last {
+			break;
+		}
+	}
+	Ok(repositories)
+}
+
+/// Returns the latest releases of the given repository.
+pub async fn get_releases(
+	api_url: &str,
+	token: &Option<String>,
+	repo: &str,
+	limit: usize,
+) -> Result<Vec<Release>> {
+	let url = format!(
+		"{api_url}/repos/{repo}/releases?per_page={}",
+		limit.min(MAX_PER_PAGE)
+	);
+	let releases = get_github(&url, token)
+		.send_github()
+		.await?
+		.error_for_status()?
+		.json::<Vec<Release>>()
+		.await?;
+	Ok(releases.into_iter().take(limit).collect())
+}
+
+/// Returns the latest tags of the given repository.
+pub async fn get_tags(
+	api_url: &str,
+	token: &Option<String>,
+	repo: &str,
+	limit: usize,
+) -> Result<Vec<Tag>> {
+	let url = format!(
+		"{api_url}/repos/{repo}/tags?per_page={}",
+		limit.min(MAX_PER_PAGE)
+	);
+	let tags = get_github(&url, token)
+		.send_github()
+		.await?
+		.error_for_status()?
+		.json::<Vec<Tag>>()
+		.await?;
+	Ok(tags.into_iter().take(limit).collect())
+}
+
+/// Returns the committer date of the given commit in RFC 3339 format.
+pub async fn get_commit_date(
+	api_url: &str,
+	token: &Option<String>,
+	repo: &str,
+	commit_sha: &str,
+) -> Result<String> {
+	let url = format!("{api_url}/repos/{repo}/commits/{commit_sha}");
+	let commit = get_github(&url, token)
+		.send_github()
+		.await?
+		.error_for_status()?
+		.json::<CommitDetails>()
+		.await?;
+	Ok(commit.commit.committer.date)
+}
+
+/// Validates the given token (or the unauthenticated access if it is not set)
+/// before the run.
+///
+/// Fails with [`Error::GithubTokenError`] if the token is invalid, the rate
+/// limit is exceeded or the repository cannot be accessed.
+pub async fn check_token(
+	api_url: &str,
+	token: &Option<String>,
+	repo: &str,
+) -> Result<TokenStatus> {
+	let mut login = None;
+	if token.is_some() {
+		let response = get_github(&format!("{api_url}/user"), token)
+			.send_github()
+			.await?;
+		login = match response.status() {
+			StatusCode::UNAUTHORIZED => {
+				return Err(Error::GithubTokenError(String::from(
+					"the token is invalid or expired",
+				)));
+			}
+			// Installation tokens cannot access the authenticated user.
+			StatusCode::FORBIDDEN => None,
+			_ => Some(response.error_for_status()?.json::<User>().await?.login),
+		};
+	}
+	let rate_limit = get_github(&format!("{api_url}/rate_limit"), token)
+		.send_github()
+		.await?
+		.error_for_status()?
+		.json::<RateLimit>()
+		.await?
+		.resources
+		.core;
+	if rate_limit.remaining == 0 {
+		return Err(Error::GithubRateLimit(format_timestamp(rate_limit.reset)));
+	}
+	let response = get_github(&format!("{api_url}/repos/{repo}"), token)
+		.send_github()
+		.await?;
+	if matches!(
+		response.status(),
+		StatusCode::NOT_FOUND | StatusCode::FORBIDDEN
+	) {
+		return Err(Error::GithubTokenError(format!(
+			"the repository '{repo}' cannot be accessed {}",
+			if token.is_some() {
+				"with the token"
+			} else {
+				"without a token"
+			}
+		)));
+	}
+	response.error_for_status()?;
+	Ok(TokenStatus {
+		login,
+		limit: rate_limit.limit,
+		remaining: rate_limit.remaining,
+	})
+}
+
+/// Returns the Unix timestamp that the rate limit resets at if the response
+/// is rejected because of it.
+///
+/// Both the primary (`X-RateLimit-Remaining`) and the secondary
+/// (`Retry-After`) rate limits are detected.
+fn rate_limit_reset(
+	status: StatusCode,
+	headers: &HeaderMap,
+	now: u64,
+) -> Option<u64> {
+	if !matches!(
+		status,
+		StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS
+	) {
+		return None;
+	}
+	let header = |name| {
+		headers
+			.get(name)
+			.and_then(|v| v.to_str().ok())
+			.and_then(|v| v.parse::<u64>().ok())
+	};
+	if let Some(seconds) = header("retry-after") {
+		Some(now + seconds)
+	} else if header("x-ratelimit-remaining") == Some(0) {
+		Some(header("x-ratelimit-reset").unwrap_or(now).max(now))
+	} else {
+		None
+	}
+}
+
+/// Returns the given Unix timestamp in a human-readable form.
+fn format_timestamp(timestamp: i64) -> String {
+	chrono::NaiveDateTime::from_timestamp_opt(timestamp, 0)
+		.map_or_else(|| timestamp.to_string(), |v| format!("{v} UTC"))
+}
+
+/// Extension for sending the requests to the Github API.
+trait SendGithub {
+	/// Sends the request with the retry policy and waits for the rate limit to
+	/// reset (if it is soon) before retrying.
+	///
+	/// Fails with [`Error::GithubRateLimit`] if the rate limit does not reset
+	/// in time or the request is not [retryable](retry::is_retryable).
+	fn send_github(self) -> BoxFuture<'static, Response>;
+}
+
+impl SendGithub for RequestBuilder {
+	fn send_github(self) -> BoxFuture<'static, Response> {
+		Box::pin(async move {
+			let mut request = self;
+			let mut retries = 0;
+			loop {
+				// The requests that are not idempotent (e.g. creating a release)
+				// are never sent again.
+				let next = request
+					.try_clone()
+					.filter(|_| retry::is_retryable(&request));
+				let response = request.send_with_retry().await?;
+				let now = SystemTime::now()
+					.duration_since(UNIX_EPOCH)
+					.map_or(0, |v| v.as_secs());
+				let status = response.status();
+				let reset = match rate_limit_reset(status, response.headers(), now) {
+					Some(reset) => reset,
+					None => return Ok(response),
+				};
+				let wait = Duration::from_secs(reset - now);
+				match next {
+					Some(next)
+						if retries < MAX_RATE_LIMIT_RETRIES &&
+							wait <= MAX_RATE_LIMIT_WAIT =>
+					{
+						tokio::time::sleep(wait + retry::jitter(JITTER)).await;
+						request = next;
+						retries += 1;
+					}
+					_ => {
+						return Err(Error::GithubRateLimit(format_timestamp(
+							reset as i64,
+						)));
+					}
+				}
+			}
+		})
+	}
+}
+
 fn get_github(url: &str, token: &Option<String>) -> RequestBuilder {
+	request_github(Method::GET, url, token)
+}
+
+fn request_github(
+	method: Method,
+	url: &str,
+	token: &Option<String>,
+) -> RequestBuilder {
 	let client = reqwest::Client::new();
-	let mut request = client.get(url);
+	let mut request = client
+		.request(method, url)
+		.header("User-Agent", "git-cliff");
 	if let Some(token) = token {
-		request = request
-			.header("Authorization", format!("token {token}"))
-			.header("User-Agent", "git-cliff");
+		request = request.header("Authorization", format!("token {token}"));
 	}
 	request
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use reqwest::header::HeaderValue;
+
+	#[test]
+	fn repository_of_remote() {
+		let config = GithubConfig::default();
+		assert_eq!("https://github.com", web_url(&config));
+		assert_eq!(
+			Some(String::from("orhun/git-cliff")),
+			repository_from_remote(
+				&web_url(&config),
+				"git@github.com:orhun/git-cliff.git"
+			)
+		);
+		let config = GithubConfig {
+			api_url: Some(String::from("https://github.example.com/api/v3/")),
+			..GithubConfig::default()
+		};
+		assert_eq!("https://github.example.com", web_url(&config));
+		assert_eq!(
+			Some(String::from("org/repo")),
+			repository_from_remote(
+				&web_url(&config),
+				"https://github.example.com/org/repo"
+			)
+		);
+		assert_eq!(
+			None,
+			repository_from_remote(
+				&web_url(&config),
+				"https://github.com/orhun/git-cliff"
+			)
+		);
+		assert_eq!(
+			None,
+			repository_from_remote(
+				&web_url(&config),
+				"https://github.example.com/org/repo/tree/main"
+			)
+		);
+	}
+
+	#[test]
+	fn detect_rate_limit() {
+		let mut headers = HeaderMap::new();
+		assert_eq!(None, rate_limit_reset(StatusCode::FORBIDDEN, &headers, 100));
+		headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+		headers.insert("x-ratelimit-reset", HeaderValue::from_static("160"));
+		assert_eq!(
+			Some(160),
+			rate_limit_reset(StatusCode::FORBIDDEN, &headers, 100)
+		);
+		assert_eq!(None, rate_limit_reset(StatusCode::OK, &headers, 100));
+		headers.insert("retry-after", HeaderValue::from_static("30"));
+		assert_eq!(
+			Some(130),
+			rate_limit_reset(StatusCode::TOO_MANY_REQUESTS, &headers, 100)
+		);
+		assert_eq!("1970-01-01 00:01:40 UTC", format_timestamp(100));
+	}
+
+	#[test]
+	fn remote_release() -> Result<()> {
+		let release: Release = serde_json::from_str(
+			r#"{
+				"id": 1,
+				"tag_name": "v1.0.0",
+				"name": "v1.0.0",
+				"body": "Notes",
+				"html_url": "https://github.com/owner/repo/releases/tag/v1.0.0",
+				"assets": [{
+					"name": "app.tar.gz",
+					"browser_download_url": "https://github.com/owner/repo/releases/download/v1.0.0/app.tar.gz",
+					"size": 1024,
+					"download_count": 42
+				}]
+			}"#,
+		)?;
+		let release = RemoteRelease::from(release);
+		assert_eq!(Some(String::from("Notes")), release.body);
+		assert_eq!(
+			vec![RemoteAsset {
+				name:           String::from("app.tar.gz"),
+				url:            String::from(
+					"https://github.com/owner/repo/releases/download/v1.0.0/app.tar.gz"
+				),
+				size:           1024,
+				download_count: 42,
+			}],
+			release.assets
+		);
+		Ok(())
+	}
+}
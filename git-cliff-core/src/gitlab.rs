@@ -0,0 +1,156 @@
+use crate::config::GitlabConfig;
+use crate::error::Result;
+use crate::retry::SendWithRetry;
+use reqwest::RequestBuilder;
+use serde::Deserialize;
+
+/// URL of the GitLab instance that is used by default.
+pub const DEFAULT_URL: &str = "https://gitlab.com";
+
+/// User on GitLab.
+#[derive(Deserialize, Debug)]
+pub struct User {
+	/// Username of the user.
+	pub username: String,
+}
+
+/// Merge request on GitLab.
+#[derive(Deserialize, Debug)]
+pub struct MergeRequest {
+	/// Project-level ID of the merge request.
+	pub iid:    u32,
+	/// Author of the merge request.
+	pub author: User,
+}
+
+/// Returns the base URL of the GitLab instance without the trailing slash.
+pub fn base_url(config: &GitlabConfig) -> &str {
+	config
+		.url
+		.as_deref()
+		.unwrap_or(DEFAULT_URL)
+		.trim_end_matches('/')
+}
+
+/// Returns the path of the project (e.g. `group/subgroup/project`) if the
+/// given remote URL belongs to the given GitLab instance.
+pub fn project_from_remote(base_url: &str, remote: &str) -> Option<String> {
+	let host = base_url.split("://").last()?.trim_end_matches('/');
+	let remote = remote.trim_end_matches('/');
+	let remote = remote.strip_suffix(".git").unwrap_or(remote);
+	let remote = remote.split("://").last()?;
+	let remote = remote.split_once('@').map(|(_, v)| v).unwrap_or(remote);
+	let path = remote.strip_prefix(host)?;
+	let path = match path.strip_prefix(':') {
+		Some(path) => {
+			// Skip the port number (if any).
+			let port = path.find(|c: char| !c.is_ascii_digit()).unwrap_or(0);
+			path[port..].strip_prefix('/').unwrap_or(path)
+		}
+		None => path.strip_prefix('/')?,
+	};
+	path.contains('/').then(|| path.to_string())
+}
+
+/// Returns the username of the user with the given public email (if any).
+pub async fn get_username_by_email(
+	config: &GitlabConfig,
+	email: &str,
+) -> Result<Option<String>> {
+	let url = format!("{}/api/v4/users", base_url(config));
+	let users = get_gitlab(&url, config)
+		.query(&[("search", email)])
+		.send_with_retry()
+		.await?
+		.error_for_status()?
+		.json::<Vec<User>>()
+		.await?;
+	Ok(users.into_iter().next().map(|user| user.username))
+}
+
+/// Returns the merge requests that the given commit is associated with.
+pub async fn get_merge_requests_associated_with_commit(
+	config: &GitlabConfig,
+	project: &str,
+	commit_sha: &str,
+) -> Result<Vec<MergeRequest>> {
+	let url = format!(
+		"{}/api/v4/projects/{}/repository/commits/{commit_sha}/merge_requests",
+		base_url(config),
+		encode_project(project)
+	);
+	let merge_requests = get_gitlab(&url, config)
+		.send_with_retry()
+		.await?
+		.error_for_status()?
+		.json::<Vec<MergeRequest>>()
+		.await?;
+	Ok(merge_requests)
+}
+
+/// Returns the usernames of the participants of the given merge request.
+pub async fn get_merge_request_participants(
+	config: &GitlabConfig,
+	project: &str,
+	merge_request_iid: u32,
+) -> Result<Vec<String>> {
+	let url = format!(
+		"{}/api/v4/projects/{}/merge_requests/{merge_request_iid}/participants",
+		base_url(config),
+		encode_project(project)
+	);
+	let participants = get_gitlab(&url, config)
+		.send_with_retry()
+		.await?
+		.error_for_status()?
+		.json::<Vec<User>>()
+		.await?;
+	Ok(participants.into_iter().map(|user| user.username).collect())
+}
+
+/// Returns the URL-encoded path of the project for the API.
+fn encode_project(project: &str) -> String {
+	project.replace('/', "%2F")
+}
+
+fn get_gitlab(url: &str, config: &GitlabConfig) -> RequestBuilder {
+	let mut request = reqwest::Client::new()
+		.get(url)
+		.header("User-Agent", "git-cliff");
+	if let Some(token) = &config.token {
+		request = request.header("PRIVATE-TOKEN", token);
+	}
+	request
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn project_from_remotes() {
+		for remote in [
+			"https://gitlab.com/group/subgroup/project.git",
+			"git@gitlab.com:group/subgroup/project.git",
+			"ssh://git@gitlab.com:22/group/subgroup/project",
+		] {
+			assert_eq!(
+				Some(String::from("group/subgroup/project")),
+				project_from_remote(DEFAULT_URL, remote)
+			);
+		}
+		assert_eq!(
+			Some(String::from("infra/tools")),
+			project_from_remote(
+				"https://git.example.com/",
+				"https://git.example.com/infra/tools"
+			)
+		);
+		assert_eq!(
+			None,
+			project_from_remote(DEFAULT_URL, "https://github.com/orhun/git-cliff")
+		);
+		assert_eq!("group%2Fproject", encode_project("group/project"));
+	}
+}
@@ -0,0 +1,165 @@
+use lazy_regex::Lazy;
+use serde::{
+	Deserialize,
+	Deserializer,
+	Serialize,
+	Serializer,
+};
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{
+	Arc,
+	Mutex,
+	PoisonError,
+};
+
+/// Strings that are interned so far.
+static STRINGS: Lazy<Mutex<HashSet<Arc<str>>>> = Lazy::new(Default::default);
+
+/// Interned string.
+///
+/// The repeated values of the commits (e.g. the names and emails of the
+/// authors, the groups and the scopes) are stored once and shared between
+/// the commits, so that cloning them does not allocate.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(Arc<str>);
+
+impl Symbol {
+	/// Returns the interned copy of the given string.
+	pub fn new(value: &str) -> Self {
+		let mut strings = STRINGS.lock().unwrap_or_else(PoisonError::into_inner);
+		match strings.get(value) {
+			Some(string) => Self(Arc::clone(string)),
+			None => {
+				let string = Arc::<str>::from(value);
+				strings.insert(Arc::clone(&string));
+				Self(string)
+			}
+		}
+	}
+
+	/// Returns the string slice.
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+}
+
+impl Deref for Symbol {
+	type Target = str;
+
+	fn deref(&self) -> &str {
+		&self.0
+	}
+}
+
+impl AsRef<str> for Symbol {
+	fn as_ref(&self) -> &str {
+		&self.0
+	}
+}
+
+impl Borrow<str> for Symbol {
+	fn borrow(&self) -> &str {
+		&self.0
+	}
+}
+
+impl From<&str> for Symbol {
+	fn from(value: &str) -> Self {
+		Self::new(value)
+	}
+}
+
+impl From<String> for Symbol {
+	fn from(value: String) -> Self {
+		Self::new(&value)
+	}
+}
+
+impl From<&String> for Symbol {
+	fn from(value: &String) -> Self {
+		Self::new(value)
+	}
+}
+
+impl From<Symbol> for String {
+	fn from(value: Symbol) -> Self {
+		value.as_str().to_string()
+	}
+}
+
+impl PartialEq<str> for Symbol {
+	fn eq(&self, other: &str) -> bool {
+		self.as_str() == other
+	}
+}
+
+impl PartialEq<&str> for Symbol {
+	fn eq(&self, other: &&str) -> bool {
+		self.as_str() == *other
+	}
+}
+
+impl PartialEq<String> for Symbol {
+	fn eq(&self, other: &String) -> bool {
+		self.as_str() == other
+	}
+}
+
+impl PartialEq<Symbol> for &str {
+	fn eq(&self, other: &Symbol) -> bool {
+		*self == other.as_str()
+	}
+}
+
+impl fmt::Debug for Symbol {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(self.as_str(), f)
+	}
+}
+
+impl fmt::Display for Symbol {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(self.as_str(), f)
+	}
+}
+
+impl Serialize for Symbol {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(self.as_str())
+	}
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		String::deserialize(deserializer).map(Self::from)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn intern_strings() -> crate::error::Result<()> {
+		let symbol = Symbol::from("John Doe");
+		assert!(Arc::ptr_eq(
+			&symbol.0,
+			&Symbol::from(String::from("John Doe")).0
+		));
+		assert!(!Arc::ptr_eq(&symbol.0, &Symbol::from("Jane Doe").0));
+		assert_eq!("John Doe", symbol.as_str());
+		assert_eq!("\"John Doe\"", serde_json::to_string(&symbol)?);
+		assert_eq!(symbol, serde_json::from_str::<Symbol>("\"John Doe\"")?);
+		Ok(())
+	}
+}
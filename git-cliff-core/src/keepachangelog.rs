@@ -0,0 +1,313 @@
+use crate::commit::Commit;
+use crate::release::Release;
+use crate::remote::Remote;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+use std::collections::HashSet;
+
+/// Sections of the releases in their order.
+pub const SECTIONS: [&str; 6] = [
+	"Added",
+	"Changed",
+	"Deprecated",
+	"Removed",
+	"Fixed",
+	"Security",
+];
+
+/// Default header of the changelog.
+pub const HEADER: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+
+";
+
+/// Regex for matching the headings of the releases.
+static RELEASE_REGEX: Lazy<Regex> = lazy_regex!(
+	r"^## \[(?P<label>[^\]]+)\](?: - (?P<date>\d{4}-\d{2}-\d{2}))?(?: \[YANKED\])?$"
+);
+
+/// Regex for matching the link reference definitions.
+static LINK_REGEX: Lazy<Regex> = lazy_regex!(r"^\[(?P<label>[^\]]+)\]: \S+");
+
+/// Returns the section of the given commit.
+///
+/// The groups that are already one of the sections (e.g. set by the commit
+/// parsers) are kept. Otherwise, the section is mapped from the conventional
+/// commit type and `security` type or scope is mapped to "Security".
+pub fn section(commit: &Commit) -> &'static str {
+	if let Some(section) = commit.group.as_deref().and_then(|group| {
+		SECTIONS
+			.iter()
+			.find(|section| section.eq_ignore_ascii_case(group.trim()))
+	}) {
+		return section;
+	}
+	let conv = match &commit.conv {
+		Some(conv) => conv,
+		None => return "Changed",
+	};
+	let kind = conv.type_().as_str().to_lowercase();
+	let is_security = conv
+		.scope()
+		.map_or(false, |v| v.as_str().eq_ignore_ascii_case("security"));
+	match kind.as_str() {
+		"security" | "sec" => "Security",
+		_ if is_security => "Security",
+		"feat" | "add" => "Added",
+		"fix" | "bugfix" => "Fixed",
+		"deprecate" | "deprecated" => "Deprecated",
+		"remove" | "removal" => "Removed",
+		_ => "Changed",
+	}
+}
+
+/// Returns the label of the release that is used in the heading and the link
+/// reference.
+fn label(release: &Release) -> String {
+	match &release.version {
+		Some(version) => version.trim_start_matches('v').to_string(),
+		None => String::from("Unreleased"),
+	}
+}
+
+/// Renders the given release with the sections in their order.
+pub fn render_release(release: &Release) -> String {
+	let mut output = match release.version.as_ref().and_then(|_| {
+		chrono::NaiveDateTime::from_timestamp_opt(release.timestamp, 0)
+	}) {
+		Some(date) => {
+			format!("## [{}] - {}\n\n", label(release), date.format("%Y-%m-%d"))
+		}
+		None => format!("## [{}]\n\n", label(release)),
+	};
+	for section in SECTIONS {
+		let entries = release
+			.commits
+			.iter()
+			.filter(|commit| commit.group.as_deref() == Some(section))
+			.map(|commit| {
+				let (scope, message) = match &commit.conv {
+					Some(conv) => {
+						(conv.scope().map(|v| v.to_string()), conv.description())
+					}
+					None => (
+						commit.scope.as_deref().map(String::from),
+						commit.message.lines().next().unwrap_or_default(),
+					),
+				};
+				let mut chars = message.trim().chars();
+				let message = chars
+					.next()
+					.map(|c| c.to_uppercase().collect::<String>() + chars.as_str())
+					.unwrap_or_default();
+				match scope {
+					Some(scope) => format!("- **{scope}**: {message}\n"),
+					None => format!("- {message}\n"),
+				}
+			})
+			.collect::<String>();
+		if !entries.is_empty() {
+			output += &format!("### {section}\n\n{entries}\n");
+		}
+	}
+	output
+}
+
+/// Renders the link reference definitions of the given releases.
+///
+/// Each release is linked to its comparison with the previous release or
+/// to its history if it is the first release.
+pub fn render_links(releases: &[Release], remote: &Remote) -> String {
+	releases
+		.iter()
+		.map(|release| {
+			let to = release.version.as_deref().unwrap_or("HEAD");
+			let link =
+				match release.previous.as_ref().and_then(|v| v.version.as_ref()) {
+					Some(from) => remote.compare_link(from, to),
+					None => remote.log_link(to),
+				};
+			format!("[{}]: {link}\n", label(release).to_lowercase())
+		})
+		.collect()
+}
+
+/// Validates the structure of the given changelog against Keep a Changelog
+/// 1.1 and returns the problems.
+///
+/// The releases are checked to have the dated headings (except the
+/// unreleased changes which come first), the known sections without
+/// duplicates and the link references if any of the releases has one.
+pub fn validate(changelog: &str) -> Vec<String> {
+	let mut problems = Vec::new();
+	let mut has_title = false;
+	let mut labels = Vec::new();
+	let mut sections = HashSet::new();
+	let links = changelog
+		.lines()
+		.filter_map(|line| LINK_REGEX.captures(line))
+		.filter_map(|captures| captures.name("label"))
+		.map(|v| v.as_str().to_lowercase())
+		.collect::<HashSet<String>>();
+	for line in changelog.lines().map(str::trim_end) {
+		if line.starts_with("# ") {
+			has_title = true;
+		} else if line.starts_with("## ") {
+			let captures = match RELEASE_REGEX.captures(line) {
+				Some(captures) => captures,
+				None => {
+					problems.push(format!("invalid release heading: {line}"));
+					continue;
+				}
+			};
+			let label = captures["label"].to_string();
+			let is_unreleased = label.eq_ignore_ascii_case("unreleased");
+			if is_unreleased && !labels.is_empty() {
+				problems.push(String::from("unreleased changes are not the first"));
+			}
+			match (is_unreleased, captures.name("date")) {
+				(true, Some(_)) => {
+					problems.push(String::from("unreleased changes have a date"))
+				}
+				(false, None) => problems.push(format!("{label} has no date")),
+				_ => {}
+			}
+			labels.push(label);
+			sections.clear();
+		} else if let Some(section) = line.strip_prefix("### ") {
+			if labels.is_empty() {
+				problems.push(format!("{section} section is not in a release"));
+			} else if !SECTIONS.contains(&section) {
+				problems.push(format!("unknown section: {section}"));
+			} else if !sections.insert(section.to_string()) {
+				problems.push(format!("duplicate section: {section}"));
+			}
+		}
+	}
+	if !has_title {
+		problems.insert(0, String::from("changelog has no title"));
+	}
+	if !links.is_empty() {
+		for label in labels {
+			if !links.contains(&label.to_lowercase()) {
+				problems.push(format!("{label} has no link reference"));
+			}
+		}
+	}
+	problems
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::config::RemoteConfig;
+	use crate::intern::Symbol;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn map_sections() -> crate::error::Result<()> {
+		for (message, section) in [
+			("feat: add xyz", "Added"),
+			("fix: fix xyz", "Fixed"),
+			("fix(security): update xyz", "Security"),
+			("deprecate: deprecate xyz", "Deprecated"),
+			("remove: remove xyz", "Removed"),
+			("refactor!: rename xyz", "Changed"),
+		] {
+			let commit = Commit::new(String::from("123"), String::from(message))
+				.into_conventional()?;
+			assert_eq!(section, self::section(&commit));
+		}
+		let mut commit = Commit::from(String::from("update xyz"));
+		assert_eq!("Changed", self::section(&commit));
+		commit.group = Some(Symbol::from("fixed"));
+		assert_eq!("Fixed", self::section(&commit));
+		Ok(())
+	}
+
+	#[test]
+	fn render_and_validate() -> crate::error::Result<()> {
+		let mut commits = vec![
+			Commit::new(String::from("1"), String::from("fix(ui): fix xyz")),
+			Commit::new(String::from("2"), String::from("feat: add xyz")),
+		];
+		for commit in commits.iter_mut() {
+			*commit = commit.clone().into_conventional()?;
+			commit.group = Some(Symbol::from(section(commit)));
+		}
+		let previous = Release {
+			version: Some(String::from("v0.9.0")),
+			..Release::default()
+		};
+		let releases = vec![
+			Release {
+				commits: commits.clone(),
+				previous: Some(Box::new(Release {
+					version: Some(String::from("v1.0.0")),
+					..Release::default()
+				})),
+				..Release::default()
+			},
+			Release {
+				version: Some(String::from("v1.0.0")),
+				commits,
+				timestamp: 0,
+				previous: Some(Box::new(previous)),
+				..Release::default()
+			},
+		];
+		let remote = Remote::new(&RemoteConfig::default(), &[String::from(
+			"https://github.com/orhun/git-cliff",
+		)])
+		.expect("remote is not detected");
+		let changelog = format!(
+			"{HEADER}{}{}{}",
+			render_release(&releases[0]),
+			render_release(&releases[1]),
+			render_links(&releases, &remote)
+		);
+		assert_eq!(
+			"## [1.0.0] - 1970-01-01
+
+### Added
+
+- Add xyz
+
+### Fixed
+
+- **ui**: Fix xyz
+
+",
+			render_release(&releases[1])
+		);
+		assert!(changelog.ends_with(
+			"[unreleased]: https://github.com/orhun/git-cliff/compare/v1.0.0..HEAD
+[1.0.0]: \
+			 https://github.com/orhun/git-cliff/compare/v0.9.0..v1.0.0
+"
+		));
+		assert_eq!(Vec::<String>::new(), validate(&changelog));
+		assert_eq!(
+			vec![
+				"changelog has no title",
+				"1.0.0 has no date",
+				"unknown section: Features",
+				"unreleased changes are not the first",
+				"duplicate section: Added",
+				"1.0.0 has no link reference",
+			],
+			validate(
+				"## [1.0.0]\n### Features\n## [Unreleased]\n### Added\n### \
+				 Added\n[unreleased]: https://xyz\n"
+			)
+		);
+		Ok(())
+	}
+}
@@ -5,7 +5,14 @@
 pub use glob;
 /// Export `regex` crate.
 pub use regex;
+/// Export `serde_json` crate.
+pub use serde_json;
 
+/// Public API diff.
+#[cfg(feature = "api-diff")]
+pub mod api_diff;
+/// Cache backends.
+pub mod cache;
 /// Command runner.
 pub mod command;
 /// Git commit.
@@ -14,16 +21,67 @@ pub mod commit;
 pub mod config;
 /// Embedded file handler.
 pub mod embed;
+/// Emoji shortcode table.
+pub mod emoji;
 /// Error handling.
 pub mod error;
+/// Synthetic git repositories for testing.
+#[cfg(any(test, feature = "test-support"))]
+pub mod fixtures;
+/// Changelog fragments.
+pub mod fragment;
+/// String interning.
+pub mod intern;
+/// Localization of the dates and the lists.
+pub mod locale;
 /// Common release type.
 pub mod release;
+/// Remote repository links.
+pub mod remote;
+/// Retry policy of the requests.
+pub mod retry;
+/// PDF exporter.
+#[cfg(feature = "pdf")]
+pub mod pdf;
 /// Git repository.
 pub mod repo;
+/// Commit and tag signatures.
+pub mod signature;
 /// Template engine.
 pub mod template;
+/// Utilities for testing templates and configurations.
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+/// Translation memory.
+pub mod translation;
+/// Issue tracker integrations.
+pub mod tracker;
 /// Github utils.
 pub mod github;
+/// GitLab utils.
+pub mod gitlab;
+/// Gitea utils.
+pub mod gitea;
+/// Gerrit utils.
+pub mod gerrit;
+/// SourceHut utils.
+pub mod sourcehut;
+/// Remote providers for resolving the commit information.
+pub mod provider;
+/// Keep a Changelog format.
+pub mod keepachangelog;
+/// Merging of the generated changelogs.
+pub mod merge;
+/// Organization-wide changelog.
+pub mod org;
+/// Link checker.
+pub mod links;
+/// Metrics of the changelog generation.
+pub mod metrics;
 
 /// Default configuration file.
 pub const DEFAULT_CONFIG: &str = "cliff.toml";
+
+/// Configuration files in the other formats that are used if the default
+/// configuration file does not exist.
+pub const ALTERNATIVE_CONFIGS: [&str; 3] = ["cliff.yaml", "cliff.yml", "cliff.json"];
@@ -0,0 +1,151 @@
+use crate::cache::Cache;
+use crate::error::Result;
+use crate::metrics::Counter;
+use crate::retry::SendWithRetry;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+use reqwest::{
+	Client,
+	StatusCode,
+};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Regular expression for matching the URLs.
+static URL_REGEX: Lazy<Regex> = lazy_regex!(r#"https?://[^\s<>()\[\]{}"'`|\\]+"#);
+
+/// Namespace of the valid links in the cache.
+const CACHE_NAMESPACE: &str = "links";
+
+/// Maximum number of the concurrent requests.
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Link that cannot be reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+	/// URL of the link.
+	pub url:    String,
+	/// Reason of the failure, e.g. `404 Not Found`.
+	pub reason: String,
+}
+
+/// Returns the unique URLs in the given text.
+///
+/// Trailing punctuation (e.g. the end of a sentence) is not included.
+pub fn extract_urls(text: &str) -> Vec<String> {
+	let mut urls = Vec::new();
+	for url in URL_REGEX.find_iter(text) {
+		let url = url
+			.as_str()
+			.trim_end_matches(['.', ',', ';', ':', '!', '?']);
+		if !urls.iter().any(|v| v == url) {
+			urls.push(url.to_string());
+		}
+	}
+	urls
+}
+
+/// Checker of the links that verifies that they return a successful status.
+///
+/// Valid links are cached so that they are requested only once.
+#[derive(Debug)]
+pub struct LinkChecker {
+	cache:  Arc<dyn Cache>,
+	client: Client,
+}
+
+impl LinkChecker {
+	/// Constructs a new instance that caches the valid links in the given
+	/// cache.
+	pub fn new(cache: Arc<dyn Cache>) -> Self {
+		Self {
+			cache,
+			client: Client::new(),
+		}
+	}
+
+	/// Checks the given URLs concurrently and returns the broken ones.
+	pub async fn check(&self, urls: &[String]) -> Result<Vec<BrokenLink>> {
+		let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS));
+		let mut handles = Vec::new();
+		for url in urls {
+			if self.cache.get(CACHE_NAMESPACE, url)?.is_some() {
+				Counter::CacheHits.increment();
+				continue;
+			}
+			Counter::CacheMisses.increment();
+			let semaphore = semaphore.clone();
+			let client = self.client.clone();
+			let url = url.to_string();
+			handles.push(tokio::spawn(async move {
+				let _permit = semaphore.acquire_owned().await;
+				let reason = Self::request(&client, &url).await.err();
+				(url, reason)
+			}));
+		}
+		let mut broken_links = Vec::new();
+		for handle in handles {
+			match handle.await? {
+				(url, Some(reason)) => broken_links.push(BrokenLink { url, reason }),
+				(url, None) => self.cache.set(CACHE_NAMESPACE, &url, "ok")?,
+			}
+		}
+		Ok(broken_links)
+	}
+
+	/// Requests the URL and returns the reason of the failure (if any).
+	///
+	/// Falls back to `GET` if the server does not support `HEAD` requests.
+	async fn request(client: &Client, url: &str) -> std::result::Result<(), String> {
+		let send = |method| {
+			client
+				.request(method, url)
+				.header("User-Agent", "git-cliff")
+				.send_with_retry()
+		};
+		let mut response = send(reqwest::Method::HEAD)
+			.await
+			.map_err(|e| e.to_string())?;
+		if matches!(
+			response.status(),
+			StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED
+		) {
+			response = send(reqwest::Method::GET)
+				.await
+				.map_err(|e| e.to_string())?;
+		}
+		if response.status().is_success() {
+			Ok(())
+		} else {
+			Err(response.status().to_string())
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn extract_urls_from_text() {
+		let text = r#"
+## [1.0.0](https://github.com/owner/repo/compare/v0.1.0..v1.0.0)
+
+- Add xyz ([#1](https://github.com/owner/repo/pull/1)) by <https://github.com/user>.
+- See https://example.com/docs, https://github.com/owner/repo/pull/1
+"#;
+		assert_eq!(
+			vec![
+				"https://github.com/owner/repo/compare/v0.1.0..v1.0.0",
+				"https://github.com/owner/repo/pull/1",
+				"https://github.com/user",
+				"https://example.com/docs",
+			],
+			extract_urls(text)
+		);
+	}
+}
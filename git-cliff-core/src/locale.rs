@@ -0,0 +1,119 @@
+use crate::error::{
+	Error,
+	Result,
+};
+
+/// Lengths of the localized dates.
+pub const DATE_LENGTHS: [&str; 4] = ["full", "long", "medium", "short"];
+
+/// Default length of the localized dates.
+pub const DEFAULT_DATE_LENGTH: &str = "medium";
+
+/// Formats the date of the given timestamp in the given locale.
+///
+/// The length is one of [`DATE_LENGTHS`], e.g. the medium length formats the
+/// date as `Jan 2, 2006` in `en` and `2006/01/02` in `ja`.
+#[cfg(feature = "icu")]
+pub fn format_date(timestamp: i64, locale: &str, length: &str) -> Result<String> {
+	use chrono::Datelike;
+	use icu_calendar::{
+		Date,
+		Gregorian,
+	};
+	use icu_datetime::options::length::Date as DateLength;
+	use icu_datetime::TypedDateFormatter;
+	let length = match length {
+		"full" => DateLength::Full,
+		"long" => DateLength::Long,
+		"medium" => DateLength::Medium,
+		"short" => DateLength::Short,
+		_ => {
+			return Err(Error::LocaleError(format!("invalid date length: {length}")))
+		}
+	};
+	let date = chrono::NaiveDateTime::from_timestamp_opt(timestamp, 0)
+		.ok_or_else(|| {
+			Error::LocaleError(format!("invalid timestamp: {timestamp}"))
+		})?
+		.date();
+	let date = Date::try_new_gregorian_date(
+		date.year(),
+		date.month() as u8,
+		date.day() as u8,
+	)
+	.map_err(|e| Error::LocaleError(e.to_string()))?;
+	let formatter = TypedDateFormatter::<Gregorian>::try_new_with_length(
+		&parse_locale(locale)?.into(),
+		length,
+	)
+	.map_err(|e| Error::LocaleError(e.to_string()))?;
+	Ok(formatter.format_to_string(&date))
+}
+
+/// Formats the date of the given timestamp in the given locale.
+#[cfg(not(feature = "icu"))]
+pub fn format_date(_: i64, _: &str, _: &str) -> Result<String> {
+	Err(unsupported())
+}
+
+/// Joins the given items as a list in the given locale, e.g. `x, y, and z` in
+/// `en` and `x、y、z` in `ja`.
+#[cfg(feature = "icu")]
+pub fn join_list(items: &[String], locale: &str) -> Result<String> {
+	use icu_list::{
+		ListFormatter,
+		ListLength,
+	};
+	let formatter = ListFormatter::try_new_and_with_length(
+		&parse_locale(locale)?.into(),
+		ListLength::Wide,
+	)
+	.map_err(|e| Error::LocaleError(e.to_string()))?;
+	Ok(formatter.format_to_string(items.iter()))
+}
+
+/// Joins the given items as a list in the given locale.
+#[cfg(not(feature = "icu"))]
+pub fn join_list(_: &[String], _: &str) -> Result<String> {
+	Err(unsupported())
+}
+
+/// Parses the given locale (e.g. `ja` or `pt_BR`).
+#[cfg(feature = "icu")]
+fn parse_locale(locale: &str) -> Result<icu_locid::Locale> {
+	locale
+		.replace('_', "-")
+		.parse()
+		.map_err(|_| Error::LocaleError(format!("invalid locale: {locale}")))
+}
+
+/// Returns the error of the localization without ICU.
+#[cfg(not(feature = "icu"))]
+fn unsupported() -> Error {
+	Error::LocaleError(String::from(
+		"Localization requires the 'icu' feature to be enabled",
+	))
+}
+
+#[cfg(all(test, feature = "icu"))]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn localize() -> Result<()> {
+		// 2006-01-02 15:04:05 UTC
+		let timestamp = 1136214245;
+		assert_eq!("Jan 2, 2006", format_date(timestamp, "en", "medium")?);
+		assert_eq!("January 2, 2006", format_date(timestamp, "en", "long")?);
+		assert_eq!("2006/01/02", format_date(timestamp, "ja", "medium")?);
+		assert!(format_date(timestamp, "en", "tiny").is_err());
+		assert!(format_date(timestamp, "not a locale", "medium").is_err());
+		let items = [String::from("x"), String::from("y"), String::from("z")];
+		assert_eq!("x, y, and z", join_list(&items, "en")?);
+		assert_eq!("x, y y z", join_list(&items, "es")?);
+		assert_eq!("x、y、z", join_list(&items, "ja")?);
+		assert_eq!("x and y", join_list(&items[..2], "en_US")?);
+		Ok(())
+	}
+}
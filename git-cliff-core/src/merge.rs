@@ -0,0 +1,213 @@
+use chrono::NaiveDate;
+use indexmap::IndexMap;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+use std::collections::HashSet;
+
+/// Regex for matching the version in the heading of a release section.
+static VERSION_REGEX: Lazy<Regex> =
+	lazy_regex!(r"^##\s+\[?(?P<version>[^\]\s]+)\]?");
+
+/// Regex for matching the date in the heading of a release section.
+static DATE_REGEX: Lazy<Regex> = lazy_regex!(r"\d{4}-\d{2}-\d{2}");
+
+/// Regex for matching the link reference definitions.
+static LINK_REGEX: Lazy<Regex> = lazy_regex!(r"^\[(?P<label>[^\]]+)\]:\s");
+
+/// Release section of a changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Section {
+	/// Version of the release (`None` for the unreleased changes).
+	version: Option<String>,
+	/// Date of the release.
+	date:    Option<NaiveDate>,
+	/// Text of the section including its heading.
+	text:    String,
+}
+
+/// Changelog that is split into its header, release sections and footer.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Document {
+	/// Text before the first release section.
+	header:   String,
+	/// Release sections.
+	sections: Vec<Section>,
+	/// Link reference definitions by their labels.
+	links:    IndexMap<String, String>,
+	/// Text after the last release section (except the link references).
+	footer:   String,
+}
+
+impl Document {
+	/// Parses the given changelog.
+	///
+	/// The release sections start with the level 2 headings. The link
+	/// reference definitions and the HTML comments at the end of the last
+	/// section belong to the footer.
+	fn parse(changelog: &str) -> Self {
+		let mut document = Self::default();
+		for line in changelog.replace("\r\n", "\n").split_inclusive('\n') {
+			if line.starts_with("## ") {
+				let version = VERSION_REGEX
+					.captures(line)
+					.map(|v| v["version"].to_string())
+					.filter(|v| !v.eq_ignore_ascii_case("unreleased"));
+				let date = DATE_REGEX.find(line).and_then(|v| {
+					NaiveDate::parse_from_str(v.as_str(), "%Y-%m-%d").ok()
+				});
+				document.sections.push(Section {
+					version,
+					date,
+					text: line.to_string(),
+				});
+			} else {
+				match document.sections.last_mut() {
+					Some(section) => section.text += line,
+					None => document.header += line,
+				}
+			}
+		}
+		if let Some(section) = document.sections.last_mut() {
+			let lines = section.text.split_inclusive('\n').collect::<Vec<&str>>();
+			let end = lines
+				.iter()
+				.rposition(|line| {
+					!(line.trim().is_empty() ||
+						line.starts_with("<!--") ||
+						LINK_REGEX.is_match(line))
+				})
+				.map_or(0, |i| i + 1);
+			for line in &lines[end..] {
+				if let Some(captures) = LINK_REGEX.captures(line) {
+					document
+						.links
+						.entry(captures["label"].to_lowercase())
+						.or_insert_with(|| line.trim_end().to_string());
+				} else if !line.trim().is_empty() {
+					document.footer += line;
+				}
+			}
+			section.text = lines[..end].concat();
+		}
+		document
+	}
+}
+
+/// Merges the release sections of the given changelogs.
+///
+/// The sections are interleaved by their dates (newest first) and the ones
+/// without dates keep their positions. The sections of the identical
+/// versions (and the unreleased changes) are taken from the first changelog
+/// which also provides the header and the footer. The link references are
+/// merged in the order of the sections.
+pub fn merge(ours: &str, theirs: &str) -> String {
+	let ours = Document::parse(ours);
+	let theirs = Document::parse(theirs);
+	let mut sections = Vec::new();
+	let mut versions = HashSet::new();
+	let (mut i, mut j) = (0, 0);
+	while i < ours.sections.len() || j < theirs.sections.len() {
+		let section = match (ours.sections.get(i), theirs.sections.get(j)) {
+			(Some(a), Some(b))
+				if a.version.is_some() &&
+					(b.version.is_none() || b.date > a.date) =>
+			{
+				j += 1;
+				b
+			}
+			(Some(a), _) => {
+				i += 1;
+				a
+			}
+			(None, Some(b)) => {
+				j += 1;
+				b
+			}
+			(None, None) => break,
+		};
+		if versions.insert(section.version.clone()) {
+			sections.push(section);
+		}
+	}
+	let mut output = ours.header.clone();
+	for section in &sections {
+		output += section.text.trim_end();
+		output += "\n\n";
+	}
+	let mut links = IndexMap::new();
+	for section in &sections {
+		let label = section
+			.version
+			.as_deref()
+			.unwrap_or("unreleased")
+			.to_lowercase();
+		for label in [label.trim_start_matches('v').to_string(), label] {
+			if let Some(link) =
+				ours.links.get(&label).or_else(|| theirs.links.get(&label))
+			{
+				links.insert(label, link.to_string());
+			}
+		}
+	}
+	for (label, link) in ours.links.iter().chain(theirs.links.iter()) {
+		links
+			.entry(label.to_string())
+			.or_insert_with(|| link.to_string());
+	}
+	if links.is_empty() && ours.footer.is_empty() {
+		return output.trim_end().to_string() + "\n";
+	}
+	for link in links.values() {
+		output += link;
+		output += "\n";
+	}
+	output + &ours.footer
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn merge_changelogs() {
+		let ours = [
+			"# Changelog\n\n",
+			"## [Unreleased]\n\n- Add abc\n\n",
+			"## [1.3.0] - 2023-03-01\n\n- Add xyz\n\n",
+			"## [1.2.0] - 2023-02-01\n\n- Add def\n\n",
+			"[unreleased]: https://xyz/compare/v1.3.0..HEAD\n",
+			"[1.3.0]: https://xyz/compare/v1.2.0..v1.3.0\n",
+			"<!-- generated by git-cliff -->\n",
+		];
+		let theirs = [
+			"# Changelog\r\n\r\n",
+			"## [Unreleased]\r\n\r\n- Fix def\r\n\r\n",
+			"## [1.2.1] - 2023-03-05\r\n\r\n- Fix def\r\n\r\n",
+			"## [1.2.0] - 2023-02-01\r\n\r\n- Add def\r\n\r\n",
+			"[1.2.1]: https://xyz/compare/v1.2.0..v1.2.1\r\n",
+		];
+		assert_eq!(
+			[
+				ours[0],
+				ours[1],
+				"## [1.2.1] - 2023-03-05\n\n- Fix def\n\n",
+				ours[2],
+				ours[3],
+				ours[4],
+				"[1.2.1]: https://xyz/compare/v1.2.0..v1.2.1\n",
+				ours[5],
+				ours[6],
+			]
+			.concat(),
+			merge(&ours.concat(), &theirs.concat())
+		);
+		assert_eq!(
+			"## Unreleased\n\n- Add xyz\n\n## v1.0.0\n",
+			merge("## v1.0.0\n", "## Unreleased\n\n- Add xyz\n## v1.0.0\n")
+		);
+	}
+}
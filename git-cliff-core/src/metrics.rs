@@ -0,0 +1,164 @@
+use crate::error::Result;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{
+	AtomicU64,
+	Ordering,
+};
+use std::time::Duration;
+
+/// Number of the requests that are sent to the remote APIs.
+static API_REQUESTS: AtomicU64 = AtomicU64::new(0);
+
+/// Number of the values that are found in the cache.
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+
+/// Number of the values that are not found in the cache.
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Counter of the process-wide metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Counter {
+	/// Requests to the remote APIs.
+	ApiRequests,
+	/// Cache hits.
+	CacheHits,
+	/// Cache misses.
+	CacheMisses,
+}
+
+impl Counter {
+	/// Returns the atomic value of the counter.
+	fn value(&self) -> &'static AtomicU64 {
+		match self {
+			Self::ApiRequests => &API_REQUESTS,
+			Self::CacheHits => &CACHE_HITS,
+			Self::CacheMisses => &CACHE_MISSES,
+		}
+	}
+
+	/// Increments the counter.
+	pub fn increment(&self) {
+		self.value().fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// Returns the current value of the counter.
+	pub fn get(&self) -> u64 {
+		self.value().load(Ordering::Relaxed)
+	}
+}
+
+/// Metrics of a changelog generation.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize)]
+pub struct Metrics {
+	/// Number of the processed commits.
+	pub commits:      usize,
+	/// Number of the rendered releases.
+	pub releases:     usize,
+	/// Number of the rendered entries (i.e. commits in the releases).
+	pub entries:      usize,
+	/// Number of the requests that are sent to the remote APIs.
+	pub api_requests: u64,
+	/// Number of the values that are found in the cache.
+	pub cache_hits:   u64,
+	/// Number of the values that are not found in the cache.
+	pub cache_misses: u64,
+	/// Durations of the phases in seconds.
+	pub durations:    BTreeMap<String, f64>,
+}
+
+impl Metrics {
+	/// Sets the current values of the process-wide counters.
+	pub fn collect_counters(&mut self) {
+		self.api_requests = Counter::ApiRequests.get();
+		self.cache_hits = Counter::CacheHits.get();
+		self.cache_misses = Counter::CacheMisses.get();
+	}
+
+	/// Records the duration of the given phase.
+	pub fn set_duration(&mut self, phase: &str, duration: Duration) {
+		self.durations
+			.insert(phase.to_string(), duration.as_secs_f64());
+	}
+
+	/// Returns the metrics as JSON.
+	pub fn to_json(&self) -> Result<String> {
+		Ok(serde_json::to_string_pretty(self)? + "\n")
+	}
+
+	/// Returns the metrics in the Prometheus text exposition format.
+	pub fn to_prometheus(&self) -> Result<String> {
+		let mut output = String::new();
+		for (name, help, value) in [
+			(
+				"commits",
+				"Number of the processed commits.",
+				self.commits as u64,
+			),
+			(
+				"releases",
+				"Number of the rendered releases.",
+				self.releases as u64,
+			),
+			(
+				"entries",
+				"Number of the rendered entries.",
+				self.entries as u64,
+			),
+			(
+				"api_requests",
+				"Number of the API requests.",
+				self.api_requests,
+			),
+			("cache_hits", "Number of the cache hits.", self.cache_hits),
+			(
+				"cache_misses",
+				"Number of the cache misses.",
+				self.cache_misses,
+			),
+		] {
+			writeln!(output, "# HELP git_cliff_{name} {help}")?;
+			writeln!(output, "# TYPE git_cliff_{name} gauge")?;
+			writeln!(output, "git_cliff_{name} {value}")?;
+		}
+		writeln!(
+			output,
+			"# HELP git_cliff_phase_duration_seconds Duration of the phases."
+		)?;
+		writeln!(output, "# TYPE git_cliff_phase_duration_seconds gauge")?;
+		for (phase, seconds) in &self.durations {
+			writeln!(
+				output,
+				"git_cliff_phase_duration_seconds{{phase=\"{phase}\"}} {seconds}"
+			)?;
+		}
+		Ok(output)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn export_metrics() -> Result<()> {
+		let mut metrics = Metrics {
+			commits: 3,
+			entries: 2,
+			..Metrics::default()
+		};
+		metrics.set_duration("render", Duration::from_millis(1500));
+		let output = metrics.to_prometheus()?;
+		assert!(
+			output.contains("# TYPE git_cliff_commits gauge\ngit_cliff_commits 3\n")
+		);
+		assert!(output.contains("git_cliff_entries 2\n"));
+		assert!(output
+			.contains("git_cliff_phase_duration_seconds{phase=\"render\"} 1.5\n"));
+		let json: serde_json::Value = serde_json::from_str(&metrics.to_json()?)?;
+		assert_eq!(3, json["commits"]);
+		assert_eq!(1.5, json["durations"]["render"]);
+		Ok(())
+	}
+}
@@ -0,0 +1,232 @@
+use crate::error::Result;
+use crate::github;
+use glob::Pattern;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Release of a repository in an organization.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OrgRelease {
+	/// Name of the repository.
+	pub repository:     String,
+	/// URL of the repository page.
+	pub repository_url: String,
+	/// Tag of the release.
+	pub version:        String,
+	/// Title of the release.
+	pub title:          Option<String>,
+	/// URL of the release page.
+	pub url:            String,
+	/// Release notes.
+	pub body:           Option<String>,
+	/// Publication date of the release (or the commit date of the tag).
+	pub timestamp:      i64,
+}
+
+/// Combined changelog of the repositories in an organization.
+#[derive(
+	Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct OrgChangelog {
+	/// Name of the organization.
+	pub org:      String,
+	/// Releases of the repositories, newest first.
+	pub releases: Vec<OrgRelease>,
+}
+
+impl OrgChangelog {
+	/// Fetches the latest releases of the repositories in the given Github
+	/// organization.
+	///
+	/// Only the repositories that match any of the patterns are included (all
+	/// of them if there is no pattern) and the archived ones are skipped. Tags
+	/// are used for the repositories without published releases.
+	pub async fn fetch(
+		api_url: &str,
+		token: &Option<String>,
+		org: &str,
+		patterns: &[Pattern],
+		limit: usize,
+	) -> Result<Self> {
+		let mut releases = Vec::new();
+		for repository in github::get_org_repositories(api_url, token, org).await? {
+			if repository.archived ||
+				!(patterns.is_empty() ||
+					patterns.iter().any(|v| v.matches(&repository.name)))
+			{
+				continue;
+			}
+			let mut repository_releases =
+				github::get_releases(api_url, token, &repository.full_name, limit)
+					.await?
+					.into_iter()
+					.filter(|release| !release.draft)
+					.map(|release| OrgRelease {
+						repository:     repository.name.clone(),
+						repository_url: repository.html_url.clone(),
+						url:            release.html_url.unwrap_or_else(|| {
+							format!(
+								"{}/releases/tag/{}",
+								repository.html_url, release.tag_name
+							)
+						}),
+						version:        release.tag_name,
+						title:          release.name,
+						body:           release.body,
+						timestamp:      Self::timestamp(
+							release.published_at.as_deref(),
+						),
+					})
+					.collect::<Vec<OrgRelease>>();
+			if repository_releases.is_empty() {
+				for tag in
+					github::get_tags(api_url, token, &repository.full_name, limit)
+						.await?
+				{
+					let date = github::get_commit_date(
+						api_url,
+						token,
+						&repository.full_name,
+						&tag.commit.sha,
+					)
+					.await?;
+					repository_releases.push(OrgRelease {
+						repository:     repository.name.clone(),
+						repository_url: repository.html_url.clone(),
+						url:            format!(
+							"{}/tree/{}",
+							repository.html_url, tag.name
+						),
+						version:        tag.name,
+						title:          None,
+						body:           None,
+						timestamp:      Self::timestamp(Some(&date)),
+					});
+				}
+			}
+			releases.extend(repository_releases);
+		}
+		releases.sort_by(|a, b| {
+			b.timestamp
+				.cmp(&a.timestamp)
+				.then_with(|| a.repository.cmp(&b.repository))
+		});
+		Ok(Self {
+			org: org.to_string(),
+			releases,
+		})
+	}
+
+	/// Returns the UNIX timestamp of the given RFC 3339 date.
+	fn timestamp(date: Option<&str>) -> i64 {
+		date.and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok())
+			.map(|v| v.timestamp())
+			.unwrap_or_default()
+	}
+
+	/// Renders the changelog grouped by date and repository.
+	pub fn render(&self) -> Result<String> {
+		let mut dates: Vec<(String, BTreeMap<&str, Vec<&OrgRelease>>)> = Vec::new();
+		for release in &self.releases {
+			let date = chrono::NaiveDateTime::from_timestamp(release.timestamp, 0)
+				.format("%Y-%m-%d")
+				.to_string();
+			if dates.last().map_or(true, |(v, _)| v != &date) {
+				dates.push((date, BTreeMap::new()));
+			}
+			if let Some((_, repositories)) = dates.last_mut() {
+				repositories
+					.entry(release.repository.as_str())
+					.or_insert_with(Vec::new)
+					.push(release);
+			}
+		}
+
+		let mut result = String::new();
+		writeln!(result, "# Changelog of {}\n", self.org)?;
+		for (date, repositories) in dates {
+			// ## 2022-10-05
+			writeln!(result, "## {date}\n")?;
+			for (repository, releases) in repositories {
+				// ### [repo](link)
+				writeln!(
+					result,
+					"### [{repository}]({})\n",
+					releases[0].repository_url
+				)?;
+				for release in releases {
+					// #### [v1.0.0](link) - Title
+					write!(result, "#### [{}]({})", release.version, release.url)?;
+					if let Some(title) = release.title.as_deref().filter(|v| {
+						!v.trim().is_empty() && v.trim() != release.version
+					}) {
+						write!(result, " - {}", title.trim())?;
+					}
+					writeln!(result, "\n")?;
+					if let Some(body) =
+						release.body.as_deref().filter(|v| !v.trim().is_empty())
+					{
+						writeln!(result, "{}\n", body.trim())?;
+					}
+				}
+			}
+		}
+		Ok(result)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn render() -> Result<()> {
+		let release = |repository: &str, version: &str, timestamp| OrgRelease {
+			repository: repository.to_string(),
+			repository_url: format!("https://github.com/acme/{repository}"),
+			version: version.to_string(),
+			title: Some(version.to_string()),
+			url: format!("https://github.com/acme/{repository}/tree/{version}"),
+			body: None,
+			timestamp,
+		};
+		let changelog = OrgChangelog {
+			org:      String::from("acme"),
+			releases: vec![
+				OrgRelease {
+					title: Some(String::from("Performance release")),
+					body: Some(String::from("- Faster xyz\n")),
+					..release("web", "v2.0.0", 1665000000)
+				},
+				release("api", "v1.1.0", 1665000000),
+				release("api", "v1.0.0", 1664000000),
+			],
+		};
+		assert_eq!(
+			r#"# Changelog of acme
+
+## 2022-10-05
+
+### [api](https://github.com/acme/api)
+
+#### [v1.1.0](https://github.com/acme/api/tree/v1.1.0)
+
+### [web](https://github.com/acme/web)
+
+#### [v2.0.0](https://github.com/acme/web/tree/v2.0.0) - Performance release
+
+- Faster xyz
+
+## 2022-09-24
+
+### [api](https://github.com/acme/api)
+
+#### [v1.0.0](https://github.com/acme/api/tree/v1.0.0)
+
+"#,
+			changelog.render()?
+		);
+		Ok(())
+	}
+}
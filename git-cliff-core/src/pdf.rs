@@ -0,0 +1,56 @@
+use crate::command;
+use crate::config::PdfConfig;
+use crate::error::Result;
+use std::path::Path;
+
+/// Default typesetting backend.
+///
+/// Uses [pandoc](https://pandoc.org) along with a PDF engine which supports
+/// CSS stylesheets.
+const DEFAULT_COMMAND: &str =
+	"pandoc --from=markdown --pdf-engine=weasyprint --output=\"$PDF_OUTPUT\"";
+
+/// Renders the given Markdown into a PDF file at `output`.
+///
+/// The Markdown is written to the stdin of the configured command.
+/// `PDF_OUTPUT` and `PDF_STYLESHEET` environment variables are set for the
+/// command accordingly.
+pub fn export(markdown: String, config: &PdfConfig, output: &Path) -> Result<()> {
+	let output = output.to_string_lossy();
+	let stylesheet = config.stylesheet.clone().unwrap_or_default();
+	let command = match &config.command {
+		Some(command) => command.to_string(),
+		None if stylesheet.is_empty() => DEFAULT_COMMAND.to_string(),
+		None => format!("{} --css=\"$PDF_STYLESHEET\"", DEFAULT_COMMAND),
+	};
+	command::run(&command, Some(markdown), vec![
+		("PDF_OUTPUT", &output),
+		("PDF_STYLESHEET", &stylesheet),
+	])?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use std::env;
+	use std::fs;
+	#[test]
+	#[cfg(target_family = "unix")]
+	fn export_pdf() -> Result<()> {
+		let output = env::temp_dir().join("git-cliff-export.pdf");
+		export(
+			String::from("# Changelog"),
+			&PdfConfig {
+				command:    Some(String::from(
+					"cat - \"$PDF_STYLESHEET\" > \"$PDF_OUTPUT\"",
+				)),
+				stylesheet: Some(String::from("/dev/null")),
+			},
+			&output,
+		)?;
+		assert_eq!("# Changelog", fs::read_to_string(&output)?);
+		fs::remove_file(output)?;
+		Ok(())
+	}
+}
@@ -0,0 +1,465 @@
+use crate::cache::{
+	Cache,
+	DEFAULT_TTL,
+};
+use crate::config::GiteaConfig;
+use crate::error::Result;
+use crate::remote::Remote;
+use crate::{
+	gitea,
+	github,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Namespace of the cached lookups of the remote providers.
+const CACHE_NAMESPACE: &str = "remote";
+
+/// Future that is returned by the methods of [`RemoteProvider`].
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Provider of the information of the commits on a remote repository.
+pub trait RemoteProvider: Debug + Send + Sync {
+	/// Returns the name of the provider, e.g. `github`.
+	fn name(&self) -> &'static str;
+
+	/// Returns the name of the repository with owner, e.g. `orhun/git-cliff`.
+	fn repository(&self) -> &str;
+
+	/// Returns the URL of the API, e.g. `https://api.github.com`.
+	fn api_url(&self) -> &str;
+
+	/// Returns the username of the author of the given commit (if any).
+	fn commit_author<'a>(
+		&'a self,
+		commit_sha: &'a str,
+	) -> BoxFuture<'a, Option<String>>;
+
+	/// Returns the numbers of the pull requests associated with the given
+	/// commit.
+	fn commit_pull_requests<'a>(
+		&'a self,
+		commit_sha: &'a str,
+	) -> BoxFuture<'a, Vec<u32>>;
+
+	/// Returns the usernames of the authors of the commits in the given pull
+	/// request.
+	fn pull_request_authors(&self, number: u32) -> BoxFuture<'_, Vec<String>>;
+
+	/// Returns the given pull request with its comment and reaction counts
+	/// (if supported).
+	fn pull_request(&self, _number: u32) -> BoxFuture<'_, Option<PullRequest>> {
+		Box::pin(async { Ok(None) })
+	}
+}
+
+/// Pull request on the remote repository.
+#[derive(
+	Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct PullRequest {
+	/// Number of the pull request.
+	pub number:    u32,
+	/// Title of the pull request.
+	pub title:     Option<String>,
+	/// Number of the comments.
+	pub comments:  u32,
+	/// Number of the reactions.
+	pub reactions: u32,
+}
+
+/// Github repository.
+#[derive(Debug, Clone)]
+pub struct GithubProvider {
+	api_url:    String,
+	token:      Option<String>,
+	repository: String,
+}
+
+impl GithubProvider {
+	/// Constructs a new instance that uses the Github API at the given URL.
+	pub fn new(api_url: &str, token: Option<String>, repository: String) -> Self {
+		Self {
+			api_url: api_url.trim_end_matches('/').to_string(),
+			token,
+			repository,
+		}
+	}
+}
+
+impl RemoteProvider for GithubProvider {
+	fn name(&self) -> &'static str {
+		"github"
+	}
+
+	fn repository(&self) -> &str {
+		&self.repository
+	}
+
+	fn api_url(&self) -> &str {
+		&self.api_url
+	}
+
+	fn commit_author<'a>(
+		&'a self,
+		commit_sha: &'a str,
+	) -> BoxFuture<'a, Option<String>> {
+		Box::pin(async move {
+			github::get_commit_author(
+				&self.api_url,
+				&self.token,
+				&self.repository,
+				commit_sha,
+			)
+			.await
+			.map(Some)
+		})
+	}
+
+	fn commit_pull_requests<'a>(
+		&'a self,
+		commit_sha: &'a str,
+	) -> BoxFuture<'a, Vec<u32>> {
+		Box::pin(github::get_prs_associated_with_commit(
+			&self.api_url,
+			&self.token,
+			&self.repository,
+			commit_sha,
+		))
+	}
+
+	fn pull_request(&self, number: u32) -> BoxFuture<'_, Option<PullRequest>> {
+		Box::pin(async move {
+			github::get_pull_request(
+				&self.api_url,
+				&self.token,
+				&self.repository,
+				number,
+			)
+			.await
+			.map(Some)
+		})
+	}
+
+	fn pull_request_authors(&self, number: u32) -> BoxFuture<'_, Vec<String>> {
+		Box::pin(async move {
+			github::get_pr_authors(
+				&self.api_url,
+				&self.token,
+				&self.repository,
+				&number,
+			)
+			.await
+		})
+	}
+}
+
+/// Repository on a Gitea (or Forgejo) instance, e.g. Codeberg.
+#[derive(Debug, Clone)]
+pub struct GiteaProvider {
+	config:     GiteaConfig,
+	repository: String,
+}
+
+impl GiteaProvider {
+	/// Constructs a new instance from the configuration.
+	///
+	/// If the URL of the instance is not configured, it is detected from the
+	/// git remotes on the well-known public instances (e.g. Codeberg). If the
+	/// repository is not configured, it is detected from the first git remote
+	/// on the instance. Returns `None` if the instance is not found.
+	pub fn new(config: &GiteaConfig, git_remotes: &[String]) -> Option<Self> {
+		let mut config = config.clone();
+		if config.url.is_none() {
+			config.url = git_remotes
+				.iter()
+				.filter_map(|remote| Remote::web_url(remote))
+				.find_map(|url| gitea::public_instance(&url))
+				.map(String::from);
+		}
+		let url = config.url.as_deref()?.trim_end_matches('/');
+		let repository = config.repository.clone().or_else(|| {
+			git_remotes.iter().find_map(|remote| {
+				Remote::web_url(remote)?
+					.strip_prefix(url)?
+					.strip_prefix('/')
+					.map(String::from)
+			})
+		})?;
+		Some(Self { config, repository })
+	}
+}
+
+impl RemoteProvider for GiteaProvider {
+	fn name(&self) -> &'static str {
+		"gitea"
+	}
+
+	fn repository(&self) -> &str {
+		&self.repository
+	}
+
+	fn api_url(&self) -> &str {
+		self.config
+			.url
+			.as_deref()
+			.unwrap_or_default()
+			.trim_end_matches('/')
+	}
+
+	fn commit_author<'a>(
+		&'a self,
+		commit_sha: &'a str,
+	) -> BoxFuture<'a, Option<String>> {
+		Box::pin(gitea::get_commit_author(
+			&self.config,
+			&self.repository,
+			commit_sha,
+		))
+	}
+
+	fn commit_pull_requests<'a>(
+		&'a self,
+		commit_sha: &'a str,
+	) -> BoxFuture<'a, Vec<u32>> {
+		Box::pin(gitea::get_prs_associated_with_commit(
+			&self.config,
+			&self.repository,
+			commit_sha,
+		))
+	}
+
+	fn pull_request_authors(&self, number: u32) -> BoxFuture<'_, Vec<String>> {
+		Box::pin(gitea::get_pr_authors(
+			&self.config,
+			&self.repository,
+			number,
+		))
+	}
+}
+
+/// Provider that caches the lookups of another provider.
+///
+/// The authors and pull requests of the commits are keyed by the API URL, the
+/// repository and the commit SHA and the authors of the pull requests by their
+/// number so that they are not requested again in the later runs. The empty
+/// results are not cached since they may be filled later (e.g. a pull request
+/// that is opened after the commit is pushed). The comment and reaction counts
+/// of the pull requests are not cached since they change over time.
+#[derive(Debug)]
+pub struct CachedProvider {
+	inner: Box<dyn RemoteProvider>,
+	cache: Arc<dyn Cache>,
+	ttl:   u64,
+}
+
+impl CachedProvider {
+	/// Constructs a new instance that caches the lookups of the given provider.
+	///
+	/// The cached values expire after the given number of seconds (a week by
+	/// default).
+	pub fn new(
+		inner: Box<dyn RemoteProvider>,
+		cache: Arc<dyn Cache>,
+		ttl: Option<u64>,
+	) -> Self {
+		Self {
+			inner,
+			cache,
+			ttl: ttl.unwrap_or(DEFAULT_TTL),
+		}
+	}
+
+	/// Returns the cached value of the key if it is not expired, otherwise
+	/// fetches it and caches it unless it is empty.
+	async fn get_or_fetch<T: Serialize + DeserializeOwned>(
+		&self,
+		key: String,
+		fetch: BoxFuture<'_, T>,
+		is_empty: fn(&T) -> bool,
+	) -> Result<T> {
+		let key = format!(
+			"{}/{}/{}/{key}",
+			self.inner.name(),
+			self.inner.api_url(),
+			self.inner.repository()
+		);
+		if let Some(value) =
+			self.cache.get_fresh_json(CACHE_NAMESPACE, &key, self.ttl)?
+		{
+			return Ok(value);
+		}
+		let value = fetch.await?;
+		if !is_empty(&value) {
+			self.cache.set_fresh_json(CACHE_NAMESPACE, &key, &value)?;
+		}
+		Ok(value)
+	}
+}
+
+impl RemoteProvider for CachedProvider {
+	fn name(&self) -> &'static str {
+		self.inner.name()
+	}
+
+	fn repository(&self) -> &str {
+		self.inner.repository()
+	}
+
+	fn api_url(&self) -> &str {
+		self.inner.api_url()
+	}
+
+	fn commit_author<'a>(
+		&'a self,
+		commit_sha: &'a str,
+	) -> BoxFuture<'a, Option<String>> {
+		Box::pin(self.get_or_fetch(
+			format!("commits/{commit_sha}/author"),
+			self.inner.commit_author(commit_sha),
+			Option::is_none,
+		))
+	}
+
+	fn commit_pull_requests<'a>(
+		&'a self,
+		commit_sha: &'a str,
+	) -> BoxFuture<'a, Vec<u32>> {
+		Box::pin(self.get_or_fetch(
+			format!("commits/{commit_sha}/pulls"),
+			self.inner.commit_pull_requests(commit_sha),
+			Vec::is_empty,
+		))
+	}
+
+	fn pull_request_authors(&self, number: u32) -> BoxFuture<'_, Vec<String>> {
+		Box::pin(self.get_or_fetch(
+			format!("pulls/{number}/authors"),
+			self.inner.pull_request_authors(number),
+			Vec::is_empty,
+		))
+	}
+
+	fn pull_request(&self, number: u32) -> BoxFuture<'_, Option<PullRequest>> {
+		self.inner.pull_request(number)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn gitea_repository() {
+		let mut config = GiteaConfig {
+			url: Some(String::from("https://codeberg.org/")),
+			..GiteaConfig::default()
+		};
+		let remotes = [
+			String::from("https://github.com/orhun/git-cliff"),
+			String::from("git@codeberg.org:owner/repo.git"),
+		];
+		assert_eq!(
+			Some("owner/repo"),
+			GiteaProvider::new(&config, &remotes)
+				.as_ref()
+				.map(|v| v.repository())
+		);
+		config.repository = Some(String::from("owner/other"));
+		assert_eq!(
+			Some("owner/other"),
+			GiteaProvider::new(&config, &remotes)
+				.as_ref()
+				.map(|v| v.repository())
+		);
+		config.url = None;
+		assert_eq!(
+			Some("owner/other"),
+			GiteaProvider::new(&config, &remotes)
+				.as_ref()
+				.map(|v| v.repository())
+		);
+		assert!(GiteaProvider::new(&config, &remotes[..1]).is_none());
+	}
+
+	#[tokio::test]
+	async fn cached_provider() -> Result<()> {
+		use crate::cache::MemoryCache;
+		use std::sync::atomic::{
+			AtomicUsize,
+			Ordering,
+		};
+		#[derive(Debug, Default)]
+		struct Provider {
+			requests: Arc<AtomicUsize>,
+		}
+		impl RemoteProvider for Provider {
+			fn name(&self) -> &'static str {
+				"test"
+			}
+			fn repository(&self) -> &str {
+				"owner/repo"
+			}
+			fn api_url(&self) -> &str {
+				"https://example.com"
+			}
+			fn commit_author<'a>(
+				&'a self,
+				commit_sha: &'a str,
+			) -> BoxFuture<'a, Option<String>> {
+				Box::pin(async move {
+					self.requests.fetch_add(1, Ordering::Relaxed);
+					Ok(Some(format!("user-{commit_sha}")))
+				})
+			}
+			fn commit_pull_requests<'a>(
+				&'a self,
+				_: &'a str,
+			) -> BoxFuture<'a, Vec<u32>> {
+				Box::pin(async { Ok(vec![1]) })
+			}
+			fn pull_request_authors(&self, _: u32) -> BoxFuture<'_, Vec<String>> {
+				Box::pin(async move {
+					self.requests.fetch_add(1, Ordering::Relaxed);
+					Ok(Vec::new())
+				})
+			}
+		}
+		let requests = Arc::new(AtomicUsize::new(0));
+		let cache: Arc<dyn Cache> = Arc::new(MemoryCache::default());
+		let provider = CachedProvider::new(
+			Box::new(Provider {
+				requests: requests.clone(),
+			}),
+			cache.clone(),
+			Some(60),
+		);
+		for _ in 0..2 {
+			assert_eq!(
+				Some(String::from("user-abc")),
+				provider.commit_author("abc").await?
+			);
+		}
+		assert_eq!(1, requests.load(Ordering::Relaxed));
+		cache.set_json(
+			CACHE_NAMESPACE,
+			"test/https://example.com/owner/repo/commits/def/author",
+			&serde_json::json!({ "timestamp": 0, "value": "expired" }),
+		)?;
+		assert_eq!(
+			Some(String::from("user-def")),
+			provider.commit_author("def").await?
+		);
+		assert_eq!(2, requests.load(Ordering::Relaxed));
+		for _ in 0..2 {
+			assert!(provider.pull_request_authors(1).await?.is_empty());
+		}
+		assert_eq!(4, requests.load(Ordering::Relaxed));
+		Ok(())
+	}
+}
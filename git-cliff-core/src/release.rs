@@ -1,21 +1,632 @@
-use crate::commit::Commit;
-use crate::error::Result;
+use crate::command;
+use crate::commit::{
+	Commit,
+	Signature,
+};
+use crate::config::ContextFields;
+use crate::error::{
+	Error,
+	Result,
+};
+use crate::signature::CryptoSignature;
+use git_conventional::Type;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+};
+use regex::Regex;
+use serde_json::Value;
+use sha2::{
+	Digest,
+	Sha256,
+};
+use std::collections::{
+	BTreeMap,
+	HashMap,
+	HashSet,
+};
+use std::fs;
+use std::path::Path;
+
+/// Extensions of the detached signature files.
+const SIGNATURE_EXTENSIONS: &[&str] = &["sig", "asc"];
+
+/// Version of the first release if there is no previous version.
+const INITIAL_VERSION: &str = "0.1.0";
+
+/// Regular expression for matching the start of a trailer (e.g.
+/// `Signed-off-by: `), which ends a migration note.
+static TRAILER_REGEX: Lazy<Regex> =
+	lazy_regex!(r"^(BREAKING[ -]CHANGE|[A-Za-z][A-Za-z0-9-]*)(: | #)");
+
+/// Semantic version bump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bump {
+	/// Increment the major version.
+	Major,
+	/// Increment the minor version.
+	Minor,
+	/// Increment the patch version.
+	Patch,
+	/// Determine the bump from the conventional commits.
+	Auto,
+}
 
 /// Representation of a release.
 #[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Release<'a> {
 	/// Release version, git tag.
-	pub version:   Option<String>,
+	pub version:         Option<String>,
 	/// Commits made for the release.
-	pub commits:   Vec<Commit<'a>>,
+	pub commits:         Vec<Commit<'a>>,
 	/// Commit ID of the tag.
 	#[serde(rename = "commit_id")]
-	pub commit_id: Option<String>,
+	pub commit_id:       Option<String>,
 	/// Timestamp of the release in seconds, from epoch.
-	pub timestamp: i64,
+	pub timestamp:       i64,
 	/// Previous release.
-	pub previous:  Option<Box<Release<'a>>>,
+	pub previous:        Option<Box<Release<'a>>>,
+	/// Release channel, labeled by the matched tag pattern.
+	pub channel:         Option<String>,
+	/// Number of commits omitted from each group.
+	#[serde(rename = "omitted_commits", default)]
+	pub omitted_commits: BTreeMap<String, usize>,
+	/// Number of commits in each group.
+	#[serde(default)]
+	pub summary:         Option<BTreeMap<String, usize>>,
+	/// Artifact files of the release.
+	#[serde(default)]
+	pub artifacts:       Vec<Artifact>,
+	/// Published release on the remote repository (if fetched).
+	#[serde(default)]
+	pub remote:          Option<RemoteRelease>,
+	/// Migration notes of the commits (e.g. for an upgrade guide).
+	#[serde(rename = "migration_notes", default)]
+	pub migration_notes: Vec<MigrationNote>,
+	/// Changes of the public API since the previous release.
+	#[serde(rename = "api_changes", default)]
+	pub api_changes:     Option<ApiChanges>,
+	/// Whether the tag of the release is signed.
+	#[serde(rename = "tag_signed", default)]
+	pub tag_signed:      bool,
+	/// Cryptographic signature of the tag.
+	#[serde(rename = "tag_signature", default)]
+	pub tag_signature:   Option<CryptoSignature>,
+	/// Metrics of the release that are computed by the metric commands.
+	#[serde(default)]
+	pub metrics:         BTreeMap<String, Value>,
+	/// Whether the regenerated notes differ from the published ones.
+	#[serde(default)]
+	pub edited:          bool,
+	/// Statistics of the release.
+	#[serde(default)]
+	pub statistics:      ReleaseStatistics,
+	/// Authors and co-authors of the commits.
+	#[serde(default)]
+	pub contributors:    Vec<Contributor>,
+	/// Breaking changes of the commits.
+	#[serde(rename = "breaking_changes", default)]
+	pub breaking:        Vec<BreakingChange>,
+	/// Git tag of the release.
+	#[serde(default)]
+	pub tag:             Option<Tag>,
+}
+
+/// Git tag of a release.
+#[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Tag {
+	/// Name of the tag.
+	pub name:    String,
+	/// Message of the annotated tag without its signature.
+	pub message: Option<String>,
+	/// Tagger of the annotated tag.
+	pub tagger:  Option<Signature>,
+}
+
+/// Breaking change that is introduced by a commit.
+#[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BreakingChange {
+	/// ID of the commit.
+	pub commit_id:   String,
+	/// Scope of the commit.
+	pub scope:       Option<String>,
+	/// Description of the breaking change.
+	pub description: String,
+}
+
+/// Author or co-author of the commits in a release.
+#[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Contributor {
+	/// Name of the contributor.
+	pub name:            Option<String>,
+	/// Email of the contributor.
+	pub email:           Option<String>,
+	/// Username of the contributor on GitHub.
+	pub github_username: Option<String>,
+	/// Number of the commits of the contributor in the release.
+	pub commit_count:    usize,
+	/// Whether the contributor did not contribute before the release.
+	pub is_first_time:   bool,
+}
+
+/// Statistics of a release, e.g. for rendering a summary line.
+#[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ReleaseStatistics {
+	/// Number of the commits.
+	pub commit_count:        usize,
+	/// Number of the distinct authors and co-authors.
+	pub contributor_count:   usize,
+	/// Number of the changed files since the previous release.
+	pub files_changed:       usize,
+	/// Number of the inserted lines since the previous release.
+	pub insertions:          usize,
+	/// Number of the deleted lines since the previous release.
+	pub deletions:           usize,
+	/// Number of days since the previous release.
+	pub days_since_previous: Option<i64>,
+	/// Number of the commits in each group.
+	pub group_counts:        BTreeMap<String, usize>,
+}
+
+/// Changes of the public API of a library, e.g. the Rust items that are
+/// reported by `cargo public-api diff`.
+#[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ApiChanges {
+	/// Added items.
+	pub added:   Vec<String>,
+	/// Removed items.
+	pub removed: Vec<String>,
+	/// Changed items (with their new signatures).
+	pub changed: Vec<String>,
+}
+
+impl ApiChanges {
+	/// Parses the output of a diff tool.
+	///
+	/// The items are the lines that start with `+` or `-`. Under a "Changed"
+	/// heading, the new signatures (i.e. `+`) are the changed items and the
+	/// old ones are ignored.
+	pub fn parse(output: &str) -> Self {
+		let mut changes = Self::default();
+		let mut in_changed = false;
+		for line in output.lines().map(str::trim_end) {
+			if line.chars().all(|c| c == '=' || c == '-') {
+				continue;
+			}
+			if let Some(item) = line.strip_prefix('+') {
+				if in_changed {
+					changes.changed.push(item.to_string());
+				} else {
+					changes.added.push(item.to_string());
+				}
+			} else if let Some(item) = line.strip_prefix('-') {
+				if !in_changed {
+					changes.removed.push(item.to_string());
+				}
+			} else {
+				in_changed = line.to_lowercase().starts_with("changed");
+			}
+		}
+		changes
+	}
+
+	/// Returns whether there are no changes.
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+	}
+}
+
+/// Migration note that is extracted from the body of a commit.
+#[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MigrationNote {
+	/// ID of the commit.
+	pub commit_id: String,
+	/// Text of the note without the marker.
+	pub text:      String,
+}
+
+/// Deprecation that is introduced in a release.
+#[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Deprecation {
+	/// Version of the release (`None` if unreleased).
+	pub version:   Option<String>,
+	/// ID of the commit.
+	pub commit_id: String,
+	/// Text of the deprecation.
+	pub text:      String,
+}
+
+/// Published release on the remote repository, e.g. a Github release.
+#[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RemoteRelease {
+	/// Title of the release.
+	pub name:   Option<String>,
+	/// Release notes.
+	pub body:   Option<String>,
+	/// URL of the release page.
+	pub url:    Option<String>,
+	/// Files that are uploaded to the release.
+	pub assets: Vec<RemoteAsset>,
+}
+
+/// File that is uploaded to a published release.
+#[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RemoteAsset {
+	/// File name.
+	pub name:           String,
+	/// Download URL of the file.
+	pub url:            String,
+	/// Size of the file in bytes.
+	pub size:           u64,
+	/// Number of the downloads.
+	pub download_count: u64,
+}
+
+/// Artifact file of a release.
+#[derive(Default, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Artifact {
+	/// File name.
+	pub name:      String,
+	/// Path of the file.
+	pub path:      String,
+	/// Size of the file in bytes.
+	pub size:      u64,
+	/// SHA-256 checksum of the file as a hex string.
+	pub sha256:    String,
+	/// File name of the detached signature (e.g. `<name>.sig`), if exists.
+	pub signature: Option<String>,
+}
+
+impl Artifact {
+	/// Computes the checksum of the given file.
+	pub fn new(path: &Path) -> Result<Self> {
+		let contents = fs::read(path)?;
+		let name = path
+			.file_name()
+			.map(|v| v.to_string_lossy().to_string())
+			.unwrap_or_default();
+		Ok(Self {
+			signature: SIGNATURE_EXTENSIONS
+				.iter()
+				.map(|extension| format!("{name}.{extension}"))
+				.find(|signature| path.with_file_name(signature).is_file()),
+			name,
+			path: path.to_string_lossy().to_string(),
+			size: contents.len() as u64,
+			sha256: format!("{:x}", Sha256::digest(&contents)),
+		})
+	}
+}
+
+impl<'a> Release<'a> {
+	/// Limits the number of commits in each group.
+	///
+	/// Breaking changes are kept first, then the commits that match the
+	/// `priority` patterns in the given order. The rest of the commits are
+	/// omitted and counted in [`omitted_commits`].
+	///
+	/// [`omitted_commits`]: Release::omitted_commits
+	pub fn limit_commits_per_group(&mut self, max: usize, priority: &[Regex]) {
+		let rank = |commit: &Commit| {
+			if commit.conv.as_ref().map(|c| c.breaking()).unwrap_or(false) {
+				return 0;
+			}
+			priority
+				.iter()
+				.position(|regex| regex.is_match(&commit.message))
+				.map(|i| i + 1)
+				.unwrap_or(priority.len() + 1)
+		};
+		let mut groups = BTreeMap::<String, Vec<(usize, usize)>>::new();
+		for (i, commit) in self.commits.iter().enumerate() {
+			if let Some(group) = Self::group_of(commit) {
+				groups.entry(group).or_default().push((rank(commit), i));
+			}
+		}
+		let mut omitted = HashSet::new();
+		for (group, mut entries) in groups {
+			if entries.len() > max {
+				entries.sort();
+				self.omitted_commits.insert(group, entries.len() - max);
+				omitted.extend(entries.drain(max..).map(|(_, i)| i));
+			}
+		}
+		let mut i = 0;
+		self.commits.retain(|_| {
+			i += 1;
+			!omitted.contains(&(i - 1))
+		});
+	}
+
+	/// Sets the artifacts of the release from the files that match the given
+	/// glob patterns.
+	///
+	/// `{version}` in the patterns is replaced with the version of the
+	/// release. The patterns without it only apply to the `latest` release.
+	/// Detached signatures are not included as artifacts.
+	pub fn set_artifacts(
+		&mut self,
+		patterns: &[String],
+		latest: bool,
+	) -> Result<()> {
+		self.artifacts.clear();
+		for pattern in patterns {
+			let pattern = match &self.version {
+				Some(version) if pattern.contains("{version}") => {
+					pattern.replace("{version}", version)
+				}
+				_ if latest && !pattern.contains("{version}") => pattern.to_string(),
+				_ => continue,
+			};
+			for path in glob::glob(&pattern)?.flatten() {
+				let is_signature = path
+					.extension()
+					.and_then(|v| v.to_str())
+					.map_or(false, |v| SIGNATURE_EXTENSIONS.contains(&v));
+				if path.is_file() && !is_signature {
+					self.artifacts.push(Artifact::new(&path)?);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Sets the metrics of the release by running the given commands.
+	///
+	/// The commands are run with the tag (or `HEAD` for the unreleased
+	/// changes) and the commit ID of the release as `RELEASE_REF` and
+	/// `RELEASE_COMMIT` environment variables. Numeric outputs are set as
+	/// numbers so that they can be compared between the releases, unless
+	/// they would be changed by the conversion (e.g. `1.60` as a version).
+	pub fn set_metrics(&mut self, commands: &HashMap<String, String>) -> Result<()> {
+		self.metrics.clear();
+		let reference = self.version.as_deref().unwrap_or("HEAD");
+		let commit_id = self.commit_id.as_deref().unwrap_or(reference);
+		for (name, command) in commands {
+			let output = command::run(command, None, vec![
+				("RELEASE_REF", reference),
+				("RELEASE_COMMIT", commit_id),
+			])?;
+			let output = output.trim();
+			let value = if let Ok(number) = output.parse::<i64>() {
+				Value::from(number)
+			} else if let Some(number) = output
+				.parse::<f64>()
+				.ok()
+				.filter(|v| v.to_string() == output)
+			{
+				Value::from(number)
+			} else {
+				Value::from(output)
+			};
+			self.metrics.insert(name.to_string(), value);
+		}
+		Ok(())
+	}
+
+	/// Sets the breaking changes of the release from the breaking
+	/// conventional commits.
+	///
+	/// The description is the `BREAKING CHANGE` footer of the commit (if any)
+	/// or its description.
+	pub fn set_breaking_changes(&mut self) {
+		self.breaking = self
+			.commits
+			.iter()
+			.filter_map(|commit| {
+				let conv = commit.conv.as_ref().filter(|conv| conv.breaking())?;
+				Some(BreakingChange {
+					commit_id:   commit.id.to_string(),
+					scope:       commit
+						.scope
+						.as_deref()
+						.or_else(|| conv.scope().map(|v| v.as_str()))
+						.map(String::from),
+					description: conv
+						.breaking_description()
+						.unwrap_or_else(|| conv.description())
+						.to_string(),
+				})
+			})
+			.collect();
+	}
+
+	/// Sets the migration notes of the release from the commit bodies.
+	///
+	/// A note is a paragraph (or a footer) that starts with one of the given
+	/// markers followed by a colon, e.g. `MIGRATION: rename xyz to abc`. It
+	/// ends at an empty line or another trailer (e.g. `Signed-off-by:`).
+	pub fn set_migration_notes(&mut self, markers: &[String]) {
+		self.migration_notes.clear();
+		for commit in &self.commits {
+			let message = commit.message.replace("\r\n", "\n");
+			for paragraph in message.split("\n\n").skip(1) {
+				let mut lines = paragraph.trim().lines();
+				let text = match lines.next().and_then(|line| {
+					markers.iter().find_map(|marker| {
+						line.strip_prefix(marker.as_str())
+							.and_then(|v| v.strip_prefix(':'))
+					})
+				}) {
+					Some(text) => text,
+					None => continue,
+				};
+				let text = std::iter::once(text)
+					.chain(lines.take_while(|line| !TRAILER_REGEX.is_match(line)))
+					.map(str::trim)
+					.collect::<Vec<&str>>()
+					.join("\n");
+				self.migration_notes.push(MigrationNote {
+					commit_id: commit.id.to_string(),
+					text:      text.trim().to_string(),
+				});
+			}
+		}
+	}
+
+	/// Returns the bump that is implied by the conventional commits.
+	///
+	/// Breaking changes bump the major version, features bump the minor
+	/// version and the rest bump the patch version.
+	pub fn commits_bump(&self) -> Bump {
+		let convs = self
+			.commits
+			.iter()
+			.filter_map(|commit| commit.conv.as_ref());
+		let mut bump = Bump::Patch;
+		for conv in convs {
+			if conv.breaking() {
+				return Bump::Major;
+			} else if conv.type_() == Type::FEAT {
+				bump = Bump::Minor;
+			}
+		}
+		bump
+	}
+
+	/// Calculates the next version based on the given previous version.
+	///
+	/// The prefix of the previous version (e.g. `v`) is preserved while its
+	/// pre-release and build metadata are dropped.
+	pub fn calculate_next_version(
+		&self,
+		previous: Option<&str>,
+		bump: Bump,
+	) -> Result<String> {
+		let previous = match previous {
+			Some(previous) => previous,
+			None => return Ok(String::from(INITIAL_VERSION)),
+		};
+		let invalid_version = || {
+			Error::ChangelogError(format!("Invalid semantic version: {previous}"))
+		};
+		let start = previous
+			.find(|c: char| c.is_ascii_digit())
+			.ok_or_else(invalid_version)?;
+		let (prefix, version) = previous.split_at(start);
+		let version = version.split(['-', '+']).next();
+		let numbers = version
+			.unwrap_or_default()
+			.split('.')
+			.map(|v| v.parse::<u64>().map_err(|_| invalid_version()))
+			.collect::<Result<Vec<u64>>>()?;
+		let (major, minor, patch) = match numbers.as_slice() {
+			[major, minor, patch] => (*major, *minor, *patch),
+			_ => return Err(invalid_version()),
+		};
+		let bump = match bump {
+			Bump::Auto => self.commits_bump(),
+			bump => bump,
+		};
+		Ok(match bump {
+			Bump::Major => format!("{prefix}{}.0.0", major + 1),
+			Bump::Minor => format!("{prefix}{major}.{}.0", minor + 1),
+			_ => format!("{prefix}{major}.{minor}.{}", patch + 1),
+		})
+	}
+
+	/// Updates the statistics of the release from its commits.
+	///
+	/// The changes of the files are not computed from the commits and they
+	/// are kept as is.
+	pub fn update_statistics(&mut self) {
+		let contributors = self
+			.commits
+			.iter()
+			.flat_map(Commit::signatures)
+			.filter_map(Signature::key)
+			.collect::<HashSet<&str>>();
+		self.statistics.commit_count = self.commits.len();
+		self.statistics.contributor_count = contributors.len();
+		self.statistics.days_since_previous = match &self.previous {
+			Some(previous)
+				if self.version.is_some() &&
+					previous.version.is_some() &&
+					previous.timestamp > 0 =>
+			{
+				Some((self.timestamp - previous.timestamp) / (24 * 60 * 60))
+			}
+			_ => None,
+		};
+		self.statistics.group_counts = self.group_counts();
+	}
+
+	/// Sets the contributors from the authors and co-authors of the commits.
+	///
+	/// The contributors that are not in the given set of the known
+	/// contributors are the first-time contributors and added to the set.
+	pub fn set_contributors(&mut self, known: &mut HashSet<String>) {
+		self.contributors =
+			self.contributors_of_commits(|key| known.insert(key.to_string()));
+	}
+
+	/// Updates the contributors from the commits, e.g. after they are
+	/// processed and some of them are filtered out.
+	///
+	/// The first-time contributors are kept from the previously set
+	/// contributors (see [`Release::set_contributors`]).
+	pub fn update_contributors(&mut self) {
+		let first_time = self
+			.contributors
+			.iter()
+			.filter(|v| v.is_first_time)
+			.filter_map(|v| v.email.clone().or_else(|| v.name.clone()))
+			.collect::<HashSet<String>>();
+		self.contributors =
+			self.contributors_of_commits(|key| first_time.contains(key));
+	}
+
+	/// Returns the contributors of the commits.
+	///
+	/// The given function returns whether the contributor with the given key
+	/// is a first-time contributor.
+	fn contributors_of_commits(
+		&self,
+		mut is_first_time: impl FnMut(&str) -> bool,
+	) -> Vec<Contributor> {
+		let mut contributors = Vec::<Contributor>::new();
+		let mut indices = HashMap::<&str, usize>::new();
+		for signature in self.commits.iter().flat_map(Commit::signatures) {
+			let key = match signature.key() {
+				Some(key) => key,
+				None => continue,
+			};
+			match indices.get(key) {
+				Some(&i) => contributors[i].commit_count += 1,
+				None => {
+					indices.insert(key, contributors.len());
+					contributors.push(Contributor {
+						name:            signature.name().map(String::from),
+						email:           signature.email().map(String::from),
+						github_username: None,
+						commit_count:    1,
+						is_first_time:   is_first_time(key),
+					});
+				}
+			}
+		}
+		contributors
+	}
+
+	/// Returns the number of commits in each group.
+	pub fn group_counts(&self) -> BTreeMap<String, usize> {
+		let mut counts = BTreeMap::new();
+		for group in self.commits.iter().filter_map(Self::group_of) {
+			*counts.entry(group).or_default() += 1;
+		}
+		counts
+	}
+
+	/// Returns the group of the commit based on a commit parser or its
+	/// conventional type.
+	fn group_of(commit: &Commit) -> Option<String> {
+		commit
+			.group
+			.as_deref()
+			.map(String::from)
+			.or_else(|| commit.conv.as_ref().map(|c| c.type_().to_string()))
+	}
 }
 
 /// Representation of a list of releases.
@@ -26,4 +637,536 @@ impl<'a> Releases<'a> {
 	pub fn as_json(&self) -> Result<String> {
 		Ok(serde_json::to_string(self.0)?)
 	}
+
+	/// Returns the list of releases as JSON with the given fields of the
+	/// commits.
+	///
+	/// The full messages of the commits (`raw_message`) are only serialized if
+	/// they are included.
+	pub fn as_json_with_fields(&self, fields: &ContextFields) -> Result<String> {
+		let mut releases = serde_json::to_value(self.0)?;
+		if let Value::Array(values) = &mut releases {
+			for (value, release) in values.iter_mut().zip(self.0) {
+				Self::select_fields(value, release, fields);
+			}
+		}
+		Ok(serde_json::to_string(&releases)?)
+	}
+
+	/// Selects the fields of the commits of the release and its previous
+	/// release.
+	fn select_fields(value: &mut Value, release: &Release, fields: &ContextFields) {
+		let include = fields.include.as_ref().map(|include| {
+			include
+				.iter()
+				.map(|path| path.split('.').collect())
+				.collect::<Vec<Vec<&str>>>()
+		});
+		if let Some(Value::Array(commits)) = value.get_mut("commits") {
+			for (value, commit) in commits.iter_mut().zip(&release.commits) {
+				if let Some(include) = &include {
+					if let Value::Object(object) = value {
+						if include.iter().any(|path| path == &["raw_message"]) {
+							object.insert(
+								String::from("raw_message"),
+								Value::from(commit.message.as_str()),
+							);
+						}
+					}
+					Self::retain_fields(value, include);
+				}
+				for path in fields.exclude.iter().flatten() {
+					Self::remove_field(
+						value,
+						&path.split('.').collect::<Vec<&str>>(),
+					);
+				}
+			}
+		}
+		if let (Some(value), Some(previous)) =
+			(value.get_mut("previous"), &release.previous)
+		{
+			Self::select_fields(value, previous, fields);
+		}
+	}
+
+	/// Keeps only the fields at the given paths. Arrays are traversed
+	/// element-wise.
+	fn retain_fields(value: &mut Value, paths: &[Vec<&str>]) {
+		match value {
+			Value::Array(values) => {
+				for value in values {
+					Self::retain_fields(value, paths);
+				}
+			}
+			Value::Object(object) => {
+				object.retain(|key, _| {
+					paths.iter().any(|path| path.first() == Some(&key.as_str()))
+				});
+				for (key, value) in object.iter_mut() {
+					let rest = paths
+						.iter()
+						.filter(|path| path.first() == Some(&key.as_str()))
+						.map(|path| path[1..].to_vec())
+						.collect::<Vec<Vec<&str>>>();
+					// The whole field is kept if it is included as is.
+					if !rest.iter().any(Vec::is_empty) {
+						Self::retain_fields(value, &rest);
+					}
+				}
+			}
+			_ => {}
+		}
+	}
+
+	/// Removes the field at the given path. Arrays are traversed element-wise.
+	fn remove_field(value: &mut Value, path: &[&str]) {
+		match value {
+			Value::Array(values) => {
+				for value in values {
+					Self::remove_field(value, path);
+				}
+			}
+			Value::Object(object) => match path {
+				[] => {}
+				[key] => {
+					object.remove(*key);
+				}
+				[key, rest @ ..] => {
+					if let Some(value) = object.get_mut(*key) {
+						Self::remove_field(value, rest);
+					}
+				}
+			},
+			_ => {}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::env;
+	#[test]
+	fn limit_commits_per_group() -> Result<()> {
+		let mut release = Release {
+			commits: vec![
+				"fix: fix abc",
+				"fix(ui): fix xyz",
+				"fix!: break stuff",
+				"fix: fix typo",
+				"feat: add xyz",
+			]
+			.into_iter()
+			.enumerate()
+			.map(|(i, message)| {
+				Commit::new(i.to_string(), String::from(message)).into_conventional()
+			})
+			.collect::<Result<Vec<Commit>>>()?,
+			..Release::default()
+		};
+		release.limit_commits_per_group(2, &[Regex::new(r"^fix\(ui\)")?]);
+		assert_eq!(
+			vec!["fix(ui): fix xyz", "fix!: break stuff", "feat: add xyz"],
+			release
+				.commits
+				.iter()
+				.map(|c| c.message.as_str())
+				.collect::<Vec<&str>>()
+		);
+		assert_eq!(Some(&2), release.omitted_commits.get("fix"));
+		assert_eq!(None, release.omitted_commits.get("feat"));
+		assert_eq!(
+			BTreeMap::from([(String::from("feat"), 1), (String::from("fix"), 2)]),
+			release.group_counts()
+		);
+		Ok(())
+	}
+	#[test]
+	fn update_statistics() -> Result<()> {
+		let signature = |name: &str, email: &str| {
+			serde_json::from_value::<Signature>(
+				serde_json::json!({ "name": name, "email": email, "timestamp": 0 }),
+			)
+		};
+		let mut commits = ["feat: add xyz", "fix: fix xyz", "fix: fix abc"]
+			.into_iter()
+			.enumerate()
+			.map(|(i, message)| {
+				Commit::new(i.to_string(), String::from(message)).into_conventional()
+			})
+			.collect::<Result<Vec<Commit>>>()?;
+		commits[0].author = signature("John Doe", "john@doe.com")?;
+		commits[1].author = signature("John", "john@doe.com")?;
+		commits[1].coauthors = vec![signature("Jane Doe", "jane@doe.com")?];
+		let mut release = Release {
+			version: Some(String::from("v1.0.0")),
+			commits,
+			timestamp: 864000,
+			previous: Some(Box::new(Release {
+				version: Some(String::from("v0.1.0")),
+				timestamp: 86400,
+				..Release::default()
+			})),
+			..Release::default()
+		};
+		release.update_statistics();
+		assert_eq!(
+			ReleaseStatistics {
+				commit_count: 3,
+				contributor_count: 2,
+				days_since_previous: Some(9),
+				group_counts: BTreeMap::from([
+					(String::from("feat"), 1),
+					(String::from("fix"), 2)
+				]),
+				..ReleaseStatistics::default()
+			},
+			release.statistics
+		);
+		release.version = None;
+		release.update_statistics();
+		assert_eq!(None, release.statistics.days_since_previous);
+		Ok(())
+	}
+
+	#[test]
+	fn set_contributors() -> Result<()> {
+		let signature = |name: &str, email: &str| {
+			serde_json::from_value::<Signature>(
+				serde_json::json!({ "name": name, "email": email, "timestamp": 0 }),
+			)
+		};
+		let mut commits = ["feat: add xyz", "fix: fix xyz", "fix: fix abc"]
+			.into_iter()
+			.map(|message| Commit::from(String::from(message)))
+			.collect::<Vec<Commit>>();
+		commits[0].author = signature("John Doe", "john@doe.com")?;
+		commits[1].author = signature("Jane Doe", "jane@doe.com")?;
+		commits[1].coauthors = vec![signature("John Doe", "john@doe.com")?];
+		let mut release = Release {
+			commits,
+			..Release::default()
+		};
+		let mut known = HashSet::from([String::from("jane@doe.com")]);
+		release.set_contributors(&mut known);
+		assert_eq!(
+			vec![
+				Contributor {
+					name: Some(String::from("John Doe")),
+					email: Some(String::from("john@doe.com")),
+					commit_count: 2,
+					is_first_time: true,
+					..Contributor::default()
+				},
+				Contributor {
+					name: Some(String::from("Jane Doe")),
+					email: Some(String::from("jane@doe.com")),
+					commit_count: 1,
+					is_first_time: false,
+					..Contributor::default()
+				},
+			],
+			release.contributors
+		);
+		assert!(known.contains("john@doe.com"));
+		release.commits.remove(0);
+		release.update_contributors();
+		assert_eq!(
+			vec![
+				Contributor {
+					name: Some(String::from("Jane Doe")),
+					email: Some(String::from("jane@doe.com")),
+					commit_count: 1,
+					is_first_time: false,
+					..Contributor::default()
+				},
+				Contributor {
+					name: Some(String::from("John Doe")),
+					email: Some(String::from("john@doe.com")),
+					commit_count: 1,
+					is_first_time: true,
+					..Contributor::default()
+				},
+			],
+			release.contributors
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn parse_api_changes() {
+		let changes = ApiChanges::parse(
+			"Removed items from the public API
+=================================
+-pub fn xyz()
+
+Changed items in the public API
+===============================
+-pub fn abc(a: u8)
++pub fn abc(a: u16)
+
+Added items to the public API
+=============================
++pub fn def()
++pub struct Def
+",
+		);
+		assert_eq!(
+			ApiChanges {
+				added:   vec![
+					String::from("pub fn def()"),
+					String::from("pub struct Def")
+				],
+				removed: vec![String::from("pub fn xyz()")],
+				changed: vec![String::from("pub fn abc(a: u16)")],
+			},
+			changes
+		);
+		assert!(!changes.is_empty());
+		assert!(ApiChanges::parse("Added items\n(none)\n").is_empty());
+	}
+	#[test]
+	fn set_breaking_changes() -> Result<()> {
+		let mut release = Release {
+			commits: vec![
+				"feat(api)!: rename xyz",
+				"fix: fix xyz\n\nBREAKING CHANGE: xyz is removed",
+				"fix: fix abc",
+			]
+			.into_iter()
+			.enumerate()
+			.map(|(i, message)| {
+				Commit::new(i.to_string(), String::from(message)).into_conventional()
+			})
+			.collect::<Result<Vec<Commit>>>()?,
+			..Release::default()
+		};
+		release.set_breaking_changes();
+		assert_eq!(
+			vec![
+				BreakingChange {
+					commit_id:   String::from("0"),
+					scope:       Some(String::from("api")),
+					description: String::from("rename xyz"),
+				},
+				BreakingChange {
+					commit_id:   String::from("1"),
+					scope:       None,
+					description: String::from("xyz is removed"),
+				},
+			],
+			release.breaking
+		);
+		Ok(())
+	}
+	#[test]
+	fn set_migration_notes() {
+		let mut release = Release {
+			commits: vec![
+				Commit::new(
+					String::from("abc"),
+					String::from(
+						"feat!: rename xyz\n\nMIGRATION: Rename `xyz` to `abc`\nin \
+						 the config.\n\nSigned-off-by: git-cliff",
+					),
+				),
+				Commit::new(
+					String::from("def"),
+					String::from("fix: fix xyz\n\nMIGRATION: xyz"),
+				),
+				Commit::new(
+					String::from("ghi"),
+					String::from(
+						"refactor: remove xyz\r\n\r\nUpgrade: Remove \
+						 `xyz`.\r\nRefs: #1",
+					),
+				),
+			],
+			..Release::default()
+		};
+		release.set_migration_notes(&[
+			String::from("MIGRATION"),
+			String::from("Upgrade"),
+		]);
+		assert_eq!(
+			vec![
+				("abc", "Rename `xyz` to `abc`\nin the config."),
+				("def", "xyz"),
+				("ghi", "Remove `xyz`."),
+			],
+			release
+				.migration_notes
+				.iter()
+				.map(|v| (v.commit_id.as_str(), v.text.as_str()))
+				.collect::<Vec<(&str, &str)>>()
+		);
+	}
+	#[test]
+	fn calculate_next_version() -> Result<()> {
+		let release = |messages: &[&str]| -> Result<Release> {
+			Ok(Release {
+				commits: messages
+					.iter()
+					.map(|message| {
+						Commit::new(String::from("abc"), message.to_string())
+							.into_conventional()
+					})
+					.collect::<Result<Vec<Commit>>>()?,
+				..Release::default()
+			})
+		};
+		for (messages, previous, bump, expected) in [
+			(vec!["fix: abc"], Some("v1.2.3"), Bump::Auto, "v1.2.4"),
+			(
+				vec!["feat: abc", "fix: xyz"],
+				Some("1.2.3"),
+				Bump::Auto,
+				"1.3.0",
+			),
+			(
+				vec!["feat!: abc"],
+				Some("app/v1.2.3-rc.1"),
+				Bump::Auto,
+				"app/v2.0.0",
+			),
+			(vec!["fix: abc"], Some("v1.2.3"), Bump::Major, "v2.0.0"),
+			(vec!["feat: abc"], Some("v1.2.3"), Bump::Patch, "v1.2.4"),
+			(vec!["feat: abc"], None, Bump::Auto, "0.1.0"),
+		] {
+			assert_eq!(
+				expected,
+				release(&messages)?.calculate_next_version(previous, bump)?
+			);
+		}
+		assert!(release(&["fix: abc"])?
+			.calculate_next_version(Some("latest"), Bump::Auto)
+			.is_err());
+		Ok(())
+	}
+	#[test]
+	#[cfg(target_family = "unix")]
+	fn set_metrics() -> Result<()> {
+		let mut release = Release {
+			version: Some(String::from("v1.0.0")),
+			commit_id: Some(String::from("abc")),
+			..Release::default()
+		};
+		release.set_metrics(&HashMap::from([
+			(String::from("size"), String::from("echo 1024")),
+			(String::from("msrv"), String::from("echo 1.60")),
+			(String::from("ratio"), String::from("echo 0.5")),
+			(
+				String::from("tag"),
+				String::from("echo $RELEASE_REF@$RELEASE_COMMIT"),
+			),
+		]))?;
+		assert_eq!(Value::from(1024), release.metrics["size"]);
+		assert_eq!(Value::from("1.60"), release.metrics["msrv"]);
+		assert_eq!(Value::from(0.5), release.metrics["ratio"]);
+		assert_eq!(Value::from("v1.0.0@abc"), release.metrics["tag"]);
+		assert!(release
+			.set_metrics(&HashMap::from([(
+				String::from("size"),
+				String::from("exit 1")
+			)]))
+			.is_err());
+		Ok(())
+	}
+	#[test]
+	fn set_artifacts() -> Result<()> {
+		let dir = env::temp_dir().join("git-cliff-artifacts");
+		if dir.exists() {
+			fs::remove_dir_all(&dir)?;
+		}
+		fs::create_dir_all(dir.join("v1.0.0"))?;
+		fs::write(dir.join("v1.0.0").join("app.tar.gz"), "abc")?;
+		fs::write(dir.join("v1.0.0").join("app.tar.gz.sig"), "sig")?;
+		fs::write(dir.join("notes.txt"), "")?;
+		let patterns = [
+			format!("{}/{{version}}/*", dir.display()),
+			format!("{}/*.txt", dir.display()),
+		];
+		let mut release = Release {
+			version: Some(String::from("v1.0.0")),
+			..Release::default()
+		};
+		release.set_artifacts(&patterns, false)?;
+		assert_eq!(
+			vec![Artifact {
+				name:      String::from("app.tar.gz"),
+				path:      dir
+					.join("v1.0.0")
+					.join("app.tar.gz")
+					.to_string_lossy()
+					.to_string(),
+				size:      3,
+				sha256:    String::from(
+					"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+				),
+				signature: Some(String::from("app.tar.gz.sig")),
+			}],
+			release.artifacts
+		);
+		release.set_artifacts(&patterns, true)?;
+		assert_eq!(
+			vec!["app.tar.gz", "notes.txt"],
+			release
+				.artifacts
+				.iter()
+				.map(|v| v.name.as_str())
+				.collect::<Vec<&str>>()
+		);
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+
+	#[test]
+	fn releases_as_json_with_fields() -> Result<()> {
+		let mut commit =
+			Commit::new(String::from("abc123"), String::from("feat: add xyz"));
+		commit.author = serde_json::from_value(serde_json::json!({
+			"name": "Y", "email": "y@example.com", "timestamp": 0
+		}))?;
+		commit.coauthors = vec![serde_json::from_value(serde_json::json!({
+			"name": "X", "email": "x@example.com", "timestamp": 0
+		}))?];
+		let releases = vec![Release {
+			version: Some(String::from("v1.0.0")),
+			commits: vec![commit.into_conventional()?],
+			..Release::default()
+		}];
+		let fields = ContextFields {
+			include: Some(vec![
+				String::from("id"),
+				String::from("raw_message"),
+				String::from("author.name"),
+				String::from("coauthors"),
+			]),
+			exclude: Some(vec![String::from("coauthors.email")]),
+		};
+		let json: Value = serde_json::from_str(
+			&Releases(&releases).as_json_with_fields(&fields)?,
+		)?;
+		assert_eq!("v1.0.0", json[0]["version"]);
+		assert_eq!(
+			serde_json::json!({
+				"id": "abc123",
+				"raw_message": "feat: add xyz",
+				"author": { "name": "Y" },
+				"coauthors": [{ "name": "X", "timestamp": 0 }],
+			}),
+			json[0]["commits"][0]
+		);
+		let json: Value = serde_json::from_str(
+			&Releases(&releases).as_json_with_fields(&ContextFields {
+				include: None,
+				exclude: Some(vec![String::from("author")]),
+			})?,
+		)?;
+		assert_eq!(None, json[0]["commits"][0].get("raw_message"));
+		assert_eq!(None, json[0]["commits"][0].get("author"));
+		Ok(())
+	}
 }
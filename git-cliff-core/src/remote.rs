@@ -0,0 +1,301 @@
+use crate::config::{
+	Forge,
+	RemoteConfig,
+};
+use crate::gitea;
+
+impl Forge {
+	/// Detects the forge from the web URL of a repository.
+	///
+	/// The well-known public instances of Gitea (e.g. Codeberg) are detected
+	/// as well. Self-hosted instances that cannot be recognized by their host
+	/// name are assumed to be Gitea-compatible.
+	pub fn detect(url: &str) -> Self {
+		let host = url
+			.split("://")
+			.nth(1)
+			.and_then(|v| v.split('/').next())
+			.unwrap_or_default()
+			.to_lowercase();
+		if host == "github.com" || host.starts_with("github.") {
+			Self::Github
+		} else if host.contains("gitlab") {
+			Self::Gitlab
+		} else if host.ends_with("sr.ht") {
+			Self::Sourcehut
+		} else if gitea::public_instance(url).is_some() {
+			Self::Gitea
+		} else if host.contains("bitbucket") {
+			Self::Bitbucket
+		} else if host.starts_with("cgit.") {
+			Self::Cgit
+		} else {
+			Self::Gitea
+		}
+	}
+
+	/// Returns the default templates of the commit, compare and log links.
+	fn link_templates(&self) -> (&'static str, &'static str, &'static str) {
+		match self {
+			Self::Github => (
+				"{url}/commit/{id}",
+				"{url}/compare/{from}..{to}",
+				"{url}/commits/{ref}",
+			),
+			Self::Gitlab => (
+				"{url}/-/commit/{id}",
+				"{url}/-/compare/{from}...{to}",
+				"{url}/-/commits/{ref}",
+			),
+			Self::Gitea => (
+				"{url}/commit/{id}",
+				"{url}/compare/{from}...{to}",
+				"{url}/commits",
+			),
+			Self::Sourcehut => {
+				("{url}/commit/{id}", "{url}/log/{to}", "{url}/log/{ref}")
+			}
+			Self::Cgit => (
+				"{url}/commit/?id={id}",
+				"{url}/diff/?id={to}&id2={from}",
+				"{url}/log/?id={ref}",
+			),
+			Self::Bitbucket => (
+				"{url}/commits/{id}",
+				"{url}/branches/compare/{to}%0D{from}",
+				"{url}/commits",
+			),
+		}
+	}
+}
+
+/// Remote repository with the templates of its web links.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remote {
+	/// Web URL of the repository.
+	pub url:     String,
+	/// Forge of the repository.
+	pub forge:   Forge,
+	commit_url:  String,
+	compare_url: String,
+	log_url:     String,
+}
+
+impl Remote {
+	/// Constructs a new instance from the configuration.
+	///
+	/// If the URL is not configured, the first git remote that can be
+	/// converted to a web URL is used.
+	pub fn new(config: &RemoteConfig, git_remotes: &[String]) -> Option<Self> {
+		let url = config
+			.url
+			.as_ref()
+			.map(|url| url.trim_end_matches('/').to_string())
+			.or_else(|| git_remotes.iter().find_map(|v| Self::web_url(v)))?;
+		let forge = config.forge.unwrap_or_else(|| Forge::detect(&url));
+		let (commit_url, compare_url, log_url) = forge.link_templates();
+		Some(Self {
+			commit_url: config
+				.commit_url
+				.clone()
+				.unwrap_or_else(|| commit_url.to_string()),
+			compare_url: config
+				.compare_url
+				.clone()
+				.unwrap_or_else(|| compare_url.to_string()),
+			log_url: config
+				.log_url
+				.clone()
+				.unwrap_or_else(|| log_url.to_string()),
+			url,
+			forge,
+		})
+	}
+
+	/// Converts the URL of a git remote to the web URL of the repository.
+	///
+	/// Supports the URLs with a scheme (e.g. `ssh://git@host/owner/repo.git`)
+	/// and the scp-like syntax (e.g. `git@host:owner/repo.git`).
+	pub fn web_url(remote: &str) -> Option<String> {
+		let remote = remote.trim().trim_end_matches('/');
+		let remote = remote.strip_suffix(".git").unwrap_or(remote);
+		let (scheme, address) = match remote.split_once("://") {
+			Some((scheme, address)) => (scheme, address),
+			None if !remote.starts_with('/') && remote.contains(':') => {
+				("ssh", remote)
+			}
+			None => return None,
+		};
+		let address = address
+			.split_once('@')
+			.filter(|(user, _)| !user.contains('/'))
+			.map_or(address, |(_, address)| address);
+		let (host, path) = match scheme {
+			"http" | "https" => address.split_once('/')?,
+			_ => {
+				let (host, path) = address.split_once(['/', ':'])?;
+				// Drop the port of the ssh remotes.
+				let path = path
+					.split_once('/')
+					.filter(|(port, _)| port.chars().all(|c| c.is_ascii_digit()))
+					.map_or(path, |(_, path)| path);
+				(host, path)
+			}
+		};
+		if host.is_empty() || path.is_empty() {
+			return None;
+		}
+		let scheme = if scheme == "http" { "http" } else { "https" };
+		Some(format!("{scheme}://{host}/{path}"))
+	}
+
+	/// Returns the link of the commit.
+	pub fn commit_link(&self, id: &str) -> String {
+		self.commit_url
+			.replace("{url}", &self.url)
+			.replace("{id}", id)
+	}
+
+	/// Returns the link for comparing the given references.
+	pub fn compare_link(&self, from: &str, to: &str) -> String {
+		self.compare_url
+			.replace("{url}", &self.url)
+			.replace("{from}", from)
+			.replace("{to}", to)
+	}
+
+	/// Returns the link of the pull request with the given number.
+	pub fn pull_request_link(&self, number: u32) -> String {
+		match self.forge {
+			Forge::Gitlab => format!("{}/-/merge_requests/{number}", self.url),
+			Forge::Gitea => format!("{}/pulls/{number}", self.url),
+			Forge::Bitbucket => format!("{}/pull-requests/{number}", self.url),
+			_ => format!("{}/pull/{number}", self.url),
+		}
+	}
+
+	/// Returns the link of the profile of the user on the forge.
+	///
+	/// The profiles of SourceHut are on the hub (e.g. `https://sr.ht/~user`)
+	/// instead of the git service.
+	pub fn user_link(&self, username: &str) -> String {
+		let host_end = self
+			.url
+			.find("://")
+			.and_then(|i| self.url[i + 3..].find('/').map(|j| i + 3 + j))
+			.unwrap_or(self.url.len());
+		let base_url = &self.url[..host_end];
+		match self.forge {
+			Forge::Sourcehut => format!(
+				"{}/~{}",
+				base_url.replacen("://git.", "://", 1),
+				username.trim_start_matches('~')
+			),
+			_ => format!("{base_url}/{username}"),
+		}
+	}
+
+	/// Returns the link of the history of the given reference.
+	pub fn log_link(&self, reference: &str) -> String {
+		self.log_url
+			.replace("{url}", &self.url)
+			.replace("{ref}", reference)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn web_url() {
+		for (remote, url) in [
+			(
+				"https://github.com/orhun/git-cliff.git",
+				Some("https://github.com/orhun/git-cliff"),
+			),
+			(
+				"git@codeberg.org:owner/repo.git",
+				Some("https://codeberg.org/owner/repo"),
+			),
+			(
+				"ssh://git@git.example.com:2222/owner/repo.git",
+				Some("https://git.example.com/owner/repo"),
+			),
+			(
+				"git@git.sr.ht:~user/repo",
+				Some("https://git.sr.ht/~user/repo"),
+			),
+			(
+				"http://git.example.com/cgit/repo.git/",
+				Some("http://git.example.com/cgit/repo"),
+			),
+			("/srv/git/repo.git", None),
+		] {
+			assert_eq!(url.map(String::from), Remote::web_url(remote));
+		}
+	}
+
+	#[test]
+	fn links() {
+		let remote = Remote::new(&RemoteConfig::default(), &[
+			String::from("/srv/git/repo.git"),
+			String::from("git@gitlab.example.com:owner/repo.git"),
+		])
+		.expect("remote not found");
+		assert_eq!(Forge::Gitlab, remote.forge);
+		assert_eq!(
+			"https://gitlab.example.com/owner/repo/-/commit/abc",
+			remote.commit_link("abc")
+		);
+		let remote = Remote::new(
+			&RemoteConfig {
+				url:         Some(String::from("https://git.example.com/repo/")),
+				forge:       Some(Forge::Cgit),
+				commit_url:  None,
+				compare_url: None,
+				log_url:     None,
+				retry:       None,
+			},
+			&[],
+		)
+		.expect("remote not found");
+		assert_eq!(
+			"https://git.example.com/repo/diff/?id=v1.1&id2=v1.0",
+			remote.compare_link("v1.0", "v1.1")
+		);
+		assert_eq!(
+			"https://git.example.com/repo/log/?id=HEAD",
+			remote.log_link("HEAD")
+		);
+		assert_eq!(Forge::Gitea, Forge::detect("https://git.example.com/repo"));
+		assert_eq!(
+			Forge::Gitea,
+			Forge::detect("https://codeberg.org/bitbucket/repo")
+		);
+		let remote = Remote::new(&RemoteConfig::default(), &[String::from(
+			"git@codeberg.org:owner/repo.git",
+		)])
+		.expect("remote not found");
+		assert_eq!(
+			"https://codeberg.org/owner/repo/pulls/7",
+			remote.pull_request_link(7)
+		);
+		assert_eq!("https://codeberg.org/user", remote.user_link("user"));
+		let remote = Remote::new(&RemoteConfig::default(), &[String::from(
+			"git@git.sr.ht:~user/repo",
+		)])
+		.expect("remote not found");
+		assert_eq!(Forge::Sourcehut, remote.forge);
+		assert_eq!(
+			"https://git.sr.ht/~user/repo/commit/abc",
+			remote.commit_link("abc")
+		);
+		assert_eq!(
+			"https://git.sr.ht/~user/repo/log/v1.0",
+			remote.log_link("v1.0")
+		);
+		assert_eq!("https://sr.ht/~user", remote.user_link("~user"));
+	}
+}
@@ -1,18 +1,45 @@
+use crate::commit::{
+	message_of,
+	Signature,
+};
+use crate::config::{
+	MergeCommits,
+	SortBy,
+};
 use crate::error::{
 	Error,
 	Result,
 };
+use crate::release::Tag;
 use git2::{
 	Commit,
+	DescribeFormatOptions,
 	DescribeOptions,
+	DiffStats,
+	Oid,
 	Repository as GitRepository,
 	Sort,
 };
 use glob::Pattern;
 use indexmap::IndexMap;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+use std::cmp::Reverse;
+use std::collections::{
+	HashMap,
+	HashSet,
+};
 use std::io;
 use std::path::PathBuf;
 
+/// Regular expression for matching the trailer that is added by
+/// `git cherry-pick -x`.
+static CHERRY_PICK_REGEX: Lazy<Regex> =
+	lazy_regex!(r#"\(cherry picked from commit ([a-f0-9]{40})\)"#);
+
 /// Wrapper for [`Repository`] type from git2.
 ///
 /// [`Repository`]: GitRepository
@@ -37,24 +64,82 @@ impl Repository {
 
 	/// Parses and returns the commits.
 	///
+	/// `range` can be either a commit range (e.g. `v1.0.0..v1.1.0`) or a single
+	/// revision for processing all of its ancestors.
+	///
 	/// Sorts the commits by their time.
 	pub fn commits(
 		&self,
 		range: Option<String>,
 		include_path: Option<Vec<Pattern>>,
 		exclude_path: Option<Vec<Pattern>>,
+		sort_by: SortBy,
 	) -> Result<Vec<Commit>> {
-		let mut revwalk = self.inner.revwalk()?;
-		revwalk.set_sorting(Sort::TIME | Sort::TOPOLOGICAL)?;
-		if let Some(range) = range {
-			revwalk.push_range(&range)?;
-		} else {
-			revwalk.push_head()?;
+		self.commits_in_ranges(
+			&range.into_iter().collect::<Vec<String>>(),
+			include_path,
+			exclude_path,
+			sort_by,
+			MergeCommits::Include,
+		)
+	}
+
+	/// Parses and returns the commits of the given ranges (or branches).
+	///
+	/// The commits of the ranges are merged and deduplicated by their IDs, e.g.
+	/// for processing a maintenance branch along with the main branch. `HEAD`
+	/// is processed if no ranges are given.
+	///
+	/// The merge commits are handled as given, e.g. only the first-parent
+	/// history is walked with [`MergeCommits::FirstParentOnly`].
+	///
+	/// The commits are in topological order (by the commit date) so that they
+	/// can be split into the releases. The commits of multiple ranges are
+	/// sorted by the given date.
+	pub fn commits_in_ranges(
+		&self,
+		ranges: &[String],
+		include_path: Option<Vec<Pattern>>,
+		exclude_path: Option<Vec<Pattern>>,
+		sort_by: SortBy,
+		merge_commits: MergeCommits,
+	) -> Result<Vec<Commit<'_>>> {
+		let mut ids = HashSet::new();
+		let mut commits = Vec::new();
+		for range in ranges
+			.iter()
+			.map(|v| Some(v.as_str()))
+			.chain(Some(None).filter(|_| ranges.is_empty()))
+		{
+			let mut revwalk = self.inner.revwalk()?;
+			revwalk.set_sorting(Sort::TIME | Sort::TOPOLOGICAL)?;
+			if merge_commits == MergeCommits::FirstParentOnly {
+				revwalk.simplify_first_parent()?;
+			}
+			match range {
+				Some(range) if range.contains("..") => revwalk.push_range(range)?,
+				Some(range) => {
+					let commit =
+						self.inner.revparse_single(range)?.peel_to_commit()?;
+					revwalk.push(commit.id())?
+				}
+				None => revwalk.push_head()?,
+			}
+			commits.extend(
+				revwalk
+					.filter_map(|id| id.ok())
+					.filter(|id| ids.insert(*id))
+					.filter_map(|id| self.inner.find_commit(id).ok())
+					.filter(|commit| {
+						merge_commits != MergeCommits::Skip ||
+							commit.parent_count() < 2
+					}),
+			);
+		}
+		// Revwalk sorts by the commit date.
+		if ranges.len() > 1 {
+			commits.sort_by_key(|commit| Reverse(sort_by.timestamp(commit)));
 		}
-		let mut commits: Vec<Commit> = revwalk
-			.filter_map(|id| id.ok())
-			.filter_map(|id| self.inner.find_commit(id).ok())
-			.collect();
 		if include_path.is_some() || exclude_path.is_some() {
 			commits.retain(|commit| {
 				if let Ok(prev_commit) = commit.parent(0) {
@@ -87,27 +172,97 @@ impl Repository {
 		Ok(commits)
 	}
 
-	/// Returns the current tag.
+	/// Orders the commits of multiple ranges so that each release consists of
+	/// the commits that are first contained by its tag.
+	///
+	/// The commits are processed in sequence while generating the releases,
+	/// which does not work for the interleaved commits of different branches.
+	/// Thus, the ancestors of each tagged commit (from the oldest one) are
+	/// moved before it and the untagged commits are left at the end.
+	///
+	/// The ancestors are found with a single revision walk per tag that hides
+	/// the previous tags. It is only needed when multiple ranges are given
+	/// since the commits of a single range are already in order.
+	pub fn order_by_releases<'a>(
+		&self,
+		commits: Vec<Commit<'a>>,
+		tags: &IndexMap<String, String>,
+	) -> Result<Vec<Commit<'a>>> {
+		let mut remaining = commits;
+		remaining.reverse();
+		let tagged_ids = remaining
+			.iter()
+			.map(|commit| commit.id())
+			.filter(|id| tags.contains_key(&id.to_string()))
+			.collect::<Vec<Oid>>();
+		let mut ordered = Vec::with_capacity(remaining.len());
+		for (i, tagged_id) in tagged_ids.iter().enumerate() {
+			if !remaining.iter().any(|commit| commit.id() == *tagged_id) {
+				continue;
+			}
+			let mut revwalk = self.inner.revwalk()?;
+			revwalk.push(*tagged_id)?;
+			for previous_id in &tagged_ids[..i] {
+				revwalk.hide(*previous_id)?;
+			}
+			let ancestors =
+				revwalk.filter_map(|id| id.ok()).collect::<HashSet<Oid>>();
+			let (mut release, rest): (Vec<Commit>, Vec<Commit>) = remaining
+				.into_iter()
+				.partition(|commit| ancestors.contains(&commit.id()));
+			remaining = rest;
+			release.sort_by_key(|commit| commit.id() == *tagged_id);
+			ordered.extend(release);
+		}
+		ordered.extend(remaining);
+		ordered.reverse();
+		Ok(ordered)
+	}
+
+	/// Returns the current tag, i.e. the latest tag that is reachable from
+	/// `HEAD`.
 	///
-	/// It is the same as running `git describe --tags`
+	/// It is the same as running `git describe --tags --abbrev=0`
 	pub fn current_tag(&self) -> Option<String> {
 		self.inner
 			.describe(DescribeOptions::new().describe_tags())
 			.ok()
-			.and_then(|describe| describe.format(None).ok())
+			.and_then(|describe| {
+				describe
+					.format(Some(DescribeFormatOptions::new().abbreviated_size(0)))
+					.ok()
+			})
 	}
 
 	/// Parses and returns a commit-tag map.
 	///
-	/// It collects lightweight and annotated tags.
+	/// It collects lightweight and annotated tags which match any of the given
+	/// glob patterns.
 	pub fn tags(
 		&self,
-		pattern: &Option<String>,
+		patterns: &[&str],
 		date_order: bool,
+		sort_by: SortBy,
 	) -> Result<IndexMap<String, String>> {
 		let mut tags: Vec<(Commit, String)> = Vec::new();
-		let tag_names = self.inner.tag_names(pattern.as_deref())?;
-		for name in tag_names.iter().flatten().map(String::from) {
+		let mut tag_names = Vec::new();
+		let patterns = if patterns.is_empty() {
+			vec![None]
+		} else {
+			patterns.iter().map(|v| Some(*v)).collect()
+		};
+		for pattern in patterns {
+			tag_names.extend(
+				self.inner
+					.tag_names(pattern)?
+					.iter()
+					.flatten()
+					.map(String::from),
+			);
+		}
+		tag_names.sort();
+		tag_names.dedup();
+		for name in tag_names {
 			let obj = self.inner.revparse_single(&name)?;
 			if let Ok(commit) = obj.clone().into_commit() {
 				tags.push((commit, name));
@@ -122,7 +277,7 @@ impl Repository {
 			}
 		}
 		if date_order {
-			tags.sort_by(|a, b| a.0.time().seconds().cmp(&b.0.time().seconds()));
+			tags.sort_by_key(|(commit, _)| sort_by.timestamp(commit));
 		}
 		Ok(tags
 			.into_iter()
@@ -130,6 +285,238 @@ impl Repository {
 			.collect())
 	}
 
+	/// Returns the creation time of the given tag in seconds, from epoch.
+	///
+	/// It is the tagger date for annotated tags and the time of the oldest
+	/// reflog entry (if any) for lightweight tags.
+	pub fn tag_timestamp(&self, name: &str) -> Option<i64> {
+		let refname = format!("refs/tags/{name}");
+		if let Some(tagger) = self
+			.inner
+			.revparse_single(&refname)
+			.ok()
+			.and_then(|obj| obj.into_tag().ok())
+			.and_then(|tag| tag.tagger().map(|v| v.when().seconds()))
+		{
+			return Some(tagger);
+		}
+		self.inner.reflog(&refname).ok().and_then(|reflog| {
+			reflog
+				.iter()
+				.next_back()
+				.map(|entry| entry.committer().when().seconds())
+		})
+	}
+
+	/// Returns the armored signature of the given commit (if signed).
+	pub fn commit_signature(&self, commit: &Commit) -> Option<String> {
+		self.inner
+			.extract_signature(&commit.id(), None)
+			.ok()
+			.and_then(|(signature, _)| signature.as_str().map(String::from))
+	}
+
+	/// Returns the armored signature of the given tag.
+	///
+	/// Only the annotated tags can be signed and the signature is appended to
+	/// their messages.
+	pub fn tag_signature(&self, name: &str) -> Option<String> {
+		let tag = self
+			.inner
+			.revparse_single(&format!("refs/tags/{name}"))
+			.ok()?
+			.into_tag()
+			.ok()?;
+		let message = String::from_utf8_lossy(tag.message_bytes()?).to_string();
+		message
+			.find("-----BEGIN ")
+			.map(|index| message[index..].to_string())
+	}
+
+	/// Returns the given tag with its message and tagger.
+	///
+	/// The message and the tagger are only set for the annotated tags and the
+	/// signature of the tag is stripped from its message.
+	pub fn tag(&self, name: &str) -> Tag {
+		let mut result = Tag {
+			name: name.to_string(),
+			..Tag::default()
+		};
+		if let Some(tag) = self
+			.inner
+			.revparse_single(&format!("refs/tags/{name}"))
+			.ok()
+			.and_then(|obj| obj.into_tag().ok())
+		{
+			let message = tag
+				.message_bytes()
+				.map(String::from_utf8_lossy)
+				.unwrap_or_default();
+			let message = match message.find("-----BEGIN ") {
+				Some(index) => &message[..index],
+				None => &message,
+			};
+			result.message = Some(message.trim().to_string());
+			result.tagger = tag.tagger().map(Signature::from);
+		}
+		result
+	}
+
+	/// Returns the IDs of the previous tagged commits on the same release line,
+	/// mapped by the IDs of the tagged commits.
+	///
+	/// The release line of a tag is the first given branch which has the
+	/// tagged commit in its first-parent history.
+	pub fn release_lineage(
+		&self,
+		branches: &[String],
+		tags: &IndexMap<String, String>,
+	) -> Result<HashMap<String, String>> {
+		let mut lineage = HashMap::new();
+		for branch in branches {
+			let mut revwalk = self.inner.revwalk()?;
+			revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+			revwalk
+				.push(self.inner.revparse_single(branch)?.peel_to_commit()?.id())?;
+			revwalk.simplify_first_parent()?;
+			let mut previous: Option<String> = None;
+			for id in revwalk.filter_map(|id| id.ok()).map(|id| id.to_string()) {
+				if !tags.contains_key(&id) {
+					continue;
+				}
+				if let Some(previous) = previous.take() {
+					lineage.entry(id.clone()).or_insert(previous);
+				}
+				previous = Some(id);
+			}
+		}
+		Ok(lineage)
+	}
+
+	/// Returns the cherry-picks of the commits which are released on another
+	/// branch, mapped to the IDs of the original commits.
+	///
+	/// Cherry-picks are detected via the `(cherry picked from commit …)`
+	/// trailers or the patch IDs of the commits.
+	pub fn cherry_picks(
+		&self,
+		commits: &[Commit],
+	) -> Result<HashMap<String, String>> {
+		let mut revwalk = self.inner.revwalk()?;
+		for (_, tag) in self.tags(&[], false, SortBy::CommitDate)? {
+			let commit = self.inner.revparse_single(&tag)?.peel_to_commit()?;
+			revwalk.push(commit.id())?;
+		}
+		for commit in commits {
+			revwalk.hide(commit.id())?;
+		}
+		let released = revwalk.filter_map(|id| id.ok()).collect::<HashSet<Oid>>();
+		let mut patch_ids = None;
+		let mut cherry_picks = HashMap::new();
+		for commit in commits {
+			if let Some(original) = CHERRY_PICK_REGEX
+				.captures(&message_of(commit))
+				.and_then(|captures| Oid::from_str(&captures[1]).ok())
+				.filter(|id| released.contains(id))
+			{
+				cherry_picks.insert(commit.id().to_string(), original.to_string());
+				continue;
+			}
+			let patch_ids = patch_ids.get_or_insert_with(|| {
+				released
+					.iter()
+					.filter_map(|id| self.inner.find_commit(*id).ok())
+					.filter_map(|commit| {
+						self.patch_id(&commit)
+							.map(|patch_id| (patch_id, commit.id()))
+					})
+					.collect::<HashMap<Oid, Oid>>()
+			});
+			if let Some(original) =
+				self.patch_id(commit).and_then(|id| patch_ids.get(&id))
+			{
+				cherry_picks.insert(commit.id().to_string(), original.to_string());
+			}
+		}
+		Ok(cherry_picks)
+	}
+
+	/// Returns the paths of the files that are changed by the given commit.
+	pub fn changed_paths(&self, commit: &Commit) -> Vec<PathBuf> {
+		let parent_tree = commit.parent(0).ok().and_then(|v| v.tree().ok());
+		self.inner
+			.diff_tree_to_tree(
+				parent_tree.as_ref(),
+				commit.tree().ok().as_ref(),
+				None,
+			)
+			.map(|diff| {
+				diff.deltas()
+					.filter_map(|delta| {
+						delta.new_file().path().or_else(|| delta.old_file().path())
+					})
+					.map(PathBuf::from)
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Returns the statistics of the changes between the given revisions.
+	///
+	/// The changes are compared with an empty tree if there is no base
+	/// revision (e.g. for the first release).
+	pub fn diff_stats(&self, from: Option<&str>, to: &str) -> Result<DiffStats> {
+		let tree = |revision: &str| {
+			self.inner
+				.revparse_single(revision)
+				.and_then(|object| object.peel_to_tree())
+		};
+		let old_tree = from.map(tree).transpose()?;
+		let diff = self.inner.diff_tree_to_tree(
+			old_tree.as_ref(),
+			Some(&tree(to)?),
+			None,
+		)?;
+		Ok(diff.stats()?)
+	}
+
+	/// Returns the authors and co-authors (their emails or names) of the
+	/// history until the given revisions (inclusive).
+	pub fn contributors_until(&self, revisions: &[&str]) -> Result<HashSet<String>> {
+		let mut revwalk = self.inner.revwalk()?;
+		for revision in revisions {
+			let commit = self.inner.revparse_single(revision)?.peel_to_commit()?;
+			revwalk.push(commit.id())?;
+		}
+		let mut contributors = HashSet::new();
+		for commit in revwalk
+			.filter_map(|id| id.ok())
+			.filter_map(|id| self.inner.find_commit(id).ok())
+		{
+			let commit = crate::commit::Commit::from(&commit);
+			contributors.extend(
+				commit
+					.signatures()
+					.filter_map(|v| v.key())
+					.map(String::from),
+			);
+		}
+		Ok(contributors)
+	}
+
+	/// Returns the patch ID of the changes introduced by the given commit.
+	fn patch_id(&self, commit: &Commit) -> Option<Oid> {
+		let parent_tree = commit.parent(0).ok().and_then(|v| v.tree().ok());
+		self.inner
+			.diff_tree_to_tree(
+				parent_tree.as_ref(),
+				commit.tree().ok().as_ref(),
+				None,
+			)
+			.and_then(|diff| diff.patchid(None))
+			.ok()
+	}
+
 	pub fn remote_urls(&self) -> Result<Vec<String>> {
 		let mut urls = Vec::new();
 		for remote in self.inner.remotes()?.iter().filter_map(|r| r) {
@@ -145,8 +532,14 @@ impl Repository {
 mod test {
 	use super::*;
 	use crate::commit::Commit as AppCommit;
+	use crate::fixtures::{
+		FixtureCommit,
+		SampleRepository,
+	};
 	use git_conventional::ErrorKind;
 	use std::env;
+	use std::fs;
+	use std::path::Path;
 	use std::process::Command;
 	use std::str;
 
@@ -174,6 +567,370 @@ mod test {
 		.to_string())
 	}
 
+	fn git(dir: &Path, args: &[&str]) -> Result<()> {
+		let output = Command::new("git")
+			.args([
+				"-c",
+				"user.name=git-cliff",
+				"-c",
+				"user.email=git-cliff@localhost",
+			])
+			.args(args)
+			.current_dir(dir)
+			.output()?;
+		if output.status.success() {
+			Ok(())
+		} else {
+			Err(io::Error::new(
+				io::ErrorKind::Other,
+				format!(
+					"git {} exited with {:?}: {}",
+					args.join(" "),
+					output.status,
+					String::from_utf8_lossy(&output.stderr).trim()
+				),
+			)
+			.into())
+		}
+	}
+
+	#[test]
+	fn release_lineage() -> Result<()> {
+		let mut fixture = SampleRepository::new()?;
+		fixture
+			.commit("feat: add xyz")?
+			.tag("v1.0.0")?
+			.branch("1.x")?
+			.commit("feat: add abc")?
+			.tag("v2.0.0")?
+			.commit("fix: fix abc")?
+			.tag("v2.0.1")?
+			.checkout("1.x")?
+			.commit("fix: fix xyz")?
+			.tag("v1.0.1")?;
+		let repository = Repository::init(fixture.path().to_path_buf())?;
+		let tags = repository.tags(&[], false, SortBy::CommitDate)?;
+		let lineage = repository
+			.release_lineage(&[String::from("main"), String::from("1.x")], &tags)?;
+		let tag_ids = tags
+			.iter()
+			.map(|(k, v)| (v.as_str(), k))
+			.collect::<HashMap<&str, &String>>();
+		assert_eq!(3, lineage.len());
+		for (tag, previous) in [
+			("v2.0.0", "v1.0.0"),
+			("v2.0.1", "v2.0.0"),
+			("v1.0.1", "v1.0.0"),
+		] {
+			assert_eq!(Some(tag_ids[previous]), lineage.get(tag_ids[tag]));
+		}
+		Ok(())
+	}
+
+	#[test]
+	fn commits_in_ranges() -> Result<()> {
+		let mut fixture = SampleRepository::new()?;
+		fixture
+			.commit_with(FixtureCommit {
+				message: String::from("feat: add xyz"),
+				author_timestamp: Some(500000000),
+				..FixtureCommit::default()
+			})?
+			.tag("v1.0.0")?
+			.branch("1.x")?;
+		for (branch, message, timestamp, tag) in [
+			("main", "feat: add abc", 1000000000, None),
+			("1.x", "fix: fix xyz", 2000000000, Some("v1.0.1")),
+			("main", "feat: add def", 3000000000, Some("v1.1.0")),
+		] {
+			fixture.checkout(branch)?.commit_with(FixtureCommit {
+				message: message.to_string(),
+				author_timestamp: Some(timestamp),
+				..FixtureCommit::default()
+			})?;
+			if let Some(tag) = tag {
+				fixture.tag(tag)?;
+			}
+		}
+		let repository = Repository::init(fixture.path().to_path_buf())?;
+		let commits = repository.commits_in_ranges(
+			&[String::from("v1.0.0..main"), String::from("1.x")],
+			None,
+			None,
+			SortBy::AuthorDate,
+			MergeCommits::Include,
+		)?;
+		let messages = |commits: &[Commit]| {
+			commits
+				.iter()
+				.map(|commit| {
+					commit.message().unwrap_or_default().trim().to_string()
+				})
+				.collect::<Vec<String>>()
+		};
+		assert_eq!(
+			vec![
+				"feat: add def",
+				"fix: fix xyz",
+				"feat: add abc",
+				"feat: add xyz"
+			],
+			messages(&commits)
+		);
+		let tags = repository.tags(&[], false, SortBy::AuthorDate)?;
+		assert_eq!(
+			vec![
+				"feat: add def",
+				"feat: add abc",
+				"fix: fix xyz",
+				"feat: add xyz"
+			],
+			messages(&repository.order_by_releases(commits, &tags)?)
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn merge_commits() -> Result<()> {
+		let mut fixture = SampleRepository::new()?;
+		fixture
+			.commit("feat: add xyz")?
+			.branch("abc")?
+			.checkout("abc")?
+			.commit("feat: add abc")?
+			.commit("fix: fix abc")?
+			.checkout("main")?
+			.merge("abc", "feat: support abc (#1)")?;
+		let repository = Repository::init(fixture.path().to_path_buf())?;
+		let messages = |merge_commits| -> Result<Vec<String>> {
+			Ok(repository
+				.commits_in_ranges(
+					&[],
+					None,
+					None,
+					SortBy::CommitDate,
+					merge_commits,
+				)?
+				.iter()
+				.map(|commit| {
+					commit.message().unwrap_or_default().trim().to_string()
+				})
+				.collect())
+		};
+		assert_eq!(
+			vec![
+				"feat: support abc (#1)",
+				"fix: fix abc",
+				"feat: add abc",
+				"feat: add xyz"
+			],
+			messages(MergeCommits::Include)?
+		);
+		assert_eq!(
+			vec!["fix: fix abc", "feat: add abc", "feat: add xyz"],
+			messages(MergeCommits::Skip)?
+		);
+		assert_eq!(
+			vec!["feat: support abc (#1)", "feat: add xyz"],
+			messages(MergeCommits::FirstParentOnly)?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn signatures() -> Result<()> {
+		let signature =
+			"-----BEGIN PGP SIGNATURE-----\n\nabc\n-----END PGP SIGNATURE-----\n";
+		let mut fixture = SampleRepository::new()?;
+		fixture
+			.commit("feat: add xyz")?
+			.annotated_tag("v1.0.0", &format!("Release v1.0.0\n{signature}"))?
+			.tag("v1.0.1")?;
+		let repository = Repository::init(fixture.path().to_path_buf())?;
+		let head = repository.inner.head()?.peel_to_commit()?;
+		let content = repository.inner.commit_create_buffer(
+			&head.author(),
+			&head.committer(),
+			"feat: add abc",
+			&head.tree()?,
+			&[&head],
+		)?;
+		let signed = repository.inner.commit_signed(
+			content.as_str().unwrap_or_default(),
+			signature,
+			None,
+		)?;
+		let signed = repository.inner.find_commit(signed)?;
+		assert_eq!(
+			Some(signature.to_string()),
+			repository.commit_signature(&signed)
+		);
+		assert_eq!(None, repository.commit_signature(&head));
+		assert_eq!(
+			Some(signature.to_string()),
+			repository.tag_signature("v1.0.0")
+		);
+		assert_eq!(None, repository.tag_signature("v1.0.1"));
+		let tag = repository.tag("v1.0.0");
+		assert_eq!(Some(String::from("Release v1.0.0")), tag.message);
+		assert_eq!(
+			Some("git-cliff"),
+			tag.tagger.as_ref().and_then(|v| v.name())
+		);
+		assert_eq!(
+			Tag {
+				name: String::from("v1.0.1"),
+				..Tag::default()
+			},
+			repository.tag("v1.0.1")
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn sort_by_author_date() -> Result<()> {
+		let mut fixture = SampleRepository::new()?;
+		for (message, timestamp) in [
+			("feat: add xyz", 3000000000),
+			("feat: add abc", 1000000000),
+			("fix: fix abc", 2000000000),
+		] {
+			fixture.commit_with(FixtureCommit {
+				message: message.to_string(),
+				author_timestamp: Some(timestamp),
+				..FixtureCommit::default()
+			})?;
+		}
+		let repository = Repository::init(fixture.path().to_path_buf())?;
+		let messages = |sort_by| -> Result<Vec<String>> {
+			Ok(repository
+				.commits(None, None, None, sort_by)?
+				.iter()
+				.map(|commit| {
+					commit.message().unwrap_or_default().trim().to_string()
+				})
+				.collect())
+		};
+		assert_eq!(
+			vec!["fix: fix abc", "feat: add abc", "feat: add xyz"],
+			messages(SortBy::CommitDate)?
+		);
+		// The releases are split in the topological order.
+		assert_eq!(
+			vec!["fix: fix abc", "feat: add abc", "feat: add xyz"],
+			messages(SortBy::AuthorDate)?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn contributors_until() -> Result<()> {
+		let mut fixture = SampleRepository::new()?;
+		fixture
+			.commit("feat: add xyz\n\nCo-authored-by: Jane Doe <jane@doe.com>")?
+			.commit_with(FixtureCommit {
+				message: String::from("fix: fix xyz"),
+				author: Some((
+					String::from("John Doe"),
+					String::from("john@doe.com"),
+				)),
+				..FixtureCommit::default()
+			})?
+			.commit("feat: add abc")?;
+		let repository = Repository::init(fixture.path().to_path_buf())?;
+		let commits = repository.commits(None, None, None, SortBy::CommitDate)?;
+		assert_eq!(
+			HashSet::from([
+				String::from("git-cliff@localhost"),
+				String::from("jane@doe.com"),
+				String::from("john@doe.com"),
+			]),
+			repository.contributors_until(&[&commits[1].id().to_string()])?
+		);
+		assert_eq!(
+			HashSet::from([
+				String::from("git-cliff@localhost"),
+				String::from("jane@doe.com"),
+			]),
+			repository.contributors_until(&[&commits[2].id().to_string()])?
+		);
+		assert!(repository.contributors_until(&[])?.is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn changed_paths() -> Result<()> {
+		let mut fixture = SampleRepository::new()?;
+		for files in [
+			vec![(String::from("README.md"), String::from("xyz"))],
+			vec![
+				(String::from("crates/core/lib.rs"), String::from("abc")),
+				(String::from("README.md"), String::from("abc")),
+			],
+		] {
+			fixture.commit_with(FixtureCommit {
+				message: String::from("feat: add xyz"),
+				files,
+				..FixtureCommit::default()
+			})?;
+		}
+		let repository = Repository::init(fixture.path().to_path_buf())?;
+		let commits = repository.commits(None, None, None, SortBy::CommitDate)?;
+		assert_eq!(
+			vec![
+				PathBuf::from("README.md"),
+				PathBuf::from("crates/core/lib.rs")
+			],
+			repository.changed_paths(&commits[0])
+		);
+		assert_eq!(
+			vec![PathBuf::from("README.md")],
+			repository.changed_paths(&commits[1])
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn cherry_picks() -> Result<()> {
+		let dir = env::temp_dir().join("git-cliff-cherry-picks");
+		if dir.exists() {
+			fs::remove_dir_all(&dir)?;
+		}
+		fs::create_dir_all(&dir)?;
+		git(&dir, &["init", "--initial-branch", "main"])?;
+		git(&dir, &[
+			"commit",
+			"--allow-empty",
+			"-m",
+			"feat: initial commit",
+		])?;
+		git(&dir, &["branch", "1.x"])?;
+		for name in ["xyz", "abc"] {
+			fs::write(dir.join(name), name)?;
+			git(&dir, &["add", name])?;
+			git(&dir, &["commit", "-m", &format!("fix: fix {name}")])?;
+		}
+		git(&dir, &["tag", "v2.0.0"])?;
+		git(&dir, &["checkout", "1.x"])?;
+		git(&dir, &["cherry-pick", "-x", "main~1"])?;
+		git(&dir, &["cherry-pick", "main"])?;
+		fs::write(dir.join("xyz"), "zyx")?;
+		git(&dir, &["commit", "-am", "fix: fix zyx"])?;
+		let repository = Repository::init(dir.clone())?;
+		let commits = repository.commits(None, None, None, SortBy::CommitDate)?;
+		let cherry_picks = repository.cherry_picks(&commits)?;
+		assert_eq!(2, cherry_picks.len());
+		for (commit, original) in [(&commits[2], "main~1"), (&commits[1], "main")] {
+			assert_eq!(
+				Some(&repository.inner.revparse_single(original)?.id().to_string()),
+				cherry_picks.get(&commit.id().to_string())
+			);
+		}
+		assert_eq!(None, cherry_picks.get(&commits[0].id().to_string()));
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+
 	#[test]
 	fn git_log() -> Result<()> {
 		let repository = Repository::init(
@@ -182,7 +939,7 @@ mod test {
 				.expect("parent directory not found")
 				.to_path_buf(),
 		)?;
-		let commits = repository.commits(None, None, None)?;
+		let commits = repository.commits(None, None, None, SortBy::CommitDate)?;
 		let last_commit =
 			AppCommit::from(&commits.first().expect("no commits found").clone());
 		assert_eq!(get_last_commit_hash()?, last_commit.id);
@@ -196,8 +953,26 @@ mod test {
 				}
 			}
 		}
-		let tags = repository.tags(&None, false)?;
+		let tags = repository.tags(&[], false, SortBy::CommitDate)?;
 		assert_eq!(&get_last_tag()?, tags.last().expect("no tags found").1);
+		let tagger_date = str::from_utf8(
+			Command::new("git")
+				.args([
+					"tag",
+					"--list",
+					"--format=%(taggerdate:unix)",
+					&get_last_tag()?,
+				])
+				.output()?
+				.stdout
+				.as_ref(),
+		)?
+		.trim()
+		.parse::<i64>()
+		.ok();
+		if tagger_date.is_some() {
+			assert_eq!(tagger_date, repository.tag_timestamp(&get_last_tag()?));
+		}
 		Ok(())
 	}
 }
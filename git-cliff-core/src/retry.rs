@@ -0,0 +1,183 @@
+use crate::config::RetryConfig;
+use crate::metrics::Counter;
+use crate::provider::BoxFuture;
+use lazy_regex::Lazy;
+use reqwest::{
+	RequestBuilder,
+	Response,
+};
+use std::sync::RwLock;
+use std::time::{
+	Duration,
+	SystemTime,
+	UNIX_EPOCH,
+};
+
+/// Maximum number of the attempts of a request by default.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Base of the exponential backoff between the attempts in milliseconds by
+/// default.
+pub const DEFAULT_BACKOFF: u64 = 500;
+
+/// Status codes of the responses that are retried by default.
+pub const DEFAULT_RETRY_ON: [u16; 5] = [429, 500, 502, 503, 504];
+
+/// Retry policy that is applied to all requests.
+static POLICY: Lazy<RwLock<RetryConfig>> =
+	Lazy::new(|| RwLock::new(RetryConfig::default()));
+
+/// Sets the retry policy of the requests.
+pub fn set_policy(config: RetryConfig) {
+	if let Ok(mut policy) = POLICY.write() {
+		*policy = config;
+	}
+}
+
+/// Returns the retry policy of the requests.
+fn policy() -> RetryConfig {
+	POLICY.read().map(|v| v.clone()).unwrap_or_default()
+}
+
+impl RetryConfig {
+	/// Returns the maximum number of the attempts (at least one).
+	pub fn max_attempts(&self) -> u32 {
+		self.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS).max(1)
+	}
+
+	/// Returns the delay before the given retry (starting from 1).
+	pub fn backoff(&self, retry: u32) -> Duration {
+		let base = self.backoff.unwrap_or(DEFAULT_BACKOFF);
+		Duration::from_millis(
+			base.saturating_mul(2_u64.saturating_pow(retry.saturating_sub(1))),
+		)
+	}
+
+	/// Returns whether the response with the given status should be retried.
+	pub fn retries_on(&self, status: u16) -> bool {
+		match &self.retry_on {
+			Some(statuses) => statuses.contains(&status),
+			None => DEFAULT_RETRY_ON.contains(&status),
+		}
+	}
+}
+
+/// Returns a pseudo-random duration up to the given one.
+///
+/// It is added to the delays so that the concurrent requests are not retried
+/// at the same time.
+pub fn jitter(max: Duration) -> Duration {
+	let nanos = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |v| v.subsec_nanos());
+	max.mul_f64(f64::from(nanos % 1000) / 1000.0)
+}
+
+/// Returns whether the request can be retried, i.e. its method is idempotent
+/// (e.g. `GET` but not `POST`) and its body is not streamed.
+pub fn is_retryable(request: &RequestBuilder) -> bool {
+	request
+		.try_clone()
+		.and_then(|request| request.build().ok())
+		.map_or(false, |request| request.method().is_idempotent())
+}
+
+/// Extension for sending the requests with the retry policy.
+pub trait SendWithRetry {
+	/// Sends the request and retries it on the connection errors, timeouts and
+	/// the configured status codes with an exponential backoff.
+	///
+	/// Only the [retryable](is_retryable) requests are retried, the others are
+	/// sent once. The response of the last attempt is returned.
+	fn send_with_retry(self) -> BoxFuture<'static, Response>;
+
+	/// Sends the request like [`send_with_retry`] but retries it regardless of
+	/// its method.
+	///
+	/// It is meant for the requests that are idempotent despite their method,
+	/// e.g. the GraphQL queries.
+	///
+	/// [`send_with_retry`]: SendWithRetry::send_with_retry
+	fn send_with_retry_always(self) -> BoxFuture<'static, Response>;
+}
+
+impl SendWithRetry for RequestBuilder {
+	fn send_with_retry(self) -> BoxFuture<'static, Response> {
+		let retryable = is_retryable(&self);
+		send(self, retryable)
+	}
+
+	fn send_with_retry_always(self) -> BoxFuture<'static, Response> {
+		send(self, true)
+	}
+}
+
+/// Sends the request and retries it with the retry policy if it is retryable.
+fn send(request: RequestBuilder, retryable: bool) -> BoxFuture<'static, Response> {
+	Box::pin(async move {
+		let policy = policy();
+		let max_attempts = if retryable { policy.max_attempts() } else { 1 };
+		let mut attempt = 1;
+		loop {
+			// Requests with streaming bodies cannot be cloned.
+			let next = match request.try_clone() {
+				Some(next) if attempt < max_attempts => next,
+				_ => {
+					Counter::ApiRequests.increment();
+					return Ok(request.send().await?);
+				}
+			};
+			Counter::ApiRequests.increment();
+			match next.send().await {
+				Ok(response) if !policy.retries_on(response.status().as_u16()) => {
+					return Ok(response);
+				}
+				Err(e) if !(e.is_connect() || e.is_timeout()) => {
+					return Err(e.into());
+				}
+				_ => {}
+			}
+			let delay = policy.backoff(attempt);
+			tokio::time::sleep(delay + jitter(delay / 4)).await;
+			attempt += 1;
+		}
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn retry_policy() {
+		let mut config = RetryConfig::default();
+		assert_eq!(DEFAULT_MAX_ATTEMPTS, config.max_attempts());
+		assert_eq!(Duration::from_millis(500), config.backoff(1));
+		assert_eq!(Duration::from_millis(2000), config.backoff(3));
+		assert!(config.retries_on(503));
+		assert!(!config.retries_on(404));
+		config = RetryConfig {
+			max_attempts: Some(0),
+			backoff:      Some(100),
+			retry_on:     Some(vec![404]),
+		};
+		assert_eq!(1, config.max_attempts());
+		assert_eq!(Duration::from_millis(200), config.backoff(2));
+		assert!(config.retries_on(404));
+		assert!(!config.retries_on(503));
+		assert!(jitter(Duration::from_secs(1)) < Duration::from_secs(1));
+	}
+
+	#[test]
+	fn retry_idempotent_methods() {
+		let client = reqwest::Client::new();
+		let url = "https://example.com";
+		assert!(is_retryable(&client.get(url)));
+		assert!(is_retryable(&client.head(url)));
+		assert!(is_retryable(&client.put(url)));
+		assert!(is_retryable(&client.delete(url)));
+		assert!(!is_retryable(&client.post(url)));
+		assert!(!is_retryable(&client.patch(url)));
+	}
+}
@@ -0,0 +1,113 @@
+use crate::command;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+
+/// Regex for extracting the signer from the output of `git verify-commit` and
+/// `git verify-tag` for GPG and SSH signatures.
+static SIGNER_REGEX: Lazy<Regex> = lazy_regex!(
+	r#"Good (?:"[^"]*" )?signature (?:from "(?P<gpg>[^"]+)"|for (?P<ssh>\S+))"#
+);
+
+/// Cryptographic signature of a commit or an annotated tag.
+#[derive(
+	Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct CryptoSignature {
+	/// Format of the signature (`gpg`, `ssh` or `x509`).
+	pub format:   String,
+	/// Whether the signature is verified by the verification command.
+	pub verified: bool,
+	/// Signer that is reported by the verification command.
+	pub signer:   Option<String>,
+}
+
+impl CryptoSignature {
+	/// Constructs a new instance from the armored signature.
+	pub fn new(armored: &str) -> Self {
+		let format = if armored.contains("-----BEGIN SSH SIGNATURE-----") {
+			"ssh"
+		} else if armored.contains("-----BEGIN SIGNED MESSAGE-----") {
+			"x509"
+		} else {
+			"gpg"
+		};
+		Self {
+			format: format.to_string(),
+			..Self::default()
+		}
+	}
+
+	/// Verifies the signature via the given command.
+	///
+	/// The type (`commit` or `tag`) and the name of the signed object are set
+	/// as `SIGNED_TYPE` and `SIGNED_OBJECT` environment variables. The
+	/// signature is verified if the command succeeds and the signer is parsed
+	/// from its output.
+	pub fn verify(&mut self, command: &str, kind: &str, object: &str) {
+		match command::run(command, None, vec![
+			("SIGNED_TYPE", kind),
+			("SIGNED_OBJECT", object),
+		]) {
+			Ok(output) => {
+				self.verified = true;
+				self.signer = SIGNER_REGEX.captures(&output).and_then(|captures| {
+					captures
+						.name("gpg")
+						.or_else(|| captures.name("ssh"))
+						.map(|v| v.as_str().to_string())
+				});
+			}
+			Err(_) => {
+				self.verified = false;
+				self.signer = None;
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn signature_format() {
+		assert_eq!(
+			"gpg",
+			CryptoSignature::new("-----BEGIN PGP SIGNATURE-----\n").format
+		);
+		assert_eq!(
+			"ssh",
+			CryptoSignature::new("-----BEGIN SSH SIGNATURE-----\n").format
+		);
+	}
+
+	#[test]
+	#[cfg(target_family = "unix")]
+	fn verify_signature() {
+		let mut signature = CryptoSignature::new("");
+		signature.verify(
+			"echo \"gpg: Good signature from \\\"John Doe <john@doe.com>\\\" \
+			 [$SIGNED_TYPE]\"",
+			"commit",
+			"abc",
+		);
+		assert!(signature.verified);
+		assert_eq!(
+			Some(String::from("John Doe <john@doe.com>")),
+			signature.signer
+		);
+		signature.verify(
+			"echo 'Good \"git\" signature for john@doe.com with ED25519 key'",
+			"tag",
+			"v1.0.0",
+		);
+		assert_eq!(Some(String::from("john@doe.com")), signature.signer);
+		signature.verify("test \"$SIGNED_OBJECT\" = v1.0.0", "tag", "v0.1.0");
+		assert!(!signature.verified);
+		assert_eq!(None, signature.signer);
+	}
+}
@@ -0,0 +1,76 @@
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+
+/// Regex for matching the links to the patchsets and the threads in the
+/// archives of lists.sr.ht.
+static PATCHSET_REGEX: Lazy<Regex> = lazy_regex!(
+	r"https://lists\.sr\.ht/(?P<list>~[\w.-]+/[\w.-]+)/(?:patches/(?P<id>\d+)|(?P<thread>%3C[^\s/]+%3E|<[^\s/>]+>))"
+);
+
+/// Patchset (or the thread of a patch) on a mailing list of lists.sr.ht.
+#[derive(
+	Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct Patchset {
+	/// Mailing list with owner, e.g. `~user/project-devel`.
+	pub list: String,
+	/// ID of the patchset (if it is linked instead of the thread).
+	pub id:   Option<u64>,
+	/// Link to the patchset or the thread in the archives.
+	pub url:  String,
+}
+
+/// Returns the patchsets and the threads of lists.sr.ht that are referenced
+/// in the given commit message, e.g. by the `Link` trailers of the applied
+/// patches.
+pub fn patchsets(message: &str) -> Vec<Patchset> {
+	let mut patchsets = Vec::<Patchset>::new();
+	for captures in PATCHSET_REGEX.captures_iter(message) {
+		let url = captures[0].replace('<', "%3C").replace('>', "%3E");
+		if patchsets.iter().any(|v| v.url == url) {
+			continue;
+		}
+		patchsets.push(Patchset {
+			list: captures["list"].to_string(),
+			id: captures.name("id").and_then(|v| v.as_str().parse().ok()),
+			url,
+		});
+	}
+	patchsets
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn parse_patchsets() {
+		assert_eq!(
+			vec![
+				Patchset {
+					list: String::from("~user/project-devel"),
+					id:   Some(42),
+					url:  String::from(
+						"https://lists.sr.ht/~user/project-devel/patches/42"
+					),
+				},
+				Patchset {
+					list: String::from("~user/project-devel"),
+					id:   None,
+					url:  String::from(
+						"https://lists.sr.ht/~user/project-devel/%3C123.abc@example.com%3E"
+					),
+				},
+			],
+			patchsets(
+				"fix: fix xyz\n\nSee https://lists.sr.ht/~user/project-devel/patches/42\n\nLink: \
+				 https://lists.sr.ht/~user/project-devel/<123.abc@example.com>\n"
+			)
+		);
+		assert!(patchsets("fix: fix xyz (#42)").is_empty());
+	}
+}
@@ -1,12 +1,41 @@
+use crate::command;
+use crate::commit::Commit;
+use crate::config::{
+	BucketPosition,
+	ChangelogConfig,
+	Layout,
+	TableFormat,
+	TablesConfig,
+	UnscopedConfig,
+};
+use crate::embed::EmbeddedConfig;
 use crate::error::{
 	Error,
 	Result,
 };
+use crate::locale;
 use crate::release::Release;
+use crate::remote::Remote;
+use glob::Pattern;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+};
+use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error as ErrorImpl;
 use std::fmt::Write;
-use std::thread::scope;
+use std::io;
+use std::sync::atomic::{
+	AtomicBool,
+	Ordering,
+};
+use std::sync::{
+	mpsc,
+	Arc,
+};
+use std::thread;
+use std::time::Duration;
 use regex::Regex;
 use tera::{
 	Context as TeraContext,
@@ -14,18 +43,139 @@ use tera::{
 	Tera,
 	Value,
 };
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Regular expression for matching the `commit_line` block of a template.
+static COMMIT_LINE_BLOCK_REGEX: Lazy<Regex> = lazy_regex!(
+	r"(?s)\{%-?\s*block\s+commit_line\s*-?%\}(.*?)\{%-?\s*endblock(?:\s+commit_line)?\s*-?%\}"
+);
+
+/// Regular expression for matching the issue references (e.g. `#123`) that
+/// are not a part of a word, a link or an HTML entity.
+static ISSUE_REFERENCE_REGEX: Lazy<Regex> = lazy_regex!(r"(^|[^\w&/\[])#(\d+)\b");
+
+/// Default timeout of the rendering of a release.
+pub const DEFAULT_RENDER_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Default maximum size of the rendered output of a release in bytes.
+pub const DEFAULT_MAX_OUTPUT_SIZE: usize = 16 * 1024 * 1024;
+
+thread_local! {
+	/// Limits of the rendering on the current thread.
+	///
+	/// They are checked by the functions that create values in the template
+	/// (e.g. `range`) since the values are not written to the output.
+	static RENDER_LIMITS: RefCell<Option<RenderLimits>> = const { RefCell::new(None) };
+}
 
 /// Wrapper for [`Tera`].
 #[derive(Debug)]
 pub struct Template {
-	tera: Tera,
+	tera:            Arc<Tera>,
+	extra_context:   HashMap<String, Value>,
+	timeout:         Duration,
+	max_output_size: usize,
+}
+
+/// Limits of a rendering.
+#[derive(Clone)]
+struct RenderLimits {
+	/// Maximum size of the output in bytes.
+	max_output_size: usize,
+	/// Whether the rendering timed out and should be stopped.
+	cancelled:       Arc<AtomicBool>,
+}
+
+impl RenderLimits {
+	/// Returns an error if the rendering is cancelled.
+	fn check_cancelled(&self) -> TeraResult<()> {
+		if self.cancelled.load(Ordering::Relaxed) {
+			Err(tera::Error::msg("Rendering is cancelled"))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+/// Output of the rendering that is limited to a maximum size.
+struct LimitedOutput {
+	/// Rendered bytes.
+	bytes:    Vec<u8>,
+	/// Limits of the rendering.
+	limits:   RenderLimits,
+	/// Whether the output exceeded the maximum size.
+	exceeded: bool,
+}
+
+impl io::Write for LimitedOutput {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		if self.limits.cancelled.load(Ordering::Relaxed) {
+			return Err(io::Error::new(
+				io::ErrorKind::Interrupted,
+				"rendering is cancelled",
+			));
+		}
+		if self.bytes.len() + buf.len() > self.limits.max_output_size {
+			self.exceeded = true;
+			return Err(io::Error::new(
+				io::ErrorKind::Other,
+				"output exceeds the maximum size",
+			));
+		}
+		self.bytes.extend_from_slice(buf);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
 }
 
 impl Template {
 	/// Constructs a new instance.
+	///
+	/// The embedded templates (e.g. `default`) are registered as well so that
+	/// the template can extend them. Since blocks are not allowed in loops, the
+	/// `commit_line` block of the template replaces the embedded `commit_line`
+	/// template which is included for each commit.
 	pub fn new(template: String) -> Result<Self> {
+		Self::with_partials(template, &HashMap::new())
+	}
+
+	/// Constructs a new instance with the given partial templates.
+	///
+	/// The partials can be included (e.g. `{% include "commit_line" %}`) or
+	/// extended by their names and they replace the embedded templates with
+	/// the same names.
+	pub fn with_partials(
+		template: String,
+		partials: &HashMap<String, String>,
+	) -> Result<Self> {
+		if partials.contains_key("template") {
+			return Err(Error::TemplateParseError(String::from(
+				"Partial template cannot be named `template`",
+			)));
+		}
 		let mut tera = Tera::default();
-		if let Err(e) = tera.add_raw_template("template", &template) {
+		let mut templates = EmbeddedConfig::get_templates()?;
+		templates.retain(|(name, _)| !partials.contains_key(name));
+		templates.extend(
+			partials
+				.iter()
+				.map(|(name, partial)| (name.to_string(), partial.to_string())),
+		);
+		if let Some(commit_line) = COMMIT_LINE_BLOCK_REGEX
+			.captures(&template)
+			.and_then(|captures| captures.get(1))
+		{
+			templates.retain(|(name, _)| name != "commit_line");
+			templates.push((
+				String::from("commit_line"),
+				commit_line.as_str().to_string(),
+			));
+		}
+		templates.push((String::from("template"), template));
+		if let Err(e) = tera.add_raw_templates(templates) {
 			return if let Some(error_source) = e.source() {
 				Err(Error::TemplateParseError(error_source.to_string()))
 			} else {
@@ -33,14 +183,251 @@ impl Template {
 			};
 		}
 		tera.register_filter("upper_first", Self::upper_first_filter);
-		Ok(Self { tera })
+		tera.register_filter("truncate", Self::truncate_filter);
+		tera.register_filter("length", Self::length_filter);
+		tera.register_filter("group_by", Self::group_by_filter);
+		tera.register_filter("group_sort", Self::group_sort_filter);
+		tera.register_filter("strip_prefix", Self::strip_prefix_filter);
+		tera.register_filter("truncate_sha", Self::truncate_sha_filter);
+		tera.register_filter("linkify_issues", Self::linkify_issues_filter);
+		tera.register_filter("unique_authors", Self::unique_authors_filter);
+		tera.register_filter("indent", Self::indent_filter);
+		tera.register_filter("regex_replace", Self::regex_replace_filter);
+		tera.register_filter("external", Self::external_filter(HashMap::new()));
+		tera.register_filter("date", Self::date_filter(None));
+		tera.register_function("get_env", Self::get_env_function(Vec::new()));
+		tera.register_function("range", Self::range_function);
+		Ok(Self {
+			tera:            Arc::new(tera),
+			extra_context:   HashMap::new(),
+			timeout:         DEFAULT_RENDER_TIMEOUT,
+			max_output_size: DEFAULT_MAX_OUTPUT_SIZE,
+		})
 	}
 
+	/// Sets the timeout and the maximum size of the output (in bytes) of the
+	/// rendering so that a pathological template fails instead of hanging.
+	///
+	/// The defaults ([`DEFAULT_RENDER_TIMEOUT`] and
+	/// [`DEFAULT_MAX_OUTPUT_SIZE`]) are used if they are not given.
+	pub fn set_limits(
+		&mut self,
+		timeout: Option<Duration>,
+		max_output_size: Option<usize>,
+	) {
+		self.timeout = timeout.unwrap_or(DEFAULT_RENDER_TIMEOUT);
+		self.max_output_size = max_output_size.unwrap_or(DEFAULT_MAX_OUTPUT_SIZE);
+	}
+
+	/// Sets the glob patterns of the environment variables that are allowed to
+	/// be read via the `get_env` function.
+	pub fn set_allowed_env(&mut self, patterns: &[String]) -> Result<()> {
+		let patterns = patterns
+			.iter()
+			.map(|v| Pattern::new(v))
+			.collect::<std::result::Result<Vec<Pattern>, _>>()?;
+		Arc::make_mut(&mut self.tera)
+			.register_function("get_env", Self::get_env_function(patterns));
+		Ok(())
+	}
+
+	/// Sets the commands of the external filters by their names.
+	pub fn set_external_functions(&mut self, functions: HashMap<String, String>) {
+		Arc::make_mut(&mut self.tera)
+			.register_filter("external", Self::external_filter(functions));
+	}
+
+	/// Sets the default locale of the dates.
+	pub fn set_locale(&mut self, locale: &str) {
+		Arc::make_mut(&mut self.tera)
+			.register_filter("date", Self::date_filter(Some(locale.to_string())));
+	}
+
+	/// Returns a filter for formatting the timestamps as dates.
+	///
+	/// The dates are localized via ICU if a locale is given (or set by the
+	/// configuration) without a `format`. Otherwise, it falls back to the
+	/// built-in filter of Tera.
+	///
+	/// Usage: `timestamp | date(locale="ja", length="long")`
+	fn date_filter(
+		locale: Option<String>,
+	) -> impl Fn(&Value, &HashMap<String, Value>) -> TeraResult<Value> {
+		move |value: &Value, args: &HashMap<String, Value>| {
+			let locale = match args.get("locale") {
+				Some(v) => Some(tera::try_get_value!("date", "locale", String, v)),
+				None => locale.clone(),
+			};
+			if let (Some(locale), false) = (locale, args.contains_key("format")) {
+				let timestamp = tera::try_get_value!("date", "value", i64, value);
+				let length = match args.get("length") {
+					Some(v) => tera::try_get_value!("date", "length", String, v),
+					None => locale::DEFAULT_DATE_LENGTH.to_string(),
+				};
+				return locale::format_date(timestamp, &locale, &length)
+					.map(Value::String)
+					.map_err(|e| tera::Error::msg(e.to_string()));
+			}
+			// The built-in filter is not exposed, so it is applied via a
+			// one-off template.
+			let mut context = TeraContext::new();
+			context.insert("value", value);
+			let mut names = Vec::new();
+			for name in ["format", "timezone"] {
+				if let Some(arg) = args.get(name) {
+					context.insert(name, arg);
+					names.push(format!("{name}={name}"));
+				}
+			}
+			Tera::one_off(
+				&format!("{{{{ value | date({}) }}}}", names.join(", ")),
+				&context,
+				false,
+			)
+			.map(Value::String)
+		}
+	}
+
+	/// Returns a filter for running the value through an external command.
+	///
+	/// The value is written to the stdin of the command (as JSON if it isn't
+	/// a string) and the output is returned without its trailing newline.
+	///
+	/// Usage: `commit.message | external(name="my-script")`
+	fn external_filter(
+		functions: HashMap<String, String>,
+	) -> impl Fn(&Value, &HashMap<String, Value>) -> TeraResult<Value> {
+		move |value: &Value, args: &HashMap<String, Value>| {
+			let name = tera::try_get_value!(
+				"external",
+				"name",
+				String,
+				Self::required_arg("external", args, "name")?
+			);
+			let command = functions.get(&name).ok_or_else(|| {
+				tera::Error::msg(format!(
+					"Function `{name}` is not defined in `template.functions`"
+				))
+			})?;
+			let input = match value {
+				Value::String(v) => v.to_string(),
+				_ => value.to_string(),
+			};
+			let output =
+				command::run(command, Some(input), vec![]).map_err(|e| {
+					tera::Error::msg(format!("Function `{name}` failed: {e}"))
+				})?;
+			Ok(Value::String(
+				output.strip_suffix('\n').unwrap_or(&output).to_string(),
+			))
+		}
+	}
+
+	/// Returns a function for reading the allowed environment variables.
+	///
+	/// Usage: `get_env(name="BUILD_URL", default="")`
+	fn get_env_function(
+		allowed: Vec<Pattern>,
+	) -> impl Fn(&HashMap<String, Value>) -> TeraResult<Value> {
+		move |args: &HashMap<String, Value>| {
+			let name = match args.get("name").and_then(|v| v.as_str()) {
+				Some(name) => name,
+				None => {
+					return Err(tera::Error::msg(
+						"Function `get_env` didn't receive a `name` string argument",
+					));
+				}
+			};
+			if !allowed.iter().any(|pattern| pattern.matches(name)) {
+				return Err(tera::Error::msg(format!(
+					"Environment variable `{name}` is not in `allowed_env`"
+				)));
+			}
+			match (std::env::var(name).ok(), args.get("default")) {
+				(Some(value), _) => Ok(Value::String(value)),
+				(None, Some(default)) => Ok(default.clone()),
+				(None, None) => Err(tera::Error::msg(format!(
+					"Environment variable `{name}` not found"
+				))),
+			}
+		}
+	}
+
+	/// Returns the numbers from `start` (inclusive) to `end` (exclusive) by
+	/// `step_by`.
+	///
+	/// This replaces the built-in `range` function so that the array is
+	/// limited to the maximum output size and a cancelled rendering stops.
+	///
+	/// Usage: `range(start=1, end=10, step_by=2)`
+	fn range_function(args: &HashMap<String, Value>) -> TeraResult<Value> {
+		let number = |name: &str, default: Option<u64>| match args.get(name) {
+			Some(value) => value.as_u64().ok_or_else(|| {
+				tera::Error::msg(format!(
+					"Function `range` received {name}={value} but `{name}` can \
+					 only be a number"
+				))
+			}),
+			None => default.ok_or_else(|| {
+				tera::Error::msg(format!(
+					"Function `range` was called without a `{name}` argument"
+				))
+			}),
+		};
+		let start = number("start", Some(0))?;
+		let end = number("end", None)?;
+		let step_by = number("step_by", Some(1))?;
+		if step_by == 0 {
+			return Err(tera::Error::msg(
+				"Function `range` was called with a `step_by` argument of 0",
+			));
+		}
+		if start > end {
+			return Err(tera::Error::msg(
+				"Function `range` was called with a `start` argument greater than \
+				 the `end` one",
+			));
+		}
+		let len = (end - start + step_by - 1) / step_by;
+		RENDER_LIMITS.with(|limits| match &*limits.borrow() {
+			Some(limits) => {
+				limits.check_cancelled()?;
+				if len > limits.max_output_size as u64 {
+					return Err(tera::Error::msg(format!(
+						"Function `range` exceeds the maximum size of {} elements",
+						limits.max_output_size
+					)));
+				}
+				Ok(())
+			}
+			None => Ok(()),
+		})?;
+		Ok(Value::from(
+			(start..end).step_by(step_by as usize).collect::<Vec<u64>>(),
+		))
+	}
+
+	/// Sets the additional variables of the template context.
+	///
+	/// Variables of the release take precedence over them.
+	pub fn set_extra_context(&mut self, extra_context: HashMap<String, Value>) {
+		self.extra_context = extra_context;
+	}
+
+	/// Makes the first grapheme cluster of a string uppercase.
 	fn upper_first(value: &str) -> String {
-		let mut c = value.chars();
-		match c.next() {
+		let mut graphemes = value.graphemes(true);
+		match graphemes.next() {
 			None => String::new(),
-			Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
+			Some(first) => first.to_uppercase() + graphemes.as_str(),
+		}
+	}
+
+	/// Returns the first `length` grapheme clusters of a string.
+	fn truncate(value: &str, length: usize) -> &str {
+		match value.grapheme_indices(true).nth(length) {
+			Some((index, _)) => &value[..index],
+			None => value,
 		}
 	}
 
@@ -55,58 +442,421 @@ impl Template {
 		Ok(tera::to_value(&s)?)
 	}
 
+	/// Filter for truncating a string to the given number of grapheme
+	/// clusters.
+	///
+	/// Usage: `commit.id | truncate(length=7, end="")`
+	fn truncate_filter(
+		value: &Value,
+		args: &HashMap<String, Value>,
+	) -> TeraResult<Value> {
+		let s = tera::try_get_value!("truncate", "value", String, value);
+		let length = match args.get("length") {
+			Some(v) => tera::try_get_value!("truncate", "length", usize, v),
+			None => 255,
+		};
+		let end = match args.get("end") {
+			Some(v) => tera::try_get_value!("truncate", "end", String, v),
+			None => String::from("…"),
+		};
+		let truncated = Self::truncate(&s, length);
+		if truncated.len() == s.len() {
+			Ok(tera::to_value(&s)?)
+		} else {
+			Ok(tera::to_value(truncated.to_string() + &end)?)
+		}
+	}
+
+	/// Filter for returning the length of an array, an object or a string.
+	///
+	/// The length of a string is the number of its grapheme clusters.
+	fn length_filter(
+		value: &Value,
+		_: &HashMap<String, Value>,
+	) -> TeraResult<Value> {
+		match value {
+			Value::Array(v) => Ok(tera::to_value(v.len())?),
+			Value::Object(v) => Ok(tera::to_value(v.len())?),
+			Value::String(v) => Ok(tera::to_value(v.graphemes(true).count())?),
+			_ => Err(tera::Error::msg(
+				"Filter `length` was used on a value that isn't an array, an \
+				 object, or a string",
+			)),
+		}
+	}
+
+	/// Filter for grouping an array by an arbitrary (dotted) attribute.
+	///
+	/// Unlike the built-in filter of Tera, the items are put into a group for
+	/// each element if the attribute is an array (e.g. `pull_requests`) and the
+	/// items without the attribute are put into the `default` group if given.
+	///
+	/// Usage: `commits | group_by(attribute="author.name", default="other")`
+	fn group_by_filter(
+		value: &Value,
+		args: &HashMap<String, Value>,
+	) -> TeraResult<Value> {
+		let items = tera::try_get_value!("group_by", "value", Vec<Value>, value);
+		let attribute = match args.get("attribute") {
+			Some(v) => tera::try_get_value!("group_by", "attribute", String, v),
+			None => {
+				return Err(tera::Error::msg(
+					"The `group_by` filter has to have an `attribute` argument",
+				));
+			}
+		};
+		let default = match args.get("default") {
+			Some(Value::String(v)) => Some(v.to_string()),
+			Some(Value::Null) | None => None,
+			Some(v) => Some(v.to_string()),
+		};
+		let mut groups = tera::Map::new();
+		for item in items {
+			let keys = match tera::dotted_pointer(&item, &attribute) {
+				Some(Value::Array(values)) => values
+					.iter()
+					.filter(|v| !v.is_null())
+					.map(|v| match v {
+						Value::String(s) => s.to_string(),
+						_ => v.to_string(),
+					})
+					.collect::<Vec<String>>(),
+				Some(Value::String(s)) => vec![s.to_string()],
+				Some(Value::Null) | None => Vec::new(),
+				Some(v) => vec![v.to_string()],
+			};
+			let keys = if keys.is_empty() {
+				default.clone().into_iter().collect()
+			} else {
+				keys
+			};
+			for key in keys {
+				match groups.get_mut(&key) {
+					Some(Value::Array(group)) => group.push(item.clone()),
+					_ => {
+						groups.insert(key, Value::Array(vec![item.clone()]));
+					}
+				}
+			}
+		}
+		Ok(Value::Object(groups))
+	}
+
+	/// Returns the value of a required argument of a filter.
+	fn required_arg<'a>(
+		filter: &str,
+		args: &'a HashMap<String, Value>,
+		name: &str,
+	) -> TeraResult<&'a Value> {
+		args.get(name).ok_or_else(|| {
+			tera::Error::msg(format!(
+				"The `{filter}` filter has to have a `{name}` argument"
+			))
+		})
+	}
+
+	/// Filter for ordering the groups of the `group_by` filter.
+	///
+	/// The groups are returned as an array of objects with `name` and `items`
+	/// fields. The groups in `order` come first in the given order and the rest
+	/// is sorted by name.
+	///
+	/// Usage: `commits | group_by(attribute="group") |
+	/// group_sort(order=["Features"])`
+	fn group_sort_filter(
+		value: &Value,
+		args: &HashMap<String, Value>,
+	) -> TeraResult<Value> {
+		let groups = match value {
+			Value::Object(v) => v.clone(),
+			_ => {
+				return Err(tera::Error::msg(
+					"Filter `group_sort` was used on a value that isn't an object",
+				));
+			}
+		};
+		let order = match args.get("order") {
+			Some(v) => tera::try_get_value!("group_sort", "order", Vec<String>, v),
+			None => Vec::new(),
+		};
+		let mut groups = groups.into_iter().collect::<Vec<(String, Value)>>();
+		groups.sort_by_cached_key(|(name, _)| {
+			(
+				order.iter().position(|v| v == name).unwrap_or(order.len()),
+				name.to_string(),
+			)
+		});
+		Ok(Value::Array(
+			groups
+				.into_iter()
+				.map(|(name, items)| {
+					let mut group = tera::Map::new();
+					group.insert(String::from("name"), Value::String(name));
+					group.insert(String::from("items"), items);
+					Value::Object(group)
+				})
+				.collect(),
+		))
+	}
+
+	/// Filter for removing a prefix from a string if it is present.
+	///
+	/// Usage: `commit.message | strip_prefix(prefix="chore: ")`
+	fn strip_prefix_filter(
+		value: &Value,
+		args: &HashMap<String, Value>,
+	) -> TeraResult<Value> {
+		let s = tera::try_get_value!("strip_prefix", "value", String, value);
+		let prefix = tera::try_get_value!(
+			"strip_prefix",
+			"prefix",
+			String,
+			Self::required_arg("strip_prefix", args, "prefix")?
+		);
+		Ok(Value::String(
+			s.strip_prefix(&prefix).unwrap_or(&s).to_string(),
+		))
+	}
+
+	/// Filter for shortening a commit SHA to the given length (defaults to 7).
+	///
+	/// Usage: `commit.id | truncate_sha(length=8)`
+	fn truncate_sha_filter(
+		value: &Value,
+		args: &HashMap<String, Value>,
+	) -> TeraResult<Value> {
+		let sha = tera::try_get_value!("truncate_sha", "value", String, value);
+		let length = match args.get("length") {
+			Some(v) => tera::try_get_value!("truncate_sha", "length", usize, v),
+			None => 7,
+		};
+		Ok(Value::String(sha.chars().take(length).collect()))
+	}
+
+	/// Filter for converting the issue references (e.g. `#123`) of a string to
+	/// Markdown links to the issues of the given GitHub repository.
+	///
+	/// Usage: `commit.message | linkify_issues(repo="orhun/git-cliff")`
+	fn linkify_issues_filter(
+		value: &Value,
+		args: &HashMap<String, Value>,
+	) -> TeraResult<Value> {
+		let s = tera::try_get_value!("linkify_issues", "value", String, value);
+		let repo = tera::try_get_value!(
+			"linkify_issues",
+			"repo",
+			String,
+			Self::required_arg("linkify_issues", args, "repo")?
+		);
+		let replacement =
+			format!("${{1}}[#${{2}}](https://github.com/{repo}/issues/${{2}})");
+		Ok(Value::String(
+			ISSUE_REFERENCE_REGEX
+				.replace_all(&s, replacement.as_str())
+				.to_string(),
+		))
+	}
+
+	/// Filter for returning the unique author names of an array of commits in
+	/// the order of their first appearance.
+	///
+	/// Usage: `commits | unique_authors | join(sep=", ")`
+	fn unique_authors_filter(
+		value: &Value,
+		_: &HashMap<String, Value>,
+	) -> TeraResult<Value> {
+		let commits =
+			tera::try_get_value!("unique_authors", "value", Vec<Value>, value);
+		let mut authors = Vec::new();
+		for commit in commits {
+			if let Some(Value::String(name)) =
+				tera::dotted_pointer(&commit, "author.name")
+			{
+				if !authors.contains(name) {
+					authors.push(name.to_string());
+				}
+			}
+		}
+		Ok(tera::to_value(authors)?)
+	}
+
+	/// Filter for indenting the lines of a string.
+	///
+	/// The lines are prefixed with `width` spaces or the given `prefix`
+	/// (defaults to 4 spaces). The first line and the blank lines are only
+	/// indented if `first` and `blank` are set respectively.
+	///
+	/// Usage: `commit.body | indent(width=2)`
+	fn indent_filter(
+		value: &Value,
+		args: &HashMap<String, Value>,
+	) -> TeraResult<Value> {
+		let s = tera::try_get_value!("indent", "value", String, value);
+		let prefix = match (args.get("prefix"), args.get("width")) {
+			(Some(v), _) => tera::try_get_value!("indent", "prefix", String, v),
+			(None, Some(v)) => {
+				" ".repeat(tera::try_get_value!("indent", "width", usize, v))
+			}
+			(None, None) => " ".repeat(4),
+		};
+		let first = match args.get("first") {
+			Some(v) => tera::try_get_value!("indent", "first", bool, v),
+			None => false,
+		};
+		let blank = match args.get("blank") {
+			Some(v) => tera::try_get_value!("indent", "blank", bool, v),
+			None => false,
+		};
+		let mut result = String::new();
+		for (i, line) in s.split_inclusive('\n').enumerate() {
+			if (i != 0 || first) && (blank || !line.trim().is_empty()) {
+				result.push_str(&prefix);
+			}
+			result.push_str(line);
+		}
+		Ok(Value::String(result))
+	}
+
+	/// Filter for replacing the matches of a regular expression in a string.
+	///
+	/// The replacement can refer to the capture groups (e.g. `$1`).
+	///
+	/// Usage: `commit.message | regex_replace(pattern="\(#(\d+)\)",
+	/// replacement="(!$1)")`
+	fn regex_replace_filter(
+		value: &Value,
+		args: &HashMap<String, Value>,
+	) -> TeraResult<Value> {
+		let s = tera::try_get_value!("regex_replace", "value", String, value);
+		let pattern = tera::try_get_value!(
+			"regex_replace",
+			"pattern",
+			String,
+			Self::required_arg("regex_replace", args, "pattern")?
+		);
+		let replacement = tera::try_get_value!(
+			"regex_replace",
+			"replacement",
+			String,
+			Self::required_arg("regex_replace", args, "replacement")?
+		);
+		let regex = Regex::new(&pattern).map_err(|e| {
+			tera::Error::msg(format!(
+				"Filter `regex_replace` received an invalid pattern: {e}"
+			))
+		})?;
+		Ok(Value::String(
+			regex.replace_all(&s, replacement.as_str()).to_string(),
+		))
+	}
+
 	/// Renders the template.
+	///
+	/// The rendering fails if it exceeds the timeout or the maximum size of
+	/// the output. The rendering that timed out is cancelled, i.e. it stops
+	/// at the next write to the output or call of `range`.
 	pub fn render(&self, release: &Release) -> Result<String> {
-		let context = TeraContext::from_serialize(release)?;
-		match self.tera.render("template", &context) {
-			Ok(v) => Ok(v),
+		let mut context = TeraContext::new();
+		for (key, value) in &self.extra_context {
+			context.insert(key, value);
+		}
+		context.extend(TeraContext::from_serialize(release)?);
+		let limits = RenderLimits {
+			max_output_size: self.max_output_size,
+			cancelled:       Arc::new(AtomicBool::new(false)),
+		};
+		let cancelled = Arc::clone(&limits.cancelled);
+		let (sender, receiver) = mpsc::channel();
+		let tera = Arc::clone(&self.tera);
+		thread::spawn(move || {
+			let _ = sender.send(Self::render_context(&tera, &context, limits));
+		});
+		match receiver.recv_timeout(self.timeout) {
+			Ok(result) => result,
+			Err(mpsc::RecvTimeoutError::Timeout) => {
+				cancelled.store(true, Ordering::Relaxed);
+				Err(Error::TemplateRenderError(format!(
+					"Rendering timed out after {:?}",
+					self.timeout
+				)))
+			}
+			Err(mpsc::RecvTimeoutError::Disconnected) => Err(
+				Error::TemplateRenderError(String::from("Rendering panicked")),
+			),
+		}
+	}
+
+	/// Renders the template with the given context and limits.
+	fn render_context(
+		tera: &Tera,
+		context: &TeraContext,
+		limits: RenderLimits,
+	) -> Result<String> {
+		RENDER_LIMITS.with(|v| *v.borrow_mut() = Some(limits.clone()));
+		let mut output = LimitedOutput {
+			bytes: Vec::new(),
+			limits,
+			exceeded: false,
+		};
+		let result = tera.render_to("template", context, &mut output);
+		RENDER_LIMITS.with(|v| *v.borrow_mut() = None);
+		if output.exceeded {
+			return Err(Error::TemplateRenderError(format!(
+				"Output exceeds the maximum size of {} bytes",
+				output.limits.max_output_size
+			)));
+		}
+		match result {
+			Ok(()) => {
+				Ok(String::from_utf8(output.bytes).map_err(|e| e.utf8_error())?)
+			}
 			Err(e) => {
-				return if let Some(error_source) = e.source() {
+				if let Some(error_source) = e.source() {
 					Err(Error::TemplateRenderError(error_source.to_string()))
 				} else {
 					Err(Error::TemplateError(e))
-				};
+				}
 			}
 		}
 	}
 
-	/// Renders default template.
-	pub fn render_default(release: &Release, github_repo: Option<String>) -> Result<String> {
-		let repo_owner = &github_repo
-			.clone()
-			.map(|repo| repo.split('/').next().unwrap().to_string());
-		let repo_url = &github_repo.map(|repo| format!("https://github.com/{repo}"));
-		let mut result = String::new();
-		if let Some(version) = &release.version {
-			// ## [0.1.0] - 2222-22-22
-			writeln!(
-				result,
-				"## [{}] - {}\n",
-				version.trim_start_matches('v'),
-				chrono::NaiveDateTime::from_timestamp(release.timestamp, 0)
-					.format("%Y-%m-%d")
-			)
-		} else {
-			writeln!(result, "## [Unreleased]\n")
-		}?;
+	/// Returns the scope of the commit in the default layout.
+	fn commit_scope<'c>(
+		commit: &'c Commit,
+		unscoped: &'c UnscopedConfig,
+	) -> Option<&'c str> {
+		commit
+			.scope
+			.as_deref()
+			.or_else(|| {
+				commit
+					.conv
+					.as_ref()
+					.and_then(|c| c.scope())
+					.map(|s| s.as_str())
+			})
+			.or(commit.default_scope.as_deref())
+			.or(unscoped.merge_into.as_deref())
+	}
 
+	/// Writes the sections of the groups with the scopes nested inside for the
+	/// default (`type-first`) layout of the default template.
+	fn write_type_first(
+		result: &mut String,
+		release: &Release,
+		config: &ChangelogConfig,
+		remote: Option<&Remote>,
+		repo_owner: &Option<String>,
+		full_changes_url: Option<&str>,
+	) -> Result<()> {
+		let unscoped = &config.unscoped.clone().unwrap_or_default();
+		let scope_depth = config.scope_depth.unwrap_or(1).max(1);
 		// Groups { Scopes { Commits[] }, ... }
 		let mut grouped = BTreeMap::new();
 		for commit in &release.commits {
 			// Map only the commit with group, because it follows "conventional commits"
-			let group = commit.group.clone();
-			let conv_group = commit.conv.clone().map(|c| c.type_().to_string());
-			if let Some(group) = group.or(conv_group) {
-				let scope = commit
-					.scope
-					.as_deref()
-					.or_else(||
-						commit.conv
-							.as_ref()
-							.and_then(|c| c.scope())
-							.map(|s| s.as_str())
-					)
-					.or(commit.default_scope.as_deref());
+			for group in commit.groups() {
+				let scope = Self::commit_scope(commit, unscoped);
 				// Group by scope
 				grouped
 					.entry(group)
@@ -123,82 +873,459 @@ impl Template {
 				.trim_start_matches(|c: char| c.is_numeric())
 				.trim_start_matches(". "))?;
 
+			let mut scopes = scopes.into_iter().collect::<Vec<_>>();
+			if unscoped.position == Some(BucketPosition::Last) {
+				scopes.sort_by_key(|(scope, _)| scope.is_none());
+			}
+			if let Some(tables) = config
+				.tables
+				.as_ref()
+				.filter(|tables| tables.contains(&group))
+			{
+				let commits = scopes
+					.into_iter()
+					.flat_map(|(_, commits)| commits)
+					.collect::<Vec<_>>();
+				writeln!(result)?;
+				Self::write_table(result, &commits, tables, unscoped, remote)?;
+				scopes = Vec::new();
+			}
+			let mut previous_path = Vec::new();
 			for (scope, commits) in scopes {
 				// #### - Scope, OtherScope
-				if let Some(scope) = scope {
-					let scope = scope
+				// ##### - Nested scope
+				let path = match scope.or(unscoped.name.as_deref()) {
+					Some(scope) if !scope.contains(',') => {
+						scope.splitn(scope_depth, '/').collect::<Vec<_>>()
+					}
+					Some(scope) => vec![scope],
+					None => Vec::new(),
+				};
+				let common = path
+					.iter()
+					.zip(&previous_path)
+					.take_while(|(a, b)| a == b)
+					.count();
+				let skip = common.min(path.len().saturating_sub(1));
+				for (level, segment) in path.iter().enumerate().skip(skip) {
+					let segment = segment
 						.split(',')
 						.map(|s| Self::upper_first(s.trim()))
 						.collect::<Vec<_>>()
 						.join(", ");
+					let heading = "#".repeat((level + 4).min(6));
+					writeln!(result, "\n{heading} - {segment}\n")?;
+				}
+				previous_path = path;
+				for commit in commits {
+					Self::write_commit(
+						result,
+						commit,
+						remote,
+						repo_owner,
+						config.locale.as_deref(),
+					)?;
+				}
+			}
+
+			// - _…and 12 more_ ([see all changes](link))
+			if let Some(count) = release.omitted_commits.get(group.as_str()) {
+				write!(result, "- _…and {count} more_")?;
+				if let Some(url) = full_changes_url {
+					write!(result, " ([see all changes]({url}))")?;
+				}
+				writeln!(result)?;
+			}
+
+			writeln!(result, "\n---\n")?;
+		}
+		Ok(())
+	}
 
-					writeln!(result, "\n#### - {scope}\n")?;
+	/// Writes the sections of the scopes with the groups nested inside for the
+	/// `scope-first` layout of the default template.
+	///
+	/// The commits without scope are listed under the name of the unscoped
+	/// bucket (`Other` by default).
+	fn write_scope_first(
+		result: &mut String,
+		release: &Release,
+		config: &ChangelogConfig,
+		remote: Option<&Remote>,
+		repo_owner: &Option<String>,
+		full_changes_url: Option<&str>,
+	) -> Result<()> {
+		let unscoped = &config.unscoped.clone().unwrap_or_default();
+		// Scopes { Groups { Commits[] }, ... }
+		let mut scoped = BTreeMap::new();
+		for commit in &release.commits {
+			for group in commit.groups() {
+				scoped
+					.entry(Self::commit_scope(commit, unscoped))
+					.or_insert_with(BTreeMap::new)
+					.entry(group)
+					.or_insert_with(Vec::new)
+					.push(commit);
+			}
+		}
+		let mut scopes = scoped.into_iter().collect::<Vec<_>>();
+		if unscoped.position == Some(BucketPosition::Last) {
+			scopes.sort_by_key(|(scope, _)| scope.is_none());
+		}
+		for (scope, groups) in scopes {
+			// ### - Scope
+			let scope = scope.or(unscoped.name.as_deref()).unwrap_or("Other");
+			let scope = scope
+				.split(',')
+				.map(|s| Self::upper_first(s.trim()))
+				.collect::<Vec<_>>()
+				.join(", ");
+			writeln!(result, "### - {scope}")?;
+			for (group, commits) in groups {
+				// #### Group
+				let tables = config
+					.tables
+					.as_ref()
+					.filter(|tables| tables.contains(&group));
+				let group = group
+					.trim_start_matches(|c: char| c.is_numeric())
+					.trim_start_matches(". ");
+				writeln!(result, "\n#### {group}\n")?;
+				if let Some(tables) = tables {
+					Self::write_table(result, &commits, tables, unscoped, remote)?;
+					continue;
 				}
 				for commit in commits {
-					let authors = commit.github_authors();
-					let prs = commit.pull_requests();
-					let mut message = Self::upper_first(
-						commit.conv
-							.as_ref()
-							.map(|c| c.description())
-							.unwrap_or(&commit.message)
-					);
-
-					if !authors.is_empty() &&
-						// Skip if only owner
-						!(authors.len() == 1 && authors.first().cloned() == repo_owner.clone()) {
-						// Commit message by [@author1](link) and [@author2](link)
-						message = format!(
-							"{} by {}",
-							message,
-							authors.iter()
-								.map(|author| format!("[@{author}](https://github.com/{author})"))
-								.collect::<Vec<String>>()
-								.join(" and ")
-						)
-					}
+					Self::write_commit(
+						result,
+						commit,
+						remote,
+						repo_owner,
+						config.locale.as_deref(),
+					)?;
+				}
+			}
+			writeln!(result, "\n---\n")?;
+		}
 
-					if !prs.is_empty() && repo_url.is_some() {
-						// Commit message.. in [#1](link) and [#2](link)
-						message = format!(
-							"{} in {}",
-							message,
-							prs.iter()
-								.map(|pr| format!("[#{pr}]({}/pull/{pr})", repo_url.as_ref().unwrap()))
-								.collect::<Vec<String>>()
-								.join(" and ")
-						)
-					}
+		// - _…and 12 more_ ([see all changes](link))
+		let omitted = release.omitted_commits.values().sum::<usize>();
+		if omitted > 0 {
+			write!(result, "- _…and {omitted} more_")?;
+			if let Some(url) = full_changes_url {
+				write!(result, " ([see all changes]({url}))")?;
+			}
+			writeln!(result, "\n\n---\n")?;
+		}
+		Ok(())
+	}
 
-					// - [`short_hash`](link) Commit message
-					let short_hash = &commit.id[0..7];
-					if let Some(repo) = &repo_url {
-						writeln!(
-							result,
-							"- [`{short_hash}`]({repo}/commit/{}) {message}",
-							commit.id,
-						)?;
-					} else {
-						writeln!(result, "- `{short_hash}` {message}")?;
+	/// Writes the commits as a table with the type, scope, description, pull
+	/// requests and authors columns in the default template.
+	fn write_table(
+		result: &mut String,
+		commits: &[&Commit],
+		tables: &TablesConfig,
+		unscoped: &UnscopedConfig,
+		remote: Option<&Remote>,
+	) -> Result<()> {
+		let format = tables.format.unwrap_or(TableFormat::Markdown);
+		let link = |text: String, url: Option<String>| match (url, format) {
+			(Some(url), TableFormat::Markdown) => format!("[{text}]({url})"),
+			(Some(url), TableFormat::Html) => {
+				format!("<a href=\"{url}\">{text}</a>")
+			}
+			(None, _) => text,
+		};
+		let escape = |value: &str| match format {
+			TableFormat::Markdown => value.replace('|', "\\|"),
+			TableFormat::Html => value
+				.replace('&', "&amp;")
+				.replace('<', "&lt;")
+				.replace('>', "&gt;"),
+		};
+		let columns = ["Type", "Scope", "Description", "PR", "Author"];
+		match format {
+			TableFormat::Markdown => {
+				writeln!(result, "| {} |", columns.join(" | "))?;
+				writeln!(result, "|{}", " --- |".repeat(columns.len()))?;
+			}
+			TableFormat::Html => {
+				writeln!(result, "<table>\n<thead>\n<tr>")?;
+				for column in columns {
+					writeln!(result, "<th>{column}</th>")?;
+				}
+				writeln!(result, "</tr>\n</thead>\n<tbody>")?;
+			}
+		}
+		for commit in commits {
+			let kind = commit
+				.conv
+				.as_ref()
+				.map(|c| c.type_().to_string())
+				.unwrap_or_default();
+			let scope = Self::commit_scope(commit, unscoped).unwrap_or_default();
+			let description = Self::upper_first(
+				commit
+					.conv
+					.as_ref()
+					.map(|c| c.description())
+					.unwrap_or_else(|| {
+						commit.message.lines().next().unwrap_or_default()
+					}),
+			);
+			let prs = commit
+				.pull_requests()
+				.into_iter()
+				.map(|pr| {
+					link(
+						format!("#{pr}"),
+						remote.map(|remote| remote.pull_request_link(pr)),
+					)
+				})
+				.collect::<Vec<String>>()
+				.join(", ");
+			let mut authors = commit
+				.github_authors()
+				.into_iter()
+				.map(|author| {
+					link(
+						format!("@{author}"),
+						remote.map(|remote| remote.user_link(&author)),
+					)
+				})
+				.collect::<Vec<String>>();
+			if authors.is_empty() {
+				authors.extend(commit.author.name().map(&escape));
+			}
+			let cells = [
+				escape(&kind),
+				escape(scope),
+				escape(&description),
+				prs,
+				authors.join(", "),
+			];
+			match format {
+				TableFormat::Markdown => {
+					writeln!(result, "| {} |", cells.join(" | "))?;
+				}
+				TableFormat::Html => {
+					writeln!(result, "<tr>")?;
+					for cell in cells {
+						writeln!(result, "<td>{cell}</td>")?;
 					}
+					writeln!(result, "</tr>")?;
+				}
+			}
+		}
+		if format == TableFormat::Html {
+			writeln!(result, "</tbody>\n</table>")?;
+		}
+		Ok(())
+	}
 
-					//   　
-					//   > Commit body line1
-					//   > Commit body line2
-					if let Some(Some(body)) = commit.conv.as_ref().map(|c| c.body()) {
-						// Skip Github squash messages
-						let squash_msg_prefix = Regex::new(r"^\*[[:space:]]\w+").unwrap();
-						if !body.is_empty() && !squash_msg_prefix.is_match(body) {
-							writeln!(result, "  　")?;
-							for line in body.lines() {
-								writeln!(result, "  > {}", line)?;
-							}
-						}
-					}
+	/// Joins the given authors, pull requests or patchsets of a commit line,
+	/// e.g. `x and y` (or as a list in the given locale).
+	fn join_list(items: Vec<String>, locale: Option<&str>) -> Result<String> {
+		match locale {
+			Some(locale) => locale::join_list(&items, locale),
+			None => Ok(items.join(" and ")),
+		}
+	}
+
+	/// Writes the line of a commit in the default template.
+	fn write_commit(
+		result: &mut String,
+		commit: &Commit,
+		remote: Option<&Remote>,
+		repo_owner: &Option<String>,
+		locale: Option<&str>,
+	) -> Result<()> {
+		let authors = commit.github_authors();
+		let prs = commit.pull_requests();
+		let mut message = Self::upper_first(
+			commit.conv
+				.as_ref()
+				.map(|c| c.description())
+				.unwrap_or(&commit.message)
+		);
+
+		if !authors.is_empty() &&
+			// Skip if only owner
+			!(authors.len() == 1 && authors.first().cloned() == repo_owner.clone()) {
+			// Commit message by [@author1](link) and [@author2](link)
+			message = format!(
+				"{} by {}",
+				message,
+				Self::join_list(
+					authors
+						.iter()
+						.map(|author| format!(
+							"[@{author}]({})",
+							remote
+								.map(|remote| remote.user_link(author))
+								.unwrap_or_else(|| format!(
+									"https://github.com/{author}"
+								))
+						))
+						.collect(),
+					locale
+				)?
+			)
+		}
+
+		if let (false, Some(remote)) = (prs.is_empty(), remote) {
+			// Commit message.. in [#1](link) and [#2](link)
+			message = format!(
+				"{} in {}",
+				message,
+				Self::join_list(
+					prs.iter()
+						.map(|pr| format!(
+							"[#{pr}]({})",
+							remote.pull_request_link(*pr)
+						))
+						.collect(),
+					locale
+				)?
+			)
+		}
+
+		if !commit.patchsets.is_empty() {
+			// Commit message.. in [~user/list#1](link)
+			message = format!(
+				"{} in {}",
+				message,
+				Self::join_list(
+					commit
+						.patchsets
+						.iter()
+						.map(|patchset| {
+							let label = match patchset.id {
+								Some(id) => format!("{}#{id}", patchset.list),
+								None => patchset.list.clone(),
+							};
+							format!("[{label}]({})", patchset.url)
+						})
+						.collect(),
+					locale
+				)?
+			)
+		}
+
+		// - [`short_hash`](link) Commit message
+		let short_hash = Self::truncate(&commit.id, 7);
+		if let Some(remote) = remote {
+			writeln!(
+				result,
+				"- [`{short_hash}`]({}) {message}",
+				remote.commit_link(&commit.id),
+			)?;
+		} else {
+			writeln!(result, "- `{short_hash}` {message}")?;
+		}
+
+		//   　
+		//   > Commit body line1
+		//   > Commit body line2
+		if let Some(Some(body)) = commit.conv.as_ref().map(|c| c.body()) {
+			// Skip Github squash messages
+			let squash_msg_prefix = Regex::new(r"^\*[[:space:]]\w+").unwrap();
+			if !body.is_empty() && !squash_msg_prefix.is_match(body) {
+				writeln!(result, "  　")?;
+				for line in body.lines() {
+					writeln!(result, "  > {}", line)?;
 				}
 			}
+		}
+		Ok(())
+	}
 
-			writeln!(result, "\n---\n")?;
+	/// Renders default template.
+	pub fn render_default(
+		release: &Release,
+		config: &ChangelogConfig,
+		github_repo: Option<String>,
+		remote: Option<&Remote>,
+	) -> Result<String> {
+		let repo_owner = &github_repo
+			.clone()
+			.map(|repo| repo.split('/').next().unwrap().to_string());
+		// https://github.com/owner/repo/compare/v1.0..v1.2
+		let full_changes_url = remote.map(|remote| {
+			match release.previous.as_ref().and_then(|v| v.version.as_ref()) {
+				Some(prev) => remote.compare_link(
+					prev,
+					release.version.as_deref().unwrap_or("HEAD"),
+				),
+				None => remote.log_link("HEAD"),
+			}
+		});
+		let mut result = String::new();
+		if let Some(version) = &release.version {
+			// ## [0.1.0] - 2222-22-22
+			let date = match &config.locale {
+				Some(locale) => locale::format_date(
+					release.timestamp,
+					locale,
+					locale::DEFAULT_DATE_LENGTH,
+				)?,
+				None => chrono::NaiveDateTime::from_timestamp(release.timestamp, 0)
+					.format("%Y-%m-%d")
+					.to_string(),
+			};
+			writeln!(
+				result,
+				"## [{}] - {}\n",
+				version.trim_start_matches('v'),
+				date
+			)
+		} else {
+			writeln!(result, "## [Unreleased]\n")
+		}?;
+
+		if !release.breaking.is_empty() {
+			// ### ⚠ Breaking Changes
+			writeln!(result, "### ⚠ Breaking Changes\n")?;
+			for change in &release.breaking {
+				let short_hash = Self::truncate(&change.commit_id, 7);
+				let short_hash = match remote {
+					Some(remote) => format!(
+						"[`{short_hash}`]({})",
+						remote.commit_link(&change.commit_id)
+					),
+					None => format!("`{short_hash}`"),
+				};
+				let description = Self::upper_first(&change.description);
+				match &change.scope {
+					Some(scope) => writeln!(
+						result,
+						"- {short_hash} **{scope}**: {description}"
+					)?,
+					None => writeln!(result, "- {short_hash} {description}")?,
+				}
+			}
+			writeln!(result)?;
+		}
+
+		if config.layout == Some(Layout::ScopeFirst) {
+			Self::write_scope_first(
+				&mut result,
+				release,
+				config,
+				remote,
+				repo_owner,
+				full_changes_url.as_deref(),
+			)?;
+		} else {
+			Self::write_type_first(
+				&mut result,
+				release,
+				config,
+				remote,
+				repo_owner,
+				full_changes_url.as_deref(),
+			)?;
 		}
 
 		// _This changelog is generated by [git-cliff](https://github.com/orhun/git-cliff),_
@@ -207,16 +1334,9 @@ impl Template {
 			result,
 			"_This changelog is generated by [pretty-changelog](https://github.com/chachako/pretty-changelog)"
 		)?;
-		if let Some(repo) = repo_url {
+		if let Some(url) = &full_changes_url {
 			writeln!(result, ",_")?;
-			write!(result, "_**You can also view the full changes: {repo}/")?;
-			if let Some(Some(prev)) = release.previous.as_ref().map(|v| v.version.clone()) {
-				let current_version = release.version.as_deref().unwrap_or("HEAD");
-				write!(result, "compare/{prev}..{current_version}")?;
-			} else {
-				write!(result, "commits/HEAD")?;
-			}
-			writeln!(result, "**_")?;
+			writeln!(result, "_**You can also view the full changes: {url}**_")?;
 		}
 		writeln!(result, "\n---\n")?;
 
@@ -227,7 +1347,9 @@ impl Template {
 #[cfg(test)]
 mod test {
 	use super::*;
-	use crate::commit::Commit;
+	use crate::intern::Symbol;
+	use crate::release::ReleaseStatistics;
+	use std::env;
 
 	#[test]
 	fn render_template() -> Result<()> {
@@ -249,8 +1371,8 @@ mod test {
 		- Fix abc
 		"#,
 			template.render(&Release {
-				version:   Some(String::from("1.0")),
-				commits:   vec![
+				version:         Some(String::from("1.0")),
+				commits:         vec![
 					Commit::new(
 						String::from("123123"),
 						String::from("feat(xyz): add xyz"),
@@ -263,11 +1385,602 @@ mod test {
 				.into_iter()
 				.filter_map(|c| c.into_conventional().ok())
 				.collect(),
-				commit_id: None,
-				timestamp: 0,
-				previous:  None,
+				commit_id:       None,
+				timestamp:       0,
+				previous:        None,
+				channel:         None,
+				omitted_commits: Default::default(),
+				summary:         None,
+				artifacts:       Vec::new(),
+				remote:          None,
+				migration_notes: Vec::new(),
+				api_changes:     None,
+				tag_signed:      false,
+				tag_signature:   None,
+				metrics:         Default::default(),
+				edited:          false,
+				statistics:      ReleaseStatistics::default(),
+				contributors:    Vec::new(),
+				breaking:        Vec::new(),
+				tag:             None,
+			})?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn render_limits() -> Result<()> {
+		let mut template = Template::new(String::from(
+			"{% for i in range(end=100) %}{{ version }}{% endfor %}",
+		))?;
+		let release = Release {
+			version: Some(String::from("v1.0.0")),
+			..Release::default()
+		};
+		assert_eq!(600, template.render(&release)?.len());
+		template.set_limits(None, Some(100));
+		assert!(template.render(&release).is_err());
+		let mut template = Template::new(String::from(
+			"{% for i in range(end=1000) %}{{ range(end=1000) | length }}{% endfor \
+			 %}",
+		))?;
+		template.set_limits(None, Some(100));
+		assert!(template.render(&release).is_err());
+		template.set_limits(Some(Duration::from_nanos(1)), None);
+		assert!(template.render(&release).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn render_extra_context() -> Result<()> {
+		let mut template = Template::new(String::from(
+			"{{ product }} {{ version }} (build {{ build.number }})",
+		))?;
+		template.set_extra_context(HashMap::from([
+			(String::from("product"), Value::from("xyz")),
+			(String::from("version"), Value::from("abc")),
+			(String::from("build"), serde_json::json!({ "number": 42 })),
+		]));
+		assert_eq!(
+			"xyz 1.0 (build 42)",
+			template.render(&Release {
+				version: Some(String::from("1.0")),
+				..Release::default()
 			})?
 		);
 		Ok(())
 	}
+
+	#[test]
+	fn render_get_env() -> Result<()> {
+		env::set_var("GIT_CLIFF_TEST_BUILD_URL", "https://ci/42");
+		let mut template = Template::new(String::from(
+			"{{ get_env(name=\"GIT_CLIFF_TEST_BUILD_URL\") }} {{ \
+			 get_env(name=\"GIT_CLIFF_TEST_MISSING\", default=\"-\") }}",
+		))?;
+		assert!(template.render(&Release::default()).is_err());
+		template.set_allowed_env(&[String::from("GIT_CLIFF_TEST_*")])?;
+		assert_eq!("https://ci/42 -", template.render(&Release::default())?);
+		Ok(())
+	}
+
+	#[test]
+	fn render_date_filter() -> Result<()> {
+		let release = Release {
+			timestamp: 1136214245,
+			..Release::default()
+		};
+		let mut template = Template::new(String::from(
+			"{{ timestamp | date }} {{ timestamp | date(format=\"%d/%m/%Y %H:%M\", \
+			 timezone=\"Asia/Tokyo\") }}",
+		))?;
+		assert_eq!("2006-01-02 03/01/2006 00:04", template.render(&release)?);
+		template.set_locale("ja");
+		#[cfg(feature = "icu")]
+		assert_eq!("2006/01/02 03/01/2006 00:04", template.render(&release)?);
+		#[cfg(not(feature = "icu"))]
+		assert!(template.render(&release).is_err());
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "icu")]
+	fn render_localized_default() -> Result<()> {
+		let template =
+			Template::new(String::from("{{ timestamp | date(length=\"long\") }}"))?;
+		let mut release = Release {
+			version: Some(String::from("v1.0.0")),
+			timestamp: 1136214245,
+			commits: vec![Commit::new(
+				String::from("1231231abc"),
+				String::from("feat: add xyz"),
+			)
+			.into_conventional()?],
+			..Release::default()
+		};
+		release.commits[0].group = Some(Symbol::from("Features"));
+		release.commits[0].github_author = Some(String::from("x"));
+		release.commits[0].github_coauthors =
+			Some(vec![String::from("y"), String::from("z")]);
+		assert_eq!("2006-01-02", template.render(&release)?);
+		let mut template = template;
+		template.set_locale("en");
+		assert_eq!("January 2, 2006", template.render(&release)?);
+		let render = Template::render_default(
+			&release,
+			&ChangelogConfig {
+				locale: Some(String::from("ja")),
+				..ChangelogConfig::default()
+			},
+			None,
+			None,
+		)?;
+		assert!(render.starts_with("## [1.0.0] - 2006/01/02\n"));
+		assert!(render.contains(
+			"by [@x](https://github.com/x)、[@y](https://github.com/y)、[@z](https://github.com/z)"
+		));
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(target_family = "unix")]
+	fn render_external_filter() -> Result<()> {
+		let mut template = Template::new(String::from(
+			"{{ version | external(name=\"reverse\") }}",
+		))?;
+		let release = Release {
+			version: Some(String::from("v1.0.0")),
+			..Release::default()
+		};
+		assert!(template.render(&release).is_err());
+		template.set_external_functions(HashMap::from([(
+			String::from("reverse"),
+			String::from("rev"),
+		)]));
+		assert_eq!("0.0.1v", template.render(&release)?);
+		Ok(())
+	}
+
+	#[test]
+	fn render_partials() -> Result<()> {
+		let mut commits = vec![
+			Commit::new(String::from("123123"), String::from("feat: add xyz")),
+			Commit::new(String::from("124124"), String::from("fix: fix abc")),
+		]
+		.into_iter()
+		.filter_map(|c| c.into_conventional().ok())
+		.collect::<Vec<Commit>>();
+		commits[0].group = Some(Symbol::from("Features"));
+		let release = Release {
+			commits,
+			..Release::default()
+		};
+		let partials = HashMap::from([
+			(
+				String::from("commit_line"),
+				String::from("* {{ commit.message }}\n"),
+			),
+			(
+				String::from("heading"),
+				String::from("## {{ version | default(value=\"Unreleased\") }}\n"),
+			),
+		]);
+		assert_eq!(
+			"## Unreleased\n* add xyz\n* fix abc\n",
+			Template::with_partials(
+				String::from(
+					"{% include \"heading\" %}{% for commit in commits %}{% \
+					 include \"commit_line\" %}{% endfor %}",
+				),
+				&partials,
+			)?
+			.render(&release)?
+		);
+		assert!(Template::with_partials(
+			String::new(),
+			&HashMap::from([(String::from("template"), String::new())]),
+		)
+		.is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn render_extended_template() -> Result<()> {
+		let release = Release {
+			version: Some(String::from("v1.0.0")),
+			commits: vec![
+				Commit::new(String::from("123123"), String::from("feat: add xyz")),
+				Commit::new(String::from("124124"), String::from("fix!: fix abc")),
+				Commit::new(String::from("125125"), String::from("fix: fix xyz")),
+			]
+			.into_iter()
+			.filter_map(|c| c.into_conventional().ok())
+			.collect(),
+			timestamp: 0,
+			..Release::default()
+		};
+		assert_eq!(
+			"## [1.0.0] - 1970-01-01\n\n### Feat\n- Add xyz\n\n### Fix\n- \
+			 [**breaking**] Fix abc\n- Fix xyz\n\n",
+			Template::new(String::from(r#"{% extends "default" %}"#))?
+				.render(&release)?
+		);
+		let template = Template::new(String::from(
+			r#"{% extends "default" %}
+			{%- block header %}# {{ version }}{% endblock header -%}
+			{%- block commit_line %}* {{ commit.message }} ({{ commit.id }}){% endblock commit_line -%}
+			{%- block footer %}---{% endblock footer -%}"#,
+		))?;
+		assert_eq!(
+			"# v1.0.0\n\n### Feat\n* add xyz (123123)\n\n### Fix\n* fix abc \
+			 (124124)\n* fix xyz (125125)\n---\n",
+			template.render(&release)?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn render_grapheme_filters() -> Result<()> {
+		let release = Release {
+			version: Some(String::from("e\u{301}\u{1F468}\u{200D}\u{1F467}x")),
+			..Release::default()
+		};
+		assert_eq!(
+			"E\u{301}\u{1F468}\u{200D}\u{1F467}x 3 \
+			 e\u{301}\u{1F468}\u{200D}\u{1F467}… \
+			 e\u{301}\u{1F468}\u{200D}\u{1F467}x",
+			Template::new(String::from(
+				"{{ version | upper_first }} {{ version | length }} {{ version | \
+				 truncate(length=2) }} {{ version | truncate(length=3) }}",
+			))?
+			.render(&release)?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn render_group_by() -> Result<()> {
+		let mut commits = vec![
+			Commit::new(String::from("123123"), String::from("feat(app): add xyz")),
+			Commit::new(String::from("124124"), String::from("fix: fix abc")),
+			Commit::new(String::from("125125"), String::from("fix(app): fix xyz")),
+		]
+		.into_iter()
+		.filter_map(|c| c.into_conventional().ok())
+		.collect::<Vec<Commit>>();
+		commits[0].parents = vec![String::from("111"), String::from("222")];
+		commits[2].parents = vec![String::from("222")];
+		let release = Release {
+			commits,
+			..Release::default()
+		};
+		assert_eq!(
+			"app: 123123 125125 \nother: 124124 \n",
+			Template::new(String::from(
+				"{% for scope, commits in commits | group_by(attribute=\"scope\", \
+				 default=\"other\") %}{{ scope }}: {% for commit in commits %}{{ \
+				 commit.id }} {% endfor %}\n{% endfor %}",
+			))?
+			.render(&release)?
+		);
+		assert_eq!(
+			"111: 123123 \n222: 123123 125125 \n",
+			Template::new(String::from(
+				"{% for parent, commits in commits | \
+				 group_by(attribute=\"parents\") %}{{ parent }}: {% for commit in \
+				 commits %}{{ commit.id }} {% endfor %}\n{% endfor %}",
+			))?
+			.render(&release)?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn render_transformation_filters() -> Result<()> {
+		let mut commits = vec![
+			Commit::new(
+				String::from("a1b2c3d4e5f6"),
+				String::from("feat: add xyz (#12)"),
+			),
+			Commit::new(String::from("b1b2c3d4e5f6"), String::from("fix: fix abc")),
+			Commit::new(String::from("c1b2c3d4e5f6"), String::from("feat: add abc")),
+		]
+		.into_iter()
+		.filter_map(|c| c.into_conventional().ok())
+		.collect::<Vec<Commit>>();
+		commits[0].group = Some(Symbol::from("Features"));
+		commits[1].group = Some(Symbol::from("Bug Fixes"));
+		commits[2].group = Some(Symbol::from("Features"));
+		let release = Release {
+			commits,
+			version: Some(String::from("v1.0.0")),
+			..Release::default()
+		};
+		assert_eq!(
+			"1.0.0 a1b2c3d a1b2 [#12](https://github.com/o/r/issues/12) &#12; \
+			 (!12)\nFeatures: 2 Bug Fixes: 1 \n  a\n\n  b",
+			Template::new(String::from(
+				"{{ version | strip_prefix(prefix=\"v\") }} {{ commits.0.id | \
+				 truncate_sha }} {{ commits.0.id | truncate_sha(length=4) }} {{ \
+				 \"#12\" | linkify_issues(repo=\"o/r\") }} {{ \"&#12;\" | \
+				 linkify_issues(repo=\"o/r\") }} {{ \"(#12)\" | \
+				 regex_replace(pattern=\"\\(#(\\d+)\\)\", replacement=\"(!$1)\") \
+				 }}\n{% for group in commits | group_by(attribute=\"group\") | \
+				 group_sort(order=[\"Features\"]) %}{{ group.name }}: {{ \
+				 group.items | length }} {% endfor %}\n{{ \"a\n\nb\" | \
+				 indent(width=2, first=true) }}",
+			))?
+			.render(&release)?
+		);
+		let mut template = Template::new(String::from(
+			"{{ authors | unique_authors | join(sep=\", \") }}",
+		))?;
+		template.set_extra_context(HashMap::from([(
+			String::from("authors"),
+			serde_json::json!([
+				{ "author": { "name": "a" } },
+				{ "author": { "name": "b" } },
+				{ "author": { "name": "a" } },
+				{ "author": { "name": null } },
+			]),
+		)]));
+		assert_eq!("a, b", template.render(&release)?);
+		Ok(())
+	}
+
+	#[test]
+	fn render_unscoped_bucket() -> Result<()> {
+		let release = Release {
+			version: None,
+			commits: vec![
+				Commit::new(String::from("1231231"), String::from("feat: add xyz")),
+				Commit::new(
+					String::from("1241241"),
+					String::from("feat(app): add abc"),
+				),
+			]
+			.into_iter()
+			.filter_map(|c| c.into_conventional().ok())
+			.collect(),
+			..Release::default()
+		};
+		let render = |unscoped| {
+			Template::render_default(
+				&release,
+				&ChangelogConfig {
+					unscoped: Some(unscoped),
+					..ChangelogConfig::default()
+				},
+				None,
+				None,
+			)
+		};
+		assert!(render(UnscopedConfig::default())?.contains(
+			"### feat\n- `1231231` Add xyz\n\n#### - App\n\n- `1241241` Add abc"
+		));
+		assert!(render(UnscopedConfig {
+			name:       Some(String::from("general")),
+			position:   Some(BucketPosition::Last),
+			merge_into: None,
+		})?
+		.contains(
+			"#### - App\n\n- `1241241` Add abc\n\n#### - General\n\n- `1231231` \
+			 Add xyz"
+		));
+		assert!(render(UnscopedConfig {
+			name:       None,
+			position:   None,
+			merge_into: Some(String::from("app")),
+		})?
+		.contains("#### - App\n\n- `1231231` Add xyz\n- `1241241` Add abc"));
+		Ok(())
+	}
+
+	#[test]
+	fn render_default_nested_scopes() -> Result<()> {
+		let release = Release {
+			commits: vec![
+				"feat(ui/button/icon): add icons",
+				"feat(ui): add theme",
+				"feat(ui/input): add validation",
+				"feat(ui/button): add sizes",
+				"feat(api): add endpoint",
+			]
+			.into_iter()
+			.enumerate()
+			.map(|(i, message)| {
+				Commit::new(format!("{i}000000"), String::from(message))
+					.into_conventional()
+			})
+			.collect::<Result<Vec<Commit>>>()?,
+			..Release::default()
+		};
+		let config = ChangelogConfig {
+			scope_depth: Some(2),
+			..ChangelogConfig::default()
+		};
+		let render = Template::render_default(&release, &config, None, None)?;
+		let headings = render
+			.lines()
+			.filter(|line| line.starts_with('#') || line.starts_with("- "))
+			.collect::<Vec<&str>>();
+		assert_eq!(
+			vec![
+				"## [Unreleased]",
+				"### feat",
+				"#### - Api",
+				"- `4000000` Add endpoint",
+				"#### - Ui",
+				"- `1000000` Add theme",
+				"##### - Button",
+				"- `3000000` Add sizes",
+				"##### - Button/icon",
+				"- `0000000` Add icons",
+				"##### - Input",
+				"- `2000000` Add validation",
+			],
+			headings
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn render_default_scope_first() -> Result<()> {
+		let release = Release {
+			commits: vec![
+				"feat(ui): add theme",
+				"fix(api): fix endpoint",
+				"fix: fix typo",
+				"feat(api): add endpoint",
+			]
+			.into_iter()
+			.enumerate()
+			.map(|(i, message)| {
+				Commit::new(format!("{i}000000"), String::from(message))
+					.into_conventional()
+			})
+			.collect::<Result<Vec<Commit>>>()?,
+			..Release::default()
+		};
+		let config = ChangelogConfig {
+			layout: Some(Layout::ScopeFirst),
+			unscoped: Some(UnscopedConfig {
+				position: Some(BucketPosition::Last),
+				..UnscopedConfig::default()
+			}),
+			..ChangelogConfig::default()
+		};
+		let render = Template::render_default(&release, &config, None, None)?;
+		let headings = render
+			.lines()
+			.filter(|line| line.starts_with('#') || line.starts_with("- "))
+			.collect::<Vec<&str>>();
+		assert_eq!(
+			vec![
+				"## [Unreleased]",
+				"### - Api",
+				"#### feat",
+				"- `3000000` Add endpoint",
+				"#### fix",
+				"- `1000000` Fix endpoint",
+				"### - Ui",
+				"#### feat",
+				"- `0000000` Add theme",
+				"### - Other",
+				"#### fix",
+				"- `2000000` Fix typo",
+			],
+			headings
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn render_default_tables() -> Result<()> {
+		let mut commits = vec![
+			"feat(api): add `a|b` endpoint",
+			"fix: fix <typo>",
+			"feat(ui): add theme",
+		]
+		.into_iter()
+		.enumerate()
+		.map(|(i, message)| {
+			Commit::new(format!("{i}000000"), String::from(message))
+				.into_conventional()
+		})
+		.collect::<Result<Vec<Commit>>>()?;
+		commits[0].github_author = Some(String::from("orhun"));
+		commits[0].pull_requests = Some(vec![42]);
+		let release = Release {
+			commits,
+			..Release::default()
+		};
+		let mut config = ChangelogConfig {
+			tables: Some(TablesConfig {
+				groups: Some(vec![String::from("feat")]),
+				format: None,
+			}),
+			..ChangelogConfig::default()
+		};
+		let render = Template::render_default(&release, &config, None, None)?;
+		assert!(render.contains(
+			"### feat\n\n| Type | Scope | Description | PR | Author |\n| --- | --- \
+			 | --- | --- | --- |\n| feat | api | Add `a\\|b` endpoint | #42 | \
+			 @orhun |\n| feat | ui | Add theme |  |  |\n"
+		));
+		assert!(render.contains("### fix\n- `1000000` Fix <typo>\n"));
+		config.tables = Some(TablesConfig {
+			groups: None,
+			format: Some(TableFormat::Html),
+		});
+		let render = Template::render_default(&release, &config, None, None)?;
+		assert!(render.contains(
+			"<tr>\n<td>fix</td>\n<td></td>\n<td>Fix \
+			 &lt;typo&gt;</td>\n<td></td>\n<td></td>\n</tr>\n</tbody>\n</table>\n"
+		));
+		Ok(())
+	}
+
+	#[test]
+	fn render_default_remote_links() -> Result<()> {
+		let release = Release {
+			version: Some(String::from("v1.1.0")),
+			commits: vec![Commit::new(
+				String::from("1231231abc"),
+				String::from("feat: add xyz"),
+			)]
+			.into_iter()
+			.filter_map(|c| c.into_conventional().ok())
+			.collect(),
+			previous: Some(Box::new(Release {
+				version: Some(String::from("v1.0.0")),
+				..Release::default()
+			})),
+			..Release::default()
+		};
+		let remote = Remote::new(&Default::default(), &[String::from(
+			"git@git.example.com:owner/repo.git",
+		)]);
+		let render = Template::render_default(
+			&release,
+			&ChangelogConfig::default(),
+			None,
+			remote.as_ref(),
+		)?;
+		assert!(render.contains(
+			"- [`1231231`](https://git.example.com/owner/repo/commit/1231231abc) \
+			 Add xyz"
+		));
+		assert!(render.contains(
+			"https://git.example.com/owner/repo/compare/v1.0.0...v1.1.0"
+		));
+		let mut release = release;
+		release.set_breaking_changes();
+		assert!(release.breaking.is_empty());
+		release.commits[0] = Commit::new(
+			String::from("1231231abc"),
+			String::from("feat(api)!: add xyz"),
+		)
+		.into_conventional()?;
+		release.set_breaking_changes();
+		release.commits[0].patchsets = crate::sourcehut::patchsets(
+			"Link: https://lists.sr.ht/~owner/repo-devel/patches/42",
+		);
+		let render = Template::render_default(
+			&release,
+			&ChangelogConfig::default(),
+			None,
+			remote.as_ref(),
+		)?;
+		assert!(render.contains(
+			"Add xyz in \
+			 [~owner/repo-devel#42](https://lists.sr.ht/~owner/repo-devel/patches/42)"
+		));
+		assert!(render.contains(
+			"### ⚠ Breaking Changes\n\n- \
+			 [`1231231`](https://git.example.com/owner/repo/commit/1231231abc) \
+			 **api**: Add xyz\n\n"
+		));
+		Ok(())
+	}
 }
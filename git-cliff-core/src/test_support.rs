@@ -0,0 +1,92 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+pub use crate::fixtures::SampleRepository;
+
+/// Environment variable for updating the golden files instead of comparing
+/// them.
+const UPDATE_GOLDEN_ENV: &str = "GIT_CLIFF_UPDATE_GOLDEN";
+
+/// Asserts that the output matches the content of the golden file.
+///
+/// If the `GIT_CLIFF_UPDATE_GOLDEN` environment variable is set, the golden
+/// file is (re)written with the output instead.
+///
+/// # Panics
+///
+/// Panics if the output does not match or the golden file cannot be read.
+pub fn assert_golden<P: AsRef<Path>>(path: P, output: &str) {
+	let path = path.as_ref();
+	if env::var_os(UPDATE_GOLDEN_ENV).is_some() {
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent).expect("cannot create the golden directory");
+		}
+		fs::write(path, output).expect("cannot write the golden file");
+		return;
+	}
+	let expected = fs::read_to_string(path).unwrap_or_else(|e| {
+		panic!(
+			"cannot read the golden file {path:?}: {e} (set {UPDATE_GOLDEN_ENV} to \
+			 create it)"
+		)
+	});
+	if expected != output {
+		let line = expected
+			.lines()
+			.zip(output.lines())
+			.position(|(a, b)| a != b)
+			.unwrap_or_else(|| expected.lines().count().min(output.lines().count()));
+		panic!(
+			"output does not match the golden file {path:?} at line {} (set \
+			 {UPDATE_GOLDEN_ENV} to update it)\n--- expected\n{expected}\n+++ \
+			 actual\n{output}",
+			line + 1
+		);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::error::Result;
+	use crate::fixtures::{
+		COMMIT_INTERVAL,
+		START_TIMESTAMP,
+	};
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn sample_repository() -> Result<()> {
+		let mut repository = SampleRepository::new()?;
+		repository
+			.commit("feat: add xyz")?
+			.tag("v1.0.0")?
+			.commit("fix: fix xyz")?
+			.commit("feat: add abc")?
+			.tag("v1.1.0")?
+			.commit("docs: update readme")?;
+		let releases = repository.releases()?;
+		assert_eq!(
+			vec![None, Some("v1.1.0"), Some("v1.0.0")],
+			releases
+				.iter()
+				.map(|v| v.version.as_deref())
+				.collect::<Vec<Option<&str>>>()
+		);
+		assert_eq!(
+			Some("v1.0.0"),
+			releases[1]
+				.previous
+				.as_ref()
+				.and_then(|v| v.version.as_deref())
+		);
+		assert_eq!(START_TIMESTAMP + 2 * COMMIT_INTERVAL, releases[1].timestamp);
+
+		let output = "# Changelog\n";
+		let golden = repository.path().join("CHANGELOG.md");
+		fs::write(&golden, output)?;
+		assert_golden(&golden, output);
+		Ok(())
+	}
+}
@@ -0,0 +1,529 @@
+use crate::cache::{
+	Cache,
+	MemoryCache,
+	DEFAULT_TTL,
+};
+use crate::config::{
+	JiraConfig,
+	LinearConfig,
+	ShortcutConfig,
+	TrackersConfig,
+};
+use crate::error::Result;
+use crate::retry::SendWithRetry;
+use lazy_regex::{
+	lazy_regex,
+	Lazy,
+	Regex,
+};
+use regex::Captures;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// Regular expression for matching the Jira ticket keys.
+static JIRA_KEY_REGEX: Lazy<Regex> = lazy_regex!(r#"\b([A-Z][A-Z0-9]+-[0-9]+)\b"#);
+
+/// Regular expression for matching the Linear ticket identifiers.
+static LINEAR_KEY_REGEX: Lazy<Regex> = lazy_regex!(r#"\b([A-Z][A-Z0-9]+-[0-9]+)\b"#);
+
+/// Regular expression for matching the Shortcut story IDs.
+static SHORTCUT_KEY_REGEX: Lazy<Regex> = lazy_regex!(r#"(?i)\bsc-([0-9]+)\b"#);
+
+/// GraphQL endpoint of the Linear API.
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+/// Base URL of the Shortcut API.
+const SHORTCUT_API_URL: &str = "https://api.app.shortcut.com/api/v3";
+
+/// Namespace of the tickets in the cache.
+const CACHE_NAMESPACE: &str = "tickets";
+
+/// GraphQL query for fetching a Linear issue by its identifier.
+const LINEAR_ISSUE_QUERY: &str =
+	"query($id: String!) { issue(id: $id) { title state { name } } }";
+
+/// Ticket of an issue tracker that is referenced by a commit.
+#[derive(
+	Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct Ticket {
+	/// Name of the issue tracker, e.g. `jira`.
+	///
+	/// It is not set for the tickets of the issue parsers without a tracker.
+	pub tracker: Option<String>,
+	/// Key of the ticket, e.g. `PROJ-123`.
+	pub key:     String,
+	/// URL of the ticket.
+	pub url:     String,
+	/// Title of the ticket, if fetched.
+	pub title:   Option<String>,
+	/// State of the ticket, if fetched.
+	pub state:   Option<String>,
+	/// Type of the ticket (e.g. `Bug`), if fetched.
+	#[serde(rename = "type")]
+	pub kind:    Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct JiraIssue {
+	fields: JiraFields,
+}
+
+#[derive(Deserialize, Debug)]
+struct JiraFields {
+	summary:   Option<String>,
+	status:    Option<JiraStatus>,
+	issuetype: Option<JiraIssueType>,
+}
+
+#[derive(Deserialize, Debug)]
+struct JiraStatus {
+	name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct JiraIssueType {
+	name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct LinearResponse {
+	data: Option<LinearData>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LinearData {
+	issue: Option<LinearIssue>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LinearIssue {
+	title: String,
+	state: Option<LinearState>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LinearState {
+	name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ShortcutStory {
+	name:       String,
+	started:    bool,
+	completed:  bool,
+	story_type: Option<String>,
+}
+
+impl ShortcutStory {
+	/// Returns the state of the story.
+	fn state(&self) -> &'static str {
+		if self.completed {
+			"completed"
+		} else if self.started {
+			"started"
+		} else {
+			"unstarted"
+		}
+	}
+}
+
+/// Configured issue tracker.
+#[derive(Clone, Copy, Debug)]
+enum Tracker<'a> {
+	/// Jira.
+	Jira(&'a JiraConfig),
+	/// Linear.
+	Linear(&'a LinearConfig),
+	/// Shortcut.
+	Shortcut(&'a ShortcutConfig),
+}
+
+impl<'a> Tracker<'a> {
+	/// Returns the configured trackers.
+	fn all(config: &'a TrackersConfig) -> Vec<Self> {
+		let mut trackers = Vec::new();
+		if let Some(jira) = &config.jira {
+			trackers.push(Self::Jira(jira));
+		}
+		if let Some(linear) = &config.linear {
+			trackers.push(Self::Linear(linear));
+		}
+		if let Some(shortcut) = &config.shortcut {
+			trackers.push(Self::Shortcut(shortcut));
+		}
+		trackers
+	}
+
+	/// Returns the name of the tracker.
+	fn name(&self) -> &'static str {
+		match self {
+			Self::Jira(_) => "jira",
+			Self::Linear(_) => "linear",
+			Self::Shortcut(_) => "shortcut",
+		}
+	}
+
+	/// Returns the instance of the tracker, i.e. the URL of Jira or the
+	/// workspace of Linear and Shortcut.
+	fn instance(&self) -> &'a str {
+		match self {
+			Self::Jira(config) => config.url.trim_end_matches('/'),
+			Self::Linear(config) => &config.workspace,
+			Self::Shortcut(config) => &config.workspace,
+		}
+	}
+
+	/// Returns the regex for matching the ticket keys.
+	fn pattern(&self) -> &'a Regex {
+		match self {
+			Self::Jira(config) => config.pattern.as_ref().unwrap_or(&JIRA_KEY_REGEX),
+			Self::Linear(config) => {
+				config.pattern.as_ref().unwrap_or(&LINEAR_KEY_REGEX)
+			}
+			Self::Shortcut(config) => {
+				config.pattern.as_ref().unwrap_or(&SHORTCUT_KEY_REGEX)
+			}
+		}
+	}
+}
+
+/// Resolver of the tickets that are referenced in the commit messages.
+///
+/// Tickets are cached so that they are fetched only once. Only the fetched
+/// tickets (i.e. with a title) are cached and they expire after the TTL.
+#[derive(Debug)]
+pub struct TicketResolver {
+	cache: Arc<dyn Cache>,
+	ttl:   u64,
+}
+
+impl Default for TicketResolver {
+	fn default() -> Self {
+		Self::new(Arc::new(MemoryCache::default()), None)
+	}
+}
+
+impl TicketResolver {
+	/// Constructs a new instance that caches the tickets in the given cache.
+	///
+	/// The cached tickets expire after the given number of seconds (a week by
+	/// default).
+	pub fn new(cache: Arc<dyn Cache>, ttl: Option<u64>) -> Self {
+		Self {
+			cache,
+			ttl: ttl.unwrap_or(DEFAULT_TTL),
+		}
+	}
+
+	/// Returns the tickets that are referenced in the given commit message.
+	pub async fn resolve(
+		&mut self,
+		config: &TrackersConfig,
+		message: &str,
+	) -> Result<Vec<Ticket>> {
+		let mut tickets = Vec::new();
+		for tracker in Tracker::all(config) {
+			for (key, _) in keys(tracker.pattern(), message) {
+				tickets.push(self.cached_ticket(tracker, key).await?);
+			}
+		}
+		Ok(tickets)
+	}
+
+	/// Returns the ticket with the given key of the tracker with the given
+	/// name (if configured).
+	///
+	/// The key is in the format of the tracker, e.g. the numeric ID of the
+	/// Shortcut stories.
+	pub async fn resolve_key(
+		&mut self,
+		config: &TrackersConfig,
+		tracker: &str,
+		key: &str,
+	) -> Result<Option<Ticket>> {
+		match Tracker::all(config)
+			.into_iter()
+			.find(|v| v.name() == tracker)
+		{
+			Some(tracker) => Ok(Some(self.cached_ticket(tracker, key).await?)),
+			None => Ok(None),
+		}
+	}
+
+	/// Returns the ticket of the given tracker from the cache or fetches it.
+	async fn cached_ticket(
+		&mut self,
+		tracker: Tracker<'_>,
+		key: &str,
+	) -> Result<Ticket> {
+		let cache_key = format!("{}/{}/{key}", tracker.name(), tracker.instance());
+		if let Some(ticket) =
+			self.cache
+				.get_fresh_json(CACHE_NAMESPACE, &cache_key, self.ttl)?
+		{
+			return Ok(ticket);
+		}
+		let ticket = Self::ticket(tracker, key).await?;
+		if ticket.title.is_some() {
+			self.cache
+				.set_fresh_json(CACHE_NAMESPACE, &cache_key, &ticket)?;
+		}
+		Ok(ticket)
+	}
+
+	/// Returns the ticket of the given tracker.
+	async fn ticket(tracker: Tracker<'_>, key: &str) -> Result<Ticket> {
+		match tracker {
+			Tracker::Jira(config) => Self::jira_ticket(config, key).await,
+			Tracker::Linear(config) => Self::linear_ticket(config, key).await,
+			Tracker::Shortcut(config) => Self::shortcut_ticket(config, key).await,
+		}
+	}
+
+	/// Returns the Jira ticket and fetches its summary and status if enabled.
+	async fn jira_ticket(config: &JiraConfig, key: &str) -> Result<Ticket> {
+		let url = config.url.trim_end_matches('/');
+		let mut ticket = Ticket {
+			tracker: Some(String::from("jira")),
+			key:     key.to_string(),
+			url:     format!("{url}/browse/{key}"),
+			title:   None,
+			state:   None,
+			kind:    None,
+		};
+		if config.fetch.unwrap_or(false) {
+			let mut request = reqwest::Client::new()
+				.get(format!(
+					"{url}/rest/api/2/issue/{key}?fields=summary,status,issuetype"
+				))
+				.header("User-Agent", "git-cliff");
+			request = match (&config.user, &config.token) {
+				(Some(user), token) => request.basic_auth(user, token.as_ref()),
+				(None, Some(token)) => request.bearer_auth(token),
+				(None, None) => request,
+			};
+			let response = request.send_with_retry().await?;
+			if response.status() != StatusCode::NOT_FOUND {
+				let issue = response.error_for_status()?.json::<JiraIssue>().await?;
+				ticket.title = issue.fields.summary;
+				ticket.state = issue.fields.status.map(|status| status.name);
+				ticket.kind = issue.fields.issuetype.map(|kind| kind.name);
+			}
+		}
+		Ok(ticket)
+	}
+
+	/// Returns the Linear ticket and fetches its title and state if a token is
+	/// set.
+	async fn linear_ticket(config: &LinearConfig, key: &str) -> Result<Ticket> {
+		let mut ticket = Ticket {
+			tracker: Some(String::from("linear")),
+			key:     key.to_string(),
+			url:     format!("https://linear.app/{}/issue/{key}", config.workspace),
+			title:   None,
+			state:   None,
+			kind:    None,
+		};
+		if let Some(token) = &config.token {
+			let response = reqwest::Client::new()
+				.post(LINEAR_API_URL)
+				.header("User-Agent", "git-cliff")
+				.header("Authorization", token)
+				.json(&serde_json::json!({
+					"query": LINEAR_ISSUE_QUERY,
+					"variables": { "id": key },
+				}))
+				.send_with_retry_always()
+				.await?
+				.error_for_status()?
+				.json::<LinearResponse>()
+				.await?;
+			if let Some(issue) = response.data.and_then(|data| data.issue) {
+				ticket.title = Some(issue.title);
+				ticket.state = issue.state.map(|state| state.name);
+			}
+		}
+		Ok(ticket)
+	}
+
+	/// Returns the Shortcut story and fetches its name and state if a token is
+	/// set.
+	///
+	/// The key is the numeric ID of the story.
+	async fn shortcut_ticket(config: &ShortcutConfig, key: &str) -> Result<Ticket> {
+		let mut ticket = Ticket {
+			tracker: Some(String::from("shortcut")),
+			key:     format!("sc-{key}"),
+			url:     format!(
+				"https://app.shortcut.com/{}/story/{key}",
+				config.workspace
+			),
+			title:   None,
+			state:   None,
+			kind:    None,
+		};
+		if let Some(token) = &config.token {
+			let response = reqwest::Client::new()
+				.get(format!("{SHORTCUT_API_URL}/stories/{key}"))
+				.header("User-Agent", "git-cliff")
+				.header("Shortcut-Token", token)
+				.send_with_retry()
+				.await?;
+			if response.status() != StatusCode::NOT_FOUND {
+				let story =
+					response.error_for_status()?.json::<ShortcutStory>().await?;
+				ticket.state = Some(story.state().to_string());
+				ticket.title = Some(story.name);
+				ticket.kind = story.story_type;
+			}
+		}
+		Ok(ticket)
+	}
+}
+
+/// Returns the unique ticket keys in the message with their captures.
+///
+/// The key is the first capture group of the regex (if any) or the whole
+/// match.
+pub(crate) fn keys<'h>(
+	regex: &Regex,
+	message: &'h str,
+) -> Vec<(&'h str, Captures<'h>)> {
+	let mut keys: Vec<(&str, Captures)> = Vec::new();
+	for captures in regex.captures_iter(message) {
+		if let Some(key) = captures.get(1).or_else(|| captures.get(0)) {
+			if !keys.iter().any(|(v, _)| *v == key.as_str()) {
+				keys.push((key.as_str(), captures));
+			}
+		}
+	}
+	keys
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+
+	#[tokio::test]
+	async fn resolve_jira_tickets() -> Result<()> {
+		let config = TrackersConfig {
+			jira:     Some(JiraConfig {
+				url:     String::from("https://example.atlassian.net/"),
+				pattern: None,
+				fetch:   None,
+				user:    None,
+				token:   None,
+			}),
+			linear:   None,
+			shortcut: None,
+		};
+		let tickets = TicketResolver::default()
+			.resolve(&config, "feat: add xyz (PROJ-123)\n\nRefs PROJ-123, OPS-7")
+			.await?;
+		assert_eq!(
+			vec![
+				("PROJ-123", "https://example.atlassian.net/browse/PROJ-123"),
+				("OPS-7", "https://example.atlassian.net/browse/OPS-7"),
+			],
+			tickets
+				.iter()
+				.map(|v| (v.key.as_str(), v.url.as_str()))
+				.collect::<Vec<(&str, &str)>>()
+		);
+		let mut resolver = TicketResolver::default();
+		assert_eq!(
+			Some(String::from("https://example.atlassian.net/browse/OPS-7")),
+			resolver
+				.resolve_key(&config, "jira", "OPS-7")
+				.await?
+				.map(|v| v.url)
+		);
+		assert_eq!(
+			None,
+			resolver.resolve_key(&config, "linear", "OPS-7").await?
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn cache_tickets_per_instance() -> Result<()> {
+		let cache: Arc<dyn Cache> = Arc::new(MemoryCache::default());
+		cache.set_fresh_json(
+			CACHE_NAMESPACE,
+			"jira/https://a.atlassian.net/PROJ-1",
+			&Ticket {
+				tracker: Some(String::from("jira")),
+				key: String::from("PROJ-1"),
+				url: String::from("https://a.atlassian.net/browse/PROJ-1"),
+				title: Some(String::from("Cached")),
+				..Ticket::default()
+			},
+		)?;
+		let mut resolver = TicketResolver::new(cache.clone(), None);
+		for (url, title) in [
+			("https://a.atlassian.net/", Some(String::from("Cached"))),
+			("https://b.atlassian.net", None),
+		] {
+			let config = TrackersConfig {
+				jira:     Some(JiraConfig {
+					url:     url.to_string(),
+					pattern: None,
+					fetch:   None,
+					user:    None,
+					token:   None,
+				}),
+				linear:   None,
+				shortcut: None,
+			};
+			let ticket = resolver.resolve_key(&config, "jira", "PROJ-1").await?;
+			assert_eq!(title, ticket.and_then(|v| v.title));
+		}
+		assert_eq!(
+			None,
+			cache.get(CACHE_NAMESPACE, "jira/https://b.atlassian.net/PROJ-1")?
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn resolve_linear_and_shortcut_tickets() -> Result<()> {
+		let config = TrackersConfig {
+			jira:     None,
+			linear:   Some(LinearConfig {
+				workspace: String::from("acme"),
+				pattern:   None,
+				token:     None,
+			}),
+			shortcut: Some(ShortcutConfig {
+				workspace: String::from("acme"),
+				pattern:   None,
+				token:     None,
+			}),
+		};
+		let tickets = TicketResolver::default()
+			.resolve(&config, "fix: handle xyz (ENG-42)\n\nCloses sc-7, sc-7")
+			.await?;
+		assert_eq!(
+			vec![
+				(
+					Some("linear"),
+					"ENG-42",
+					"https://linear.app/acme/issue/ENG-42"
+				),
+				(
+					Some("shortcut"),
+					"sc-7",
+					"https://app.shortcut.com/acme/story/7"
+				),
+			],
+			tickets
+				.iter()
+				.map(|v| (v.tracker.as_deref(), v.key.as_str(), v.url.as_str()))
+				.collect::<Vec<(Option<&str>, &str, &str)>>()
+		);
+		Ok(())
+	}
+}
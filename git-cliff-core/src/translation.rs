@@ -0,0 +1,92 @@
+use crate::error::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Output of a command that is run for a commit message.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct Entry {
+	/// Command that produced the output.
+	command: String,
+	/// Commit message that is passed to the command.
+	input:   String,
+	/// Output of the command.
+	output:  String,
+}
+
+/// Translation memory for the commit preprocessor commands.
+///
+/// Stores the outputs of [`replace_command`]s keyed by the commit SHA so that
+/// unchanged commits are not passed to the command again.
+///
+/// [`replace_command`]: crate::config::CommitPreprocessor::replace_command
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TranslationMemory {
+	entries: BTreeMap<String, Vec<Entry>>,
+}
+
+impl TranslationMemory {
+	/// Loads the translation memory from the given file.
+	///
+	/// Returns an empty memory if the file does not exist.
+	pub fn load(path: &Path) -> Result<Self> {
+		if !path.exists() {
+			return Ok(Self::default());
+		}
+		Ok(Self {
+			entries: serde_json::from_str(&fs::read_to_string(path)?)?,
+		})
+	}
+
+	/// Saves the translation memory to the given file.
+	pub fn save(&self, path: &Path) -> Result<()> {
+		fs::write(path, serde_json::to_string_pretty(&self.entries)?)?;
+		Ok(())
+	}
+
+	/// Returns the memorized output of the command for the given commit.
+	pub fn get(&self, id: &str, command: &str, input: &str) -> Option<&str> {
+		self.entries.get(id).and_then(|entries| {
+			entries
+				.iter()
+				.find(|entry| entry.command == command && entry.input == input)
+				.map(|entry| entry.output.as_str())
+		})
+	}
+
+	/// Memorizes the output of the command for the given commit.
+	pub fn insert(&mut self, id: &str, command: &str, input: &str, output: &str) {
+		let entries = self.entries.entry(id.to_string()).or_default();
+		entries.retain(|entry| !(entry.command == command && entry.input == input));
+		entries.push(Entry {
+			command: command.to_string(),
+			input:   input.to_string(),
+			output:  output.to_string(),
+		});
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use pretty_assertions::assert_eq;
+	use std::env;
+	#[test]
+	fn translation_memory() -> Result<()> {
+		let path = env::temp_dir().join("git-cliff-translation-memory.json");
+		let mut memory = TranslationMemory::load(&path)?;
+		assert_eq!(None, memory.get("123123", "tr a-z A-Z", "feat: add xyz"));
+		memory.insert("123123", "tr a-z A-Z", "feat: add xyz", "FEAT: ADD XYZ");
+		memory.insert("123123", "tr a-z A-Z", "feat: add xyz", "FEAT: ADD XYZ");
+		memory.save(&path)?;
+		let memory = TranslationMemory::load(&path)?;
+		assert_eq!(
+			Some("FEAT: ADD XYZ"),
+			memory.get("123123", "tr a-z A-Z", "feat: add xyz")
+		);
+		assert_eq!(None, memory.get("123123", "tr a-z A-Z", "fix: fix abc"));
+		assert_eq!(1, memory.entries["123123"].len());
+		fs::remove_file(path)?;
+		Ok(())
+	}
+}
@@ -7,7 +7,7 @@ use git_cliff_core::config::{
 	LinkParser,
 };
 use git_cliff_core::error::Result;
-use git_cliff_core::release::*;
+use git_cliff_core::fixtures::SampleRepository;
 use git_cliff_core::template::Template;
 use pretty_assertions::assert_eq;
 use regex::Regex;
@@ -16,8 +16,8 @@ use std::fmt::Write;
 #[test]
 fn generate_changelog() -> Result<()> {
 	let changelog_config = ChangelogConfig {
-		header: Some(String::from("this is a changelog")),
-		body:   Some(String::from(
+		header:   Some(String::from("this is a changelog")),
+		body:     Some(String::from(
 			r#"
 ## Release {{ version }}
 {% for group, commits in commits | group_by(attribute="group") %}
@@ -34,52 +34,111 @@ fn generate_changelog() -> Result<()> {
 {% endfor -%}
 {% endfor %}"#,
 		)),
-		footer: Some(String::from("eoc - end of changelog")),
-		trim:   None,
+		footer:   Some(String::from("eoc - end of changelog")),
+		trim:     None,
+		pdf:      None,
+		unscoped: None,
+
+		max_entries_per_group:    None,
+		entry_priority:           None,
+		extra_context:            None,
+		allowed_env:              None,
+		previous_summary:         None,
+		render_always_unreleased: None,
+		artifacts:                None,
+		line_ending:              None,
+		scope_depth:              None,
+		context_fields:           None,
+		redact_emails:            None,
+		email_redaction:          None,
+		email_salt:               None,
+		fragments:                None,
+		header_path:              None,
+		body_path:                None,
+		footer_path:              None,
+		header_sha256:            None,
+		body_sha256:              None,
+		footer_sha256:            None,
+		layout:                   None,
+		tables:                   None,
+		format:                   None,
+		migration_markers:        None,
+		deprecations:             None,
+		api_diff:                 None,
+		metric_commands:          None,
+		detect_edits:             None,
+		locale:                   None,
 	};
 	let git_config = GitConfig {
-		conventional_commits:     Some(true),
-		filter_unconventional:    Some(true),
-		split_commits:            Some(false),
-		commit_preprocessors:     Some(vec![CommitPreprocessor {
+		conventional_commits:       Some(true),
+		filter_unconventional:      Some(true),
+		split_commits:              Some(false),
+		emojis:                     None,
+		commit_preprocessors:       Some(vec![CommitPreprocessor {
 			pattern:         Regex::new(r#"\(fixes (#[1-9]+)\)"#).unwrap(),
 			replace:         Some(String::from("[closes Issue${1}]")),
 			replace_command: None,
 		}]),
-		commit_parsers:           Some(vec![
+		commit_parsers:             Some(vec![
 			CommitParser {
 				message:       Regex::new("^feat").ok(),
 				body:          None,
+				breaking:      None,
+				merge:         None,
 				group:         Some(String::from("shiny features")),
 				default_scope: None,
 				scope:         None,
 				skip:          None,
+				priority:      None,
+				append:        None,
 			},
 			CommitParser {
 				message:       Regex::new("^fix").ok(),
 				body:          None,
+				breaking:      None,
+				merge:         None,
 				group:         Some(String::from("fix bugs")),
 				default_scope: None,
 				scope:         None,
 				skip:          None,
+				priority:      None,
+				append:        None,
 			},
 			CommitParser {
 				message:       Regex::new("^test").ok(),
 				body:          None,
+				breaking:      None,
+				merge:         None,
 				group:         None,
 				default_scope: None,
 				scope:         Some(String::from("tests")),
 				skip:          None,
+				priority:      None,
+				append:        None,
 			},
 		]),
-		protect_breaking_commits: None,
-		filter_commits:           Some(true),
-		tag_pattern:              None,
-		skip_tags:                None,
-		ignore_tags:              None,
-		date_order:               None,
-		sort_commits:             None,
-		link_parsers:             Some(vec![
+		parser_match:               None,
+		protect_breaking_commits:   None,
+		protect_referenced_commits: None,
+		fixups:                     None,
+		filter_reverted:            None,
+		filter_commits:             Some(true),
+		entry_normalizer:           None,
+		tag_pattern:                None,
+		skip_tags:                  None,
+		skip_tags_mode:             None,
+		ignore_tags:                None,
+		date_order:                 None,
+		tag_date:                   None,
+		signatures:                 None,
+		cherry_picks:               None,
+		release_lines:              None,
+		merge_commits:              None,
+		diff_stats:                 None,
+		sort_by:                    None,
+		sort_commits:               None,
+		issue_parsers:              None,
+		link_parsers:               Some(vec![
 			LinkParser {
 				pattern: Regex::new("#(\\d+)").unwrap(),
 				href:    String::from("https://github.com/$1"),
@@ -91,80 +150,50 @@ fn generate_changelog() -> Result<()> {
 				text:    Some(String::from("$1")),
 			},
 		]),
-		limit_commits:            None,
+		field_parsers:              None,
+		limit_commits:              None,
+		scope_from_paths:           None,
+		translation_memory:         None,
 	};
 
-	let releases = vec![
-		Release {
-			version:   Some(String::from("v2.0.0")),
-			commits:   vec![
-				Commit::new(
-					String::from("000abc"),
-					String::from("Add unconventional commit"),
-				),
-				Commit::new(String::from("abc123"), String::from("feat: add xyz")),
-				Commit::new(String::from("abc124"), String::from("feat: add zyx")),
-				Commit::new(
-					String::from("abc124"),
-					String::from(
-						"feat(random-scope): add random feature\n\nThis is related to https://github.com/NixOS/nixpkgs/issues/136814\n\nCloses #123",
-					),
-				),
-				Commit::new(String::from("def789"), String::from("invalid commit")),
-				Commit::new(
-					String::from("def789"),
-					String::from("feat(big-feature)!: this is a breaking change"),
-				),
-				Commit::new(String::from("qwerty"), String::from("fix: fix abc")),
-				Commit::new(
-					String::from("qwop"),
-					String::from("final: invalid commit"),
-				),
-				Commit::new(
-					String::from("hjkl12"),
-					String::from("chore: do boring stuff"),
-				),
-				Commit::new(
-					String::from("hjkl13"),
-					String::from("test(x): test some stuff"),
-				),
-				Commit::new(
-					String::from("1234"),
-					String::from("fix: support preprocessing (fixes #99)"),
-				),
-			]
-			.iter()
-			.filter_map(|c| c.process(&git_config).ok())
-			.collect::<Vec<Commit>>(),
-			commit_id: None,
-			timestamp: 0,
-			previous:  None,
-		},
-		Release {
-			version:   Some(String::from("v1.0.0")),
-			commits:   vec![
-				Commit::new(
-					String::from("0bc123"),
-					String::from("feat: add cool features"),
-				),
-				Commit::new(String::from("0werty"), String::from("fix: fix stuff")),
-				Commit::new(
-					String::from("0w3rty"),
-					String::from("fix: fix more stuff"),
-				),
-				Commit::new(
-					String::from("0jkl12"),
-					String::from("chore: do nothing"),
-				),
-			]
-			.into_iter()
-			.filter_map(|c| c.into_conventional().ok())
-			.collect::<Vec<Commit>>(),
-			commit_id: None,
-			timestamp: 0,
-			previous:  None,
-		},
-	];
+	let mut repository = SampleRepository::new()?;
+	for message in [
+		"feat: add cool features",
+		"fix: fix stuff",
+		"fix: fix more stuff",
+		"chore: do nothing",
+	] {
+		repository.commit(message)?;
+	}
+	repository.tag("v1.0.0")?;
+	for message in [
+		"Add unconventional commit",
+		"feat: add xyz",
+		"feat: add zyx",
+		"feat(random-scope): add random feature\n\nThis is related to https://github.com/NixOS/nixpkgs/issues/136814\n\nCloses #123",
+		"invalid commit",
+		"feat(big-feature)!: this is a breaking change",
+		"fix: fix abc",
+		"final: invalid commit",
+		"chore: do boring stuff",
+		"test(x): test some stuff",
+		"fix: support preprocessing (fixes #99)",
+	] {
+		repository.commit(message)?;
+	}
+	repository.tag("v2.0.0")?;
+
+	let mut releases = repository.releases()?;
+	releases[0].commits = releases[0]
+		.commits
+		.iter()
+		.filter_map(|c| c.process(&git_config, None).ok())
+		.collect::<Vec<Commit>>();
+	releases[1].commits = releases[1]
+		.commits
+		.drain(..)
+		.filter_map(|c| c.into_conventional().ok())
+		.collect::<Vec<Commit>>();
 
 	let out = &mut String::new();
 	let template = Template::new(changelog_config.body.unwrap())?;
@@ -2,6 +2,7 @@ use clap::{
 	AppSettings,
 	ArgEnum,
 	Parser,
+	Subcommand,
 };
 use git_cliff_core::glob::Pattern;
 use git_cliff_core::DEFAULT_CONFIG;
@@ -14,19 +15,114 @@ pub enum Strip {
 	All,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum Format {
+	Markdown,
+	Pdf,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
 pub enum Sort {
 	Oldest,
 	Newest,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum Bump {
+	Major,
+	Minor,
+	Patch,
+	Auto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum LineEnding {
+	Lf,
+	Crlf,
+	Native,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+	/// Writes the unreleased changes to an editable draft file.
+	Draft {
+		/// Sets the path of the draft file.
+		#[clap(value_name = "PATH", default_value = "DRAFT.md")]
+		path: PathBuf,
+	},
+	/// Writes the notes of each existing release to a separate file.
+	Backfill {
+		/// Sets the directory to write the release notes to.
+		#[clap(long, value_name = "PATH", default_value = "notes")]
+		output_dir: PathBuf,
+	},
+	/// Writes the body of each release to an editable fragment file.
+	Render {
+		/// Sets the directory to write the fragments to.
+		#[clap(long, value_name = "PATH", default_value = "fragments")]
+		output_dir: PathBuf,
+	},
+	/// Assembles the changelog from the fragment files.
+	Assemble {
+		/// Sets the directory to read the fragments from.
+		#[clap(long, value_name = "PATH", default_value = "fragments")]
+		input_dir: PathBuf,
+	},
+	/// Updates the notes of the published Github releases.
+	SyncReleases {
+		/// Prints the changes to the release notes without updating them.
+		#[clap(long)]
+		dry_run: bool,
+	},
+	/// Creates or updates the Github release of the latest version.
+	Publish {
+		/// Creates the release as a draft.
+		#[clap(long)]
+		draft:      bool,
+		/// Marks the release as a pre-release.
+		#[clap(long)]
+		prerelease: bool,
+		/// Updates the release if it already exists.
+		#[clap(long)]
+		overwrite:  bool,
+	},
+	/// Writes a combined changelog of the repositories in a Github
+	/// organization.
+	Org {
+		/// Sets the name of the organization.
+		#[clap(value_name = "NAME")]
+		name:  String,
+		/// Sets the patterns of the repositories to include.
+		#[clap(long, value_name = "PATTERN", multiple_values = true)]
+		repos: Option<Vec<Pattern>>,
+		/// Sets the maximum number of releases to fetch per repository.
+		#[clap(long, value_name = "NUM", default_value = "10")]
+		limit: usize,
+	},
+	/// Validates the commits in the range against the conventional commits
+	/// specification and the commit parsers.
+	Lint,
+	/// Merges the release sections of two changelogs (e.g. of diverging
+	/// branches).
+	MergeChangelogs {
+		/// Sets the changelog that the header, footer and the duplicate
+		/// versions are taken from.
+		#[clap(value_name = "PATH")]
+		ours:   PathBuf,
+		/// Sets the changelog to merge.
+		#[clap(value_name = "PATH")]
+		theirs: PathBuf,
+	},
+}
+
 /// Command-line arguments to parse.
-#[derive(Debug, Parser)]
+#[derive(Debug, Clone, Parser)]
 #[clap(
     version,
     author,
     about,
     global_setting = AppSettings::DeriveDisplayOrder,
+    propagate_version = true,
     rename_all_env = "screaming-snake",
     next_help_heading = Some("OPTIONS"),
     override_usage = "git-cliff [FLAGS] [OPTIONS] [--] [RANGE]",
@@ -36,16 +132,29 @@ pub enum Sort {
 pub struct Opt {
 	/// Increases the logging verbosity.
 	#[clap(short, long, parse(from_occurrences), alias = "debug", help_heading = Some("FLAGS"))]
-	pub verbose:      u8,
-	/// Sets the configuration file.
+	pub verbose: u8,
+	/// Sets the configuration file (or its URL).
 	#[clap(short, long, env = "GIT_CLIFF_CONFIG", value_name = "PATH", default_value = DEFAULT_CONFIG)]
-	pub config:       PathBuf,
+	pub config: PathBuf,
+	/// Sets the SHA-256 checksum to pin the remote configuration file to.
+	#[clap(long, env = "GIT_CLIFF_CONFIG_SHA256", value_name = "HASH")]
+	pub config_sha256: Option<String>,
 	/// Sets the working directory.
 	#[clap(short, long, env = "GIT_CLIFF_WORKDIR", value_name = "PATH")]
-	pub workdir:      Option<PathBuf>,
+	pub workdir: Option<PathBuf>,
 	/// Sets the git repository.
 	#[clap(short, long, env = "GIT_CLIFF_REPOSITORY", value_name = "PATH")]
-	pub repository:   Option<PathBuf>,
+	pub repository: Option<PathBuf>,
+	/// Sets the package of the monorepo to generate the changelog for.
+	#[clap(long, env = "GIT_CLIFF_PACKAGE", value_name = "NAME")]
+	pub package: Option<String>,
+	/// Generates the changelogs of all the packages of the monorepo.
+	#[clap(
+		long,
+		conflicts_with_all = &["package", "output", "prepend", "context"],
+		help_heading = Some("FLAGS")
+	)]
+	pub all_packages: bool,
 	/// Sets the path to include related commits.
 	#[clap(
 		long,
@@ -69,13 +178,24 @@ pub struct Opt {
 		value_name = "MSG",
 		multiple_values = true
 	)]
-	pub with_commit:  Option<Vec<String>>,
+	pub with_commit: Option<Vec<String>>,
+	/// Sets a variable in the template context.
+	#[clap(
+		long,
+		value_name = "KEY=VALUE",
+		multiple_occurrences = true,
+		parse(try_from_str = parse_key_value)
+	)]
+	pub context_var: Option<Vec<(String, String)>>,
 	/// Prepends entries to the given changelog file.
 	#[clap(short, long, env = "GIT_CLIFF_PREPEND", value_name = "PATH")]
-	pub prepend:      Option<PathBuf>,
+	pub prepend: Option<PathBuf>,
+	/// Uses the given draft file as the body of the latest release.
+	#[clap(long, env = "GIT_CLIFF_FROM_DRAFT", value_name = "PATH")]
+	pub from_draft: Option<PathBuf>,
 	/// Writes output to the given file.
 	#[clap(short, long, env = "GIT_CLIFF_OUTPUT", value_name = "PATH")]
-	pub output:       Option<PathBuf>,
+	pub output: Option<PathBuf>,
 	/// Sets the tag for the latest version.
 	#[clap(
 		short,
@@ -84,7 +204,25 @@ pub struct Opt {
 		value_name = "TAG",
 		allow_hyphen_values = true
 	)]
-	pub tag:          Option<String>,
+	pub tag: Option<String>,
+	/// Sets the message of the tag for the latest version.
+	#[clap(
+		long,
+		env = "GIT_CLIFF_TAG_MESSAGE",
+		value_name = "MESSAGE",
+		requires = "tag"
+	)]
+	pub tag_message: Option<String>,
+	/// Bumps the version of the unreleased changes.
+	#[clap(
+		long,
+		arg_enum,
+		value_name = "BUMP",
+		min_values = 0,
+		default_missing_value = "auto",
+		conflicts_with = "tag"
+	)]
+	pub bump: Option<Bump>,
 	/// Sets the template for the changelog body.
 	#[clap(
 		short,
@@ -93,35 +231,73 @@ pub struct Opt {
 		value_name = "TEMPLATE",
 		allow_hyphen_values = true
 	)]
-	pub body:         Option<String>,
-	/// Writes the default configuration file to cliff.toml
-	#[clap(short, long, help_heading = Some("FLAGS"))]
-	pub init:         bool,
+	pub body: Option<String>,
+	/// Writes the default (or a preset) configuration file to cliff.toml
+	#[clap(
+		short,
+		long,
+		value_name = "PRESET",
+		min_values = 0,
+		default_missing_value = "default"
+	)]
+	pub init: Option<String>,
 	/// Processes the commits starting from the latest tag.
 	#[clap(short, long, help_heading = Some("FLAGS"))]
-	pub latest:       bool,
+	pub latest: bool,
 	/// Processes the commits that belong to the current tag.
 	#[clap(long, help_heading = Some("FLAGS"))]
-	pub current:      bool,
+	pub current: bool,
 	/// Processes the commits that do not belong to a tag.
 	#[clap(short, long, help_heading = Some("FLAGS"))]
-	pub unreleased:   bool,
+	pub unreleased: bool,
+	/// Processes the commits that do not belong to a tag and writes nothing
+	/// if there are none.
+	#[clap(long, help_heading = Some("FLAGS"))]
+	pub unreleased_only_if_changes: bool,
+	/// Processes the commits that belong to the given tag.
+	#[clap(
+		long,
+		env = "GIT_CLIFF_TAG_ONLY",
+		value_name = "TAG",
+		conflicts_with_all = &["latest", "current", "unreleased", "range"]
+	)]
+	pub tag_only: Option<String>,
 	/// Sorts the tags chronologically.
 	#[clap(long, help_heading = Some("FLAGS"))]
-	pub date_order:   bool,
+	pub date_order: bool,
 	/// Prints changelog context as JSON.
 	#[clap(long, help_heading = Some("FLAGS"))]
-	pub context:      bool,
+	pub context: bool,
+	/// Writes the metrics of the run to the given file.
+	#[clap(long, env = "GIT_CLIFF_METRICS", value_name = "PATH")]
+	pub metrics: Option<PathBuf>,
+	/// Disables the cache of the remote lookups between the runs.
+	#[clap(long, help_heading = Some("FLAGS"))]
+	pub no_cache: bool,
+	/// Checks the links in the changelog before writing it.
+	#[clap(long, help_heading = Some("FLAGS"))]
+	pub check_links: bool,
 	/// Strips the given parts from the changelog.
 	#[clap(short, long, value_name = "PART", arg_enum)]
-	pub strip:        Option<Strip>,
+	pub strip: Option<Strip>,
 	/// Sets sorting of the commits inside sections.
 	#[clap(
 		long,
 		arg_enum,
 		default_value_t = Sort::Oldest
 	)]
-	pub sort:         Sort,
+	pub sort: Sort,
+	/// Sets the output format of the changelog.
+	#[clap(
+		long,
+		arg_enum,
+		value_name = "FORMAT",
+		default_value_t = Format::Markdown
+	)]
+	pub format: Format,
+	/// Sets the line ending of the changelog.
+	#[clap(long, arg_enum, value_name = "EOL")]
+	pub line_ending: Option<LineEnding>,
 	/// Token used when resolving informations related to Github.
 	#[clap(
 		long,
@@ -129,7 +305,35 @@ pub struct Opt {
 		value_name = "TOKEN"
 	)]
 	pub github_token: Option<String>,
+	/// Sets the URL of the Github API (e.g. for GitHub Enterprise Server).
+	#[clap(long, env = "GIT_CLIFF_GITHUB_API_URL", value_name = "URL")]
+	pub github_api_url: Option<String>,
+	/// Token used when resolving informations related to GitLab.
+	#[clap(long, env = "GIT_CLIFF_GITLAB_TOKEN", value_name = "TOKEN")]
+	pub gitlab_token: Option<String>,
+	/// Token used when resolving informations related to Gitea.
+	#[clap(long, env = "GIT_CLIFF_GITEA_TOKEN", value_name = "TOKEN")]
+	pub gitea_token: Option<String>,
+	/// Sets an additional commit range (or branch) to process.
+	#[clap(
+		long = "range",
+		value_name = "RANGE",
+		multiple_occurrences = true,
+		conflicts_with_all = &["latest", "current", "unreleased", "tag-only"]
+	)]
+	pub ranges: Option<Vec<String>>,
 	/// Sets the commit range to process.
 	#[clap(value_name = "RANGE", help_heading = Some("ARGS"))]
-	pub range:        Option<String>,
+	pub range: Option<String>,
+	/// Subcommand to run.
+	#[clap(subcommand)]
+	pub command: Option<Command>,
+}
+
+/// Parses a `KEY=VALUE` pair.
+fn parse_key_value(value: &str) -> Result<(String, String), String> {
+	value
+		.split_once('=')
+		.map(|(key, value)| (key.to_string(), value.to_string()))
+		.ok_or_else(|| format!("invalid KEY=VALUE: no `=` found in `{value}`"))
 }
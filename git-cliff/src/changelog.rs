@@ -1,14 +1,84 @@
-use std::collections::HashMap;
+use std::collections::{
+	HashMap,
+	HashSet,
+};
+use futures::stream::{
+	self,
+	StreamExt,
+	TryStreamExt,
+};
+use git_cliff_core::cache::{
+	self,
+	Cache,
+};
 use git_cliff_core::commit::Commit;
-use git_cliff_core::config::Config;
-use git_cliff_core::error::Result;
+use git_cliff_core::config::{
+	ApiDiffConfig,
+	ChangelogFormat,
+	Config,
+	EmailRedaction,
+	FixupMode,
+	LineEnding,
+	ReferenceProtection,
+	SkipTagsMode,
+	TagMatcher,
+};
+use git_cliff_core::error::{
+	Error,
+	Result,
+};
+use git_cliff_core::github::{
+	self,
+	NewRelease,
+};
+use git_cliff_core::gitlab;
+use git_cliff_core::intern::Symbol;
+use git_cliff_core::keepachangelog;
+use git_cliff_core::links::{
+	self,
+	BrokenLink,
+	LinkChecker,
+};
+use git_cliff_core::provider::{
+	CachedProvider,
+	GiteaProvider,
+	GithubProvider,
+	RemoteProvider,
+};
 use git_cliff_core::release::{
+	Bump,
+	Deprecation,
 	Release,
 	Releases,
+	RemoteRelease,
 };
+use git_cliff_core::remote::Remote;
 use git_cliff_core::template::Template;
-use git_cliff_core::regex::Regex;
+use git_cliff_core::tracker::TicketResolver;
+use git_cliff_core::translation::TranslationMemory;
+use std::fs::{
+	self,
+	File,
+};
 use std::io::Write;
+use std::path::{
+	Path,
+	PathBuf,
+};
+use std::sync::Arc;
+use std::thread;
+use std::time::{
+	Duration,
+	SystemTime,
+	UNIX_EPOCH,
+};
+use tokio::sync::Mutex;
+
+/// Maximum number of the commits that are resolved on the remote concurrently.
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Name of the fragment of the unreleased changes.
+const UNRELEASED_FRAGMENT: &str = "unreleased.md";
 
 /// Changelog generator.
 #[derive(Debug)]
@@ -18,6 +88,12 @@ pub struct Changelog<'a> {
 	config:       &'a Config,
 	github_token: Option<String>,
 	github_repo:  Option<String>,
+	gitlab_repo:  Option<String>,
+	provider:     Option<(Box<dyn RemoteProvider>, bool)>,
+	remote:       Option<Remote>,
+	cache:        Arc<dyn Cache>,
+	draft:        Option<String>,
+	fragments:    Option<Vec<String>>,
 }
 
 impl<'a> Changelog<'a> {
@@ -27,7 +103,7 @@ impl<'a> Changelog<'a> {
 		config: &'a Config,
 		git_remotes: Option<Vec<String>>,
 		github_token: Option<String>,
-	) -> Result<Changelog> {
+	) -> Result<Changelog<'a>> {
 		let mut template = config
 			.changelog
 			.body
@@ -40,112 +116,629 @@ impl<'a> Changelog<'a> {
 					.join("\n")
 			)
 		}
+		let github_url = github::web_url(&config.github);
 		let github_repo = config.github.repository.clone().or_else(|| {
-			if let Some(git_remotes) = &git_remotes {
-				let github_url_regex = Regex::new(
-					r"github\.com[/:]([\w._-]+?)/([\w._-]+?)(\.git)?$"
-				).unwrap();
-				git_remotes.iter().find_map(|remote| {
-					github_url_regex.captures(remote).map(|captures| format!(
-						"{}/{}",
-						captures.get(1).unwrap().as_str().to_string(),
-						captures.get(2).unwrap().as_str().to_string(),
-					))
-				})
-			} else {
-				None
-			}
+			git_remotes.iter().flatten().find_map(|remote| {
+				github::repository_from_remote(&github_url, remote)
+			})
+		});
+		let gitlab_repo = config.gitlab.project.clone().or_else(|| {
+			let base_url = gitlab::base_url(&config.gitlab);
+			git_remotes
+				.iter()
+				.flatten()
+				.find_map(|remote| gitlab::project_from_remote(base_url, remote))
+		});
+		let cache = cache::new(&config.cache)?;
+		let provider = Self::remote_provider(
+			config,
+			github_repo.as_ref(),
+			github_token.as_ref(),
+			git_remotes.as_deref().unwrap_or_default(),
+		);
+		// Cache the lookups of the provider between the runs.
+		let provider = provider.map(|(provider, resolve_authors)| {
+			let ttl = config.cache.ttl;
+			let provider: Box<dyn RemoteProvider> =
+				Box::new(CachedProvider::new(provider, cache.clone(), ttl));
+			(provider, resolve_authors)
 		});
+		let remote = Remote::new(
+			&config.remote,
+			&github_repo
+				.iter()
+				.map(|repo| format!("{github_url}/{repo}"))
+				.chain(git_remotes.into_iter().flatten())
+				.collect::<Vec<String>>(),
+		);
 		let mut changelog = Self {
 			template: if let Some(template) = template {
-				Some(Template::new(template)?)
+				let mut template = Template::with_partials(
+					template,
+					&config.template.partials.clone().unwrap_or_default(),
+				)?;
+				if let Some(extra_context) = &config.changelog.extra_context {
+					template.set_extra_context(extra_context.clone());
+				}
+				if let Some(allowed_env) = &config.changelog.allowed_env {
+					template.set_allowed_env(allowed_env)?;
+				}
+				if let Some(functions) = &config.template.functions {
+					template.set_external_functions(functions.clone());
+				}
+				if let Some(locale) = &config.changelog.locale {
+					template.set_locale(locale);
+				}
+				template.set_limits(
+					config.template.render_timeout.map(Duration::from_secs),
+					config.template.max_output_size,
+				);
+				Some(template)
 			} else {
 				None
 			},
 			releases,
 			config,
 			github_token,
-			github_repo
+			github_repo,
+			gitlab_repo,
+			provider,
+			remote,
+			cache,
+			draft: None,
+			fragments: None,
 		};
+		changelog.check_github_token().await?;
 		changelog.process_commits().await?;
 		changelog.process_releases();
+		if config.github.resolve_releases.unwrap_or(false) {
+			changelog.resolve_remote_releases().await?;
+		}
+		if let Some(patterns) = &config.changelog.artifacts {
+			for (i, release) in changelog.releases.iter_mut().enumerate() {
+				release.set_artifacts(patterns, i == 0)?;
+			}
+		}
+		if let Some(api_diff) = &config.changelog.api_diff {
+			changelog.resolve_api_changes(api_diff)?;
+		}
+		if let Some(commands) = &config.changelog.metric_commands {
+			changelog.resolve_metrics(commands)?;
+		}
 		Ok(changelog)
 	}
 
+	/// Sets the metrics of the releases and their previous releases so that
+	/// the changes between the versions can be shown.
+	fn resolve_metrics(&mut self, commands: &HashMap<String, String>) -> Result<()> {
+		let mut metrics = HashMap::new();
+		for release in self.releases.iter_mut() {
+			debug!("Computing the metrics of {:?}", release.version);
+			release.set_metrics(commands)?;
+			metrics.insert(release.version.clone(), release.metrics.clone());
+		}
+		for previous in self
+			.releases
+			.iter_mut()
+			.filter_map(|release| release.previous.as_mut())
+			.filter(|previous| previous.version.is_some())
+		{
+			match metrics.get(&previous.version) {
+				Some(values) => previous.metrics = values.clone(),
+				None => previous.set_metrics(commands)?,
+			}
+		}
+		Ok(())
+	}
+
+	/// Sets the changes of the public API of the releases since their previous
+	/// releases.
+	#[cfg(feature = "api-diff")]
+	fn resolve_api_changes(&mut self, config: &ApiDiffConfig) -> Result<()> {
+		for release in self.releases.iter_mut() {
+			let from =
+				match release.previous.as_ref().and_then(|v| v.version.as_ref()) {
+					Some(version) => version.to_string(),
+					None => continue,
+				};
+			let to = release.version.as_deref().unwrap_or("HEAD");
+			debug!("Diffing the public API of {}..{}", from, to);
+			release.api_changes =
+				Some(git_cliff_core::api_diff::diff(config, &from, to)?);
+		}
+		Ok(())
+	}
+
+	/// Sets the changes of the public API of the releases since their previous
+	/// releases.
+	#[cfg(not(feature = "api-diff"))]
+	fn resolve_api_changes(&mut self, _: &ApiDiffConfig) -> Result<()> {
+		Err(Error::ArgumentError(String::from(
+			"API diff requires the 'api-diff' feature to be enabled",
+		)))
+	}
+
+	/// Fetches the published Github releases of the versions (if any).
+	async fn resolve_remote_releases(&mut self) -> Result<()> {
+		let repo = match &self.github_repo {
+			Some(repo) => repo,
+			None => return Ok(()),
+		};
+		debug!("Fetching the Github releases...");
+		let api_url = github::api_url(&self.config.github);
+		let token = &self.github_token;
+		let versions = self.releases.iter().map(|v| v.version.clone());
+		let remote_releases: Vec<Option<github::Release>> = stream::iter(versions)
+			.map(|version| async move {
+				match version {
+					Some(tag) => {
+						github::get_release_by_tag(api_url, token, repo, &tag).await
+					}
+					None => Ok(None),
+				}
+			})
+			.buffered(MAX_CONCURRENT_REQUESTS)
+			.try_collect()
+			.await?;
+		for (release, remote) in self.releases.iter_mut().zip(remote_releases) {
+			release.remote = remote.map(RemoteRelease::from);
+		}
+		Ok(())
+	}
+
+	/// Validates the Github token before resolving the commits so that the run
+	/// fails fast instead of in the middle.
+	///
+	/// If the token is not set, the unauthenticated requests are used for the
+	/// public repositories and the Github lookups are skipped if they are not
+	/// possible.
+	async fn check_github_token(&mut self) -> Result<()> {
+		let repo = match (&self.github_repo, &self.provider) {
+			(Some(repo), Some((provider, _))) if provider.name() == "github" => repo,
+			_ => return Ok(()),
+		};
+		debug!("Validating the Github token...");
+		let api_url = github::api_url(&self.config.github);
+		let status = github::check_token(api_url, &self.github_token, repo).await;
+		match (status, &self.github_token) {
+			(Ok(status), Some(_)) => {
+				if let Some(login) = &status.login {
+					debug!("Authenticated as {}", login);
+				}
+				debug!(
+					"{}/{} Github API requests remaining",
+					status.remaining, status.limit
+				);
+			}
+			(Ok(status), None) => warn!(
+				"Github token is not set, using the unauthenticated API requests \
+				 ({}/{} remaining)",
+				status.remaining, status.limit
+			),
+			(Err(e), Some(_)) => return Err(e),
+			(Err(e), None) => {
+				warn!(
+					"Github token is not set, skipping the Github lookups: {}",
+					e
+				);
+				self.provider = None;
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the provider for resolving the information of the commits on
+	/// the remote repository (if enabled) and whether to resolve the authors.
+	///
+	/// Github is preferred over Gitea.
+	fn remote_provider(
+		config: &Config,
+		github_repo: Option<&String>,
+		github_token: Option<&String>,
+		git_remotes: &[String],
+	) -> Option<(Box<dyn RemoteProvider>, bool)> {
+		if let Some(repo) = github_repo {
+			let github = &config.github;
+			if !(github.resolve_prs.unwrap_or(true) ||
+				github.resolve_authors.unwrap_or(true))
+			{
+				return None;
+			}
+			let provider = GithubProvider::new(
+				github::api_url(github),
+				github_token.cloned(),
+				repo.clone(),
+			);
+			return Some((
+				Box::new(provider),
+				github.resolve_authors.unwrap_or(true),
+			));
+		}
+		let gitea = &config.gitea;
+		if !(gitea.resolve_prs.unwrap_or(true) ||
+			gitea.resolve_authors.unwrap_or(true))
+		{
+			return None;
+		}
+		GiteaProvider::new(gitea, git_remotes).map(|provider| {
+			let provider: Box<dyn RemoteProvider> = Box::new(provider);
+			(provider, gitea.resolve_authors.unwrap_or(true))
+		})
+	}
+
 	/// Processes the commits and omits the ones that doesn't match the
 	/// criteria set by configuration file.
 	async fn process_commits(&mut self) -> Result<()> {
 		debug!("Processing the commits...");
 
-		let mut github_usernames = HashMap::new();
-		let mut github_coauthors = HashMap::new();
+		let github_usernames = Mutex::new(HashMap::new());
+		let github_coauthors = Mutex::new(HashMap::new());
+		let mut gitlab_usernames = HashMap::new();
+		let mut gitlab_participants = HashMap::new();
+		let memory_path = self.config.git.translation_memory.as_ref().map(Path::new);
+		let mut memory = memory_path.map(TranslationMemory::load).transpose()?;
+		let mut ticket_resolver =
+			TicketResolver::new(self.cache.clone(), self.config.cache.ttl);
+		let email_redaction = self.email_redaction();
 
 		for release in self.releases.iter_mut() {
-			let mut result = Vec::new();
-			let commits = release
-				.commits
-				.iter()
-				.cloned()
-				.flat_map(|commit| {
-					if self.config.git.split_commits.unwrap_or(false) {
+			let commits =
+				Self::parse_commits(&release.commits, self.config, memory.as_mut());
+			if self.config.changelog.previous_summary.unwrap_or(false) {
+				if let Some(previous) = release.previous.as_mut() {
+					previous.commits = Self::parse_commits(
+						&previous.commits,
+						self.config,
+						memory.as_mut(),
+					);
+				}
+			}
+
+			// Concurrently process all commits
+			let mut result = match &self.provider {
+				Some((provider, resolve_authors)) => {
+					let resolve_reactions =
+						self.config.github.resolve_reactions.unwrap_or(false);
+					stream::iter(commits.iter().cloned())
+						.map(|mut commit| {
+							let (github_usernames, github_coauthors) =
+								(&github_usernames, &github_coauthors);
+							async move {
+								// Resolve the id of the commit author on the remote
+								commit
+									.resolve_remote(
+										provider.as_ref(),
+										*resolve_authors,
+										github_usernames,
+										github_coauthors,
+									)
+									.await?;
+
+								// Resolve the comment and reaction counts of the
+								// pull request
+								if resolve_reactions {
+									commit
+										.resolve_pull_request(provider.as_ref())
+										.await?;
+								}
+								Ok::<_, Error>(commit)
+							}
+						})
+						.buffered(MAX_CONCURRENT_REQUESTS)
+						.try_collect()
+						.await?
+				}
+				None => commits.clone().to_vec(),
+			};
+
+			// Resolve the GitLab usernames and merge requests
+			if let Some(gitlab_repo) = &self.gitlab_repo {
+				if self.config.gitlab.resolve_authors.unwrap_or(false) ||
+					self.config.gitlab.resolve_mrs.unwrap_or(false)
+				{
+					for commit in result.iter_mut() {
 						commit
-							.message
-							.lines()
-							.map(|line| {
-								let mut c = commit.clone();
-								c.message = line.to_string();
-								c
-							})
-							.collect()
-					} else {
-						vec![commit]
+							.resolve_gitlab(
+								&self.config.gitlab,
+								gitlab_repo,
+								&mut gitlab_usernames,
+								&mut gitlab_participants,
+							)
+							.await?;
 					}
-				})
-				.filter_map(|commit| match commit.process(&self.config.git) {
-					Ok(commit) => Some(commit),
-					Err(e) => {
-						trace!(
-							"{} - {} ({})",
-							commit.id[..7].to_string(),
-							e,
-							commit.message.lines().next().unwrap_or_default().trim()
-						);
-						None
-					}
-				})
-				.collect::<Vec<Commit>>();
+				}
+			}
 
-			// Concurrently process all commits
-			if self.config.github.resolve_prs.unwrap_or(true)
-				|| self.config.github.resolve_authors.unwrap_or(true) {
-				for commit in &commits {
-					let mut commit = commit.clone();
-
-					// Resolve the id of the commit author on Github
-					commit.resolve_github(
-						&self.config.github,
-						&self.github_token,
-						&self.github_repo.clone().expect("'repository' value is needed to resolve Github informations"),
-						&mut github_usernames,
-						&mut github_coauthors,
-					).await.expect("Failed to resolve Github informations");
-
-					result.push(commit);
+			// Resolve the Gerrit changes
+			if self.config.gerrit.url.is_some() {
+				for commit in result.iter_mut() {
+					commit.resolve_gerrit(&self.config.gerrit).await?;
+				}
+			}
+
+			// Resolve the tickets of the issue trackers
+			for commit in result.iter_mut() {
+				commit.tickets = ticket_resolver
+					.resolve(&self.config.trackers, &commit.message)
+					.await?;
+				for issue in commit.issues.iter_mut() {
+					let resolved = match &issue.tracker {
+						Some(tracker) => {
+							ticket_resolver
+								.resolve_key(
+									&self.config.trackers,
+									tracker,
+									&issue.key,
+								)
+								.await?
+						}
+						None => None,
+					};
+					if let Some(resolved) = resolved {
+						issue.title = resolved.title;
+						issue.state = resolved.state;
+						issue.kind = resolved.kind;
+					}
 				}
-			} else {
-				result = commits.clone().to_vec();
 			}
 
+			// Set the contributors from the processed commits
 			release.commits = result;
+			release.update_contributors();
+
+			// Set the usernames of the contributors from the resolved authors
+			{
+				let usernames = github_usernames.lock().await;
+				for contributor in release.contributors.iter_mut() {
+					contributor.github_username = contributor
+						.email
+						.as_ref()
+						.and_then(|email| usernames.get(email))
+						.cloned();
+				}
+			}
+
+			// Redact the email addresses of the commit authors
+			if let Some((redaction, salt)) = email_redaction {
+				for commit in release.commits.iter_mut() {
+					commit.redact_emails(redaction, salt);
+				}
+				for contributor in release.contributors.iter_mut() {
+					contributor.email = contributor
+						.email
+						.as_deref()
+						.and_then(|email| redaction.redact(email, salt));
+				}
+				if let Some(previous) = release.previous.as_mut() {
+					for commit in previous.commits.iter_mut() {
+						commit.redact_emails(redaction, salt);
+					}
+				}
+			}
 		};
 
+		if let (Some(memory), Some(path)) = (memory, memory_path) {
+			memory.save(path)?;
+		}
+
 		Ok(())
 	}
 
+	/// Returns the redaction of the email addresses and its salt (if
+	/// enabled).
+	fn email_redaction(&self) -> Option<(EmailRedaction, &'a str)> {
+		let changelog = &self.config.changelog;
+		if changelog.redact_emails.unwrap_or(false) {
+			Some((
+				changelog.email_redaction.unwrap_or(EmailRedaction::Strip),
+				changelog.email_salt.as_deref().unwrap_or_default(),
+			))
+		} else {
+			None
+		}
+	}
+
+	/// Splits (if enabled) and processes the given commits.
+	///
+	/// Omits the commits that can't be processed. The commits skipped by the
+	/// commit parsers are protected (if enabled) when they are referenced by
+	/// the reverts or fixups of the kept commits.
+	fn parse_commits<'b>(
+		commits: &[Commit<'b>],
+		config: &Config,
+		mut memory: Option<&mut TranslationMemory>,
+	) -> Vec<Commit<'b>> {
+		let mut commits = match config.git.fixups {
+			Some(mode) => Self::fold_fixups(commits, mode),
+			None => commits.to_vec(),
+		};
+		if config.git.filter_reverted.unwrap_or(false) {
+			commits = Self::filter_reverted(&commits);
+		}
+		let mut processed = Vec::new();
+		let mut skipped = Vec::new();
+		for commit in commits.into_iter().flat_map(|commit| {
+			if config.git.split_commits.unwrap_or(false) {
+				commit
+					.message
+					.lines()
+					.map(|line| {
+						let mut c = commit.clone();
+						c.message = line.to_string();
+						c
+					})
+					.collect()
+			} else {
+				vec![commit]
+			}
+		}) {
+			match commit.process(&config.git, memory.as_deref_mut()) {
+				Ok(commit) => processed.push(commit),
+				Err(e) => {
+					trace!(
+						"{} - {} ({})",
+						commit.id.get(..7).unwrap_or(&commit.id),
+						e,
+						commit.message.lines().next().unwrap_or_default().trim()
+					);
+					if matches!(e, Error::GroupError(_) | Error::SkippedCommit) {
+						skipped.push((processed.len(), commit));
+					}
+				}
+			}
+		}
+		match config.git.protect_referenced_commits {
+			Some(ReferenceProtection::Keep) => {
+				for (index, commit) in skipped.into_iter().rev() {
+					if let Some(group) = processed
+						.iter()
+						.find(|c| c.references(&commit))
+						.map(|c| c.group.clone())
+					{
+						let mut stub =
+							commit.clone().into_conventional().unwrap_or(commit);
+						stub.group = group;
+						processed.insert(index, stub);
+					}
+				}
+			}
+			Some(ReferenceProtection::Annotate) => {
+				for commit in processed.iter_mut() {
+					commit.hidden_references = skipped
+						.iter()
+						.filter(|(_, skipped)| commit.references(skipped))
+						.map(|(_, skipped)| skipped.id.clone())
+						.collect();
+				}
+			}
+			None => {}
+		}
+		if config.changelog.format == Some(ChangelogFormat::KeepAChangelog) {
+			for commit in processed.iter_mut() {
+				commit.group = Some(Symbol::from(keepachangelog::section(commit)));
+			}
+		}
+		processed
+	}
+
+	/// Removes the reverted commits along with their reverts.
+	///
+	/// The reverts that are reverted themselves are handled first so that the
+	/// reapplied commits are kept. The reverts of the commits in the other
+	/// releases are kept as well.
+	fn filter_reverted<'b>(commits: &[Commit<'b>]) -> Vec<Commit<'b>> {
+		let mut removed = vec![false; commits.len()];
+		let reverts = (0..commits.len())
+			.filter(|&i| commits[i].is_revert())
+			.collect::<Vec<usize>>();
+		loop {
+			let pair = reverts.iter().find_map(|&revert| {
+				let is_reverted = |i: usize| {
+					reverts.iter().any(|&other| {
+						other != i &&
+							!removed[other] && commits[other].reverts(&commits[i])
+					})
+				};
+				if removed[revert] || is_reverted(revert) {
+					return None;
+				}
+				(0..commits.len())
+					.find(|&i| {
+						i != revert &&
+							!removed[i] && commits[revert].reverts(&commits[i])
+					})
+					.map(|reverted| (revert, reverted))
+			});
+			match pair {
+				Some((revert, reverted)) => {
+					trace!(
+						"Removing reverted commit: {}",
+						commits[reverted].subject()
+					);
+					removed[revert] = true;
+					removed[reverted] = true;
+				}
+				None => break,
+			}
+		}
+		commits
+			.iter()
+			.zip(removed)
+			.filter(|(_, removed)| !removed)
+			.map(|(commit, _)| commit.clone())
+			.collect()
+	}
+
+	/// Removes the `fixup!`, `squash!` and `amend!` commits.
+	///
+	/// The IDs of the removed commits are added to the commits that they fix
+	/// up when folding.
+	fn fold_fixups<'b>(commits: &[Commit<'b>], mode: FixupMode) -> Vec<Commit<'b>> {
+		let (fixups, mut commits): (Vec<Commit>, Vec<Commit>) = commits
+			.iter()
+			.cloned()
+			.partition(|commit| commit.fixup_of().is_some());
+		for fixup in fixups {
+			trace!("Removing fixup commit: {}", fixup.subject());
+			if mode != FixupMode::Fold {
+				continue;
+			}
+			if let Some(commit) = commits
+				.iter_mut()
+				.find(|commit| Some(commit.subject()) == fixup.fixup_of())
+			{
+				commit.fixups.push(fixup.id.clone());
+			}
+		}
+		commits
+	}
+
+	/// Merges the commits of the skipped releases into the next release that
+	/// is not skipped.
+	///
+	/// The commits of the skipped releases that are not followed by any other
+	/// release are dropped.
+	fn merge_skipped_releases(
+		releases: Vec<Release<'a>>,
+		skip_matcher: &TagMatcher,
+	) -> Vec<Release<'a>> {
+		let mut merged = Vec::new();
+		let mut skipped: Option<Release> = None;
+		for mut release in releases {
+			if let Some(skipped) = skipped.take() {
+				let mut commits = skipped.commits;
+				commits.append(&mut release.commits);
+				release.commits = commits;
+				if release.previous.as_ref().map(|v| &v.version) ==
+					Some(&skipped.version)
+				{
+					release.previous = skipped.previous;
+				}
+			}
+			match &release.version {
+				Some(version) if skip_matcher.is_match(version) => {
+					trace!("Merging release into the next one: {}", version);
+					skipped = Some(release);
+				}
+				_ => merged.push(release),
+			}
+		}
+		if let Some(version) = skipped.and_then(|release| release.version) {
+			trace!("Skipping release without a next one: {}", version);
+		}
+		merged
+	}
+
 	/// Processes the releases and filters them out based on the configuration.
 	fn process_releases(&mut self) {
 		debug!("Processing the releases...");
-		let skip_regex = self.config.git.skip_tags.as_ref();
+		let skip_matcher = self.config.git.skip_tags.as_ref();
+		if let (Some(matcher), Some(SkipTagsMode::Merge)) =
+			(skip_matcher, self.config.git.skip_tags_mode)
+		{
+			self.releases =
+				Self::merge_skipped_releases(self.releases.clone(), matcher);
+		}
 		let mut skipped_tags = Vec::new();
 		self.releases = self
 			.releases
@@ -156,10 +749,15 @@ impl<'a> Changelog<'a> {
 				if release.commits.is_empty() {
 					if let Some(version) = release.version.as_ref().cloned() {
 						trace!("Release doesn't have any commits: {}", version);
+						false
+					} else {
+						self.config
+							.changelog
+							.render_always_unreleased
+							.unwrap_or(false)
 					}
-					false
 				} else if let Some(version) = &release.version {
-					!skip_regex
+					!skip_matcher
 						.map(|r| {
 							let skip_tag = r.is_match(version);
 							if skip_tag {
@@ -174,6 +772,22 @@ impl<'a> Changelog<'a> {
 				}
 			})
 			.collect();
+		if let Some(markers) = &self.config.changelog.migration_markers {
+			for release in self.releases.iter_mut() {
+				release.set_migration_notes(markers);
+			}
+		}
+		if let Some(max) = self.config.changelog.max_entries_per_group {
+			let priority = self
+				.config
+				.changelog
+				.entry_priority
+				.clone()
+				.unwrap_or_default();
+			for release in self.releases.iter_mut() {
+				release.limit_commits_per_group(max, &priority);
+			}
+		}
 		for skipped_tag in &skipped_tags {
 			if let Some(release_index) = self.releases.iter().position(|release| {
 				release
@@ -193,45 +807,640 @@ impl<'a> Changelog<'a> {
 				}
 			}
 		}
+		if self.config.changelog.previous_summary.unwrap_or(false) {
+			for release in self.releases.iter_mut() {
+				if let Some(previous) = release.previous.as_mut() {
+					previous.summary = Some(previous.group_counts());
+				}
+			}
+		}
+		for release in self.releases.iter_mut() {
+			release.update_statistics();
+			release.set_breaking_changes();
+		}
+	}
+
+	/// Sets the next version on the unreleased release (if any) based on the
+	/// given previous version and returns it.
+	pub fn bump_version(
+		&mut self,
+		bump: Bump,
+		previous: Option<&str>,
+	) -> Result<Option<String>> {
+		let release = match self.releases.first_mut() {
+			Some(release) if release.version.is_none() => release,
+			_ => return Ok(None),
+		};
+		let version = release.calculate_next_version(previous, bump)?;
+		release.version = Some(version.clone());
+		if release.timestamp == 0 {
+			release.timestamp = SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map(|v| v.as_secs() as i64)
+				.unwrap_or_default();
+		}
+		Ok(Some(version))
+	}
+
+	/// Returns the processed releases.
+	pub fn releases(&self) -> &[Release<'a>] {
+		&self.releases
+	}
+
+	/// Sets the edited draft to use as the body of the latest release.
+	pub fn set_draft(&mut self, draft: String) {
+		self.draft = Some(draft);
+	}
+
+	/// Reads the fragments in the given directory to use as the bodies of the
+	/// releases.
+	///
+	/// The fragments (i.e. the `.md` files) are assembled in the order of the
+	/// releases that they are named after and the other fragments follow in
+	/// the order of their names.
+	/// Returns the number of fragments.
+	pub fn set_fragments(&mut self, dir: &Path) -> Result<usize> {
+		let mut paths = fs::read_dir(dir)?
+			.map(|entry| entry.map(|v| v.path()))
+			.collect::<std::io::Result<Vec<PathBuf>>>()?;
+		paths.retain(|path| {
+			path.is_file() && path.extension().map_or(false, |v| v == "md")
+		});
+		let names = self
+			.releases
+			.iter()
+			.map(Self::fragment_name)
+			.collect::<Vec<String>>();
+		paths.sort_by_cached_key(|path| {
+			let name = path.file_name().and_then(|v| v.to_str());
+			let position = names.iter().position(|v| Some(v.as_str()) == name);
+			(position.unwrap_or(usize::MAX), path.clone())
+		});
+		let fragments = paths
+			.iter()
+			.map(|path| {
+				trace!("Reading the fragment: {:?}", path);
+				fs::read_to_string(path).map(|v| v.replace("\r\n", "\n"))
+			})
+			.collect::<std::io::Result<Vec<String>>>()?;
+		let count = fragments.len();
+		self.fragments = Some(fragments);
+		Ok(count)
+	}
+
+	/// Returns the name of the fragment of the given release, e.g.
+	/// `v1.0.0.md`.
+	fn fragment_name(release: &Release) -> String {
+		match &release.version {
+			Some(version) => format!("{}.md", version.replace('/', "-")),
+			None => String::from(UNRELEASED_FRAGMENT),
+		}
 	}
 
 	/// Generates the changelog and writes it to the given output.
+	///
+	/// The changelog is validated if it is in the Keep a Changelog format.
 	pub fn generate<W: Write>(&self, out: &mut W) -> Result<()> {
 		debug!("Generating changelog...");
-		if let Some(header) = &self.config.changelog.header {
-			write!(out, "{}", header)?;
+		let output = self.render(true, None)?;
+		if self.is_keepachangelog() {
+			let problems = keepachangelog::validate(&output);
+			if !problems.is_empty() {
+				return Err(Error::ChangelogError(format!(
+					"changelog does not follow Keep a Changelog: {}",
+					problems.join(", ")
+				)));
+			}
 		}
-		for release in &self.releases {
-			if let Some(template) = &self.template {
-				write!(out, "{}", template.render(release)?)?;
-			} else {
-				write!(out, "{}", Template::render_default(release, self.github_repo.clone())?)?;
+		Self::write_output(out, &output, self.config.changelog.line_ending)
+	}
+
+	/// Returns whether the changelog is in the Keep a Changelog format.
+	fn is_keepachangelog(&self) -> bool {
+		self.config.changelog.format == Some(ChangelogFormat::KeepAChangelog)
+	}
+
+	/// Renders the changelog with the header and footer.
+	///
+	/// The appendix (i.e. the deprecations or the link references of the
+	/// releases in the Keep a Changelog format) is rendered before the footer
+	/// if `appendix` is set. The releases with the given versions are skipped.
+	fn render(
+		&self,
+		appendix: bool,
+		skipped_versions: Option<&HashSet<String>>,
+	) -> Result<String> {
+		let mut output = String::new();
+		match &self.config.changelog.header {
+			Some(header) => output += header,
+			None if self.is_keepachangelog() => output += keepachangelog::HEADER,
+			None => {}
+		}
+		if let Some(fragments) = &self.fragments {
+			output += &fragments.concat();
+		} else {
+			let releases = self
+				.releases
+				.iter()
+				.enumerate()
+				.filter(|(_, release)| {
+					if let (Some(version), Some(skipped_versions)) =
+						(&release.version, skipped_versions)
+					{
+						if skipped_versions.contains(version.trim_start_matches('v'))
+						{
+							trace!("Release already exists: {}", version);
+							return false;
+						}
+					}
+					true
+				})
+				.collect::<Vec<(usize, &Release)>>();
+			output += &self.render_releases(&releases)?.concat();
+		}
+		if appendix && self.is_keepachangelog() {
+			if let Some(remote) = &self.remote {
+				output += &keepachangelog::render_links(&self.releases, remote);
 			}
+		} else if appendix && self.config.changelog.deprecations.unwrap_or(false) {
+			output += &self.render_deprecations();
 		}
 		if let Some(footer) = &self.config.changelog.footer {
-			write!(out, "{}", footer)?;
+			output += footer;
 		}
-		Ok(())
+		Ok(output)
 	}
 
-	/// Generates a changelog and prepends it to the given changelog.
-	pub fn prepend<W: Write>(
+	/// Renders the given releases (along with their indexes) in parallel.
+	///
+	/// The releases are split into chunks which are rendered on separate
+	/// threads since the templates only depend on their contexts. The
+	/// outputs are joined in the order of the releases.
+	fn render_releases(
 		&self,
-		mut changelog: String,
+		releases: &[(usize, &Release)],
+	) -> Result<Vec<String>> {
+		let render = |(i, release): &(usize, &Release)| match (i, &self.draft) {
+			(0, Some(draft)) => Ok(draft.to_string()),
+			_ => self.render_release(release),
+		};
+		let threads = thread::available_parallelism().map_or(1, |v| v.get());
+		if threads == 1 || releases.len() <= 1 {
+			return releases.iter().map(render).collect();
+		}
+		let chunk_size = (releases.len() + threads - 1) / threads;
+		crossbeam_utils::thread::scope(|scope| {
+			releases
+				.chunks(chunk_size)
+				.map(|chunk| {
+					scope.spawn(move |_| {
+						chunk.iter().map(render).collect::<Result<Vec<String>>>()
+					})
+				})
+				.collect::<Vec<_>>()
+				.into_iter()
+				.map(|handle| {
+					handle.join().unwrap_or_else(|_| {
+						Err(Error::ChangelogError(String::from(
+							"Thread panicked while rendering the releases",
+						)))
+					})
+				})
+				.collect::<Result<Vec<Vec<String>>>>()
+		})
+		.map_err(|_| {
+			Error::ChangelogError(String::from(
+				"Thread panicked while rendering the releases",
+			))
+		})?
+		.map(|outputs| outputs.concat())
+	}
+
+	/// Returns the deprecations of the releases (from the oldest one) along
+	/// with the versions that they are introduced in.
+	pub fn deprecations(&self) -> Vec<Deprecation> {
+		self.releases
+			.iter()
+			.rev()
+			.flat_map(|release| {
+				release.commits.iter().flat_map(move |commit| {
+					commit
+						.deprecations()
+						.into_iter()
+						.map(move |text| Deprecation {
+							version: release.version.clone(),
+							commit_id: commit.id.to_string(),
+							text,
+						})
+				})
+			})
+			.collect()
+	}
+
+	/// Renders the cumulative appendix of the deprecations.
+	fn render_deprecations(&self) -> String {
+		let deprecations = self.deprecations();
+		if deprecations.is_empty() {
+			return String::new();
+		}
+		let mut output = String::from("## Deprecations\n\n");
+		for deprecation in deprecations {
+			let since = match &deprecation.version {
+				Some(version) => format!("since {version}"),
+				None => String::from("unreleased"),
+			};
+			output += &format!("- {} ({since})\n", deprecation.text);
+		}
+		output + "\n"
+	}
+
+	/// Writes the output with the given line ending (if any).
+	fn write_output<W: Write>(
 		out: &mut W,
+		output: &str,
+		line_ending: Option<LineEnding>,
 	) -> Result<()> {
+		match line_ending {
+			Some(line_ending) => write!(out, "{}", line_ending.apply(output))?,
+			None => write!(out, "{}", output)?,
+		}
+		Ok(())
+	}
+
+	/// Renders the given release with the template.
+	fn render_release(&self, release: &Release) -> Result<String> {
+		match &self.template {
+			Some(template) => template.render(release),
+			None if self.is_keepachangelog() => {
+				Ok(keepachangelog::render_release(release))
+			}
+			None => Template::render_default(
+				release,
+				&self.config.changelog,
+				self.github_repo.clone(),
+				self.remote.as_ref(),
+			),
+		}
+	}
+
+	/// Writes the notes of each tagged release to a separate file in the given
+	/// directory.
+	///
+	/// Files that already exist are not overwritten.
+	/// Returns the number of written files.
+	pub fn backfill(&self, dir: &Path) -> Result<usize> {
+		debug!("Backfilling the release notes...");
+		let mut count = 0;
+		fs::create_dir_all(dir)?;
+		for release in &self.releases {
+			if release.version.is_none() {
+				continue;
+			}
+			let path = dir.join(Self::fragment_name(release));
+			if path.exists() {
+				trace!("Release notes already exist: {:?}", path);
+				continue;
+			}
+			let mut file = File::create(&path)?;
+			Self::write_output(
+				&mut file,
+				&self.render_release(release)?,
+				self.config.changelog.line_ending,
+			)?;
+			count += 1;
+		}
+		Ok(count)
+	}
+
+	/// Writes the rendered body of each release to a separate fragment in the
+	/// given directory for assembling the changelog later.
+	///
+	/// The fragments are named after the versions of the releases (e.g.
+	/// `v1.0.0.md`) and the ones that already exist are not overwritten so
+	/// that they can be edited by hand. The fragment of the unreleased changes
+	/// is rendered again or removed if there are no unreleased changes.
+	/// Returns the number of written fragments.
+	pub fn render_fragments(&self, dir: &Path) -> Result<usize> {
+		debug!("Rendering the fragments...");
+		fs::create_dir_all(dir)?;
+		let mut count = 0;
+		let unreleased = dir.join(UNRELEASED_FRAGMENT);
+		if unreleased.exists() {
+			trace!("Removing the unreleased fragment: {:?}", unreleased);
+			fs::remove_file(unreleased)?;
+		}
+		for (i, release) in self.releases.iter().enumerate() {
+			let path = dir.join(Self::fragment_name(release));
+			if path.exists() {
+				trace!("Fragment already exists: {:?}", path);
+				continue;
+			}
+			let output = match (i, &self.draft) {
+				(0, Some(draft)) => draft.to_string(),
+				_ if release.version.is_none() && release.commits.is_empty() => {
+					continue;
+				}
+				_ => self.render_release(release)?,
+			};
+			Self::write_output(
+				&mut File::create(&path)?,
+				&output,
+				self.config.changelog.line_ending,
+			)?;
+			count += 1;
+		}
+		Ok(count)
+	}
+
+	/// Generates a changelog and prepends it to the given changelog.
+	///
+	/// Line endings of the given changelog are normalized before removing
+	/// its header and the line ending of the output defaults to the one of
+	/// the given changelog. The releases that already have a heading in the
+	/// given changelog are skipped and its unreleased section is replaced so
+	/// that prepending is idempotent.
+	pub fn prepend<W: Write>(&self, changelog: String, out: &mut W) -> Result<()> {
 		debug!("Generating changelog and prepending...");
+		let line_ending = self
+			.config
+			.changelog
+			.line_ending
+			.or_else(|| changelog.contains("\r\n").then(|| LineEnding::Crlf));
+		let mut changelog = changelog.replace("\r\n", "\n");
 		if let Some(header) = &self.config.changelog.header {
-			changelog = changelog.replacen(header, "", 1);
+			changelog = changelog.replacen(&header.replace("\r\n", "\n"), "", 1);
 		}
-		self.generate(out)?;
-		write!(out, "{}", changelog)?;
-		Ok(())
+		let changelog = Self::strip_unreleased(&changelog);
+		let versions = Self::heading_versions(&changelog);
+		Self::write_output(
+			out,
+			&(self.render(false, Some(&versions))? + &changelog),
+			line_ending,
+		)
+	}
+
+	/// Flags the releases whose regenerated notes differ from the published
+	/// ones, i.e. the notes of the Github releases (if resolved) or the
+	/// sections of the given changelog (e.g. the committed one).
+	///
+	/// A release differs from the given changelog if its version is in a
+	/// heading but its rendered notes are not found as is.
+	/// Returns the versions of the edited releases.
+	pub fn detect_edits(&mut self, changelog: Option<&str>) -> Result<Vec<String>> {
+		debug!("Detecting the edited releases...");
+		let changelog = changelog.map(|v| v.replace("\r\n", "\n"));
+		let versions = changelog
+			.as_deref()
+			.map(Self::heading_versions)
+			.unwrap_or_default();
+		let mut edited = Vec::new();
+		for i in 0..self.releases.len() {
+			let release = &self.releases[i];
+			let version = match &release.version {
+				Some(version) => version.to_string(),
+				None => continue,
+			};
+			let output = self.render_release(release)?.replace("\r\n", "\n");
+			let output = output.trim();
+			let is_edited = release
+				.remote
+				.as_ref()
+				.and_then(|remote| remote.body.as_ref())
+				.map_or(false, |body| body.replace("\r\n", "\n").trim() != output) ||
+				changelog.as_ref().map_or(false, |changelog| {
+					versions.contains(version.trim_start_matches('v')) &&
+						!changelog.contains(output)
+				});
+			if is_edited {
+				warn!(
+					"Release notes of {} differ from the published ones",
+					version
+				);
+				self.releases[i].edited = true;
+				edited.push(version);
+			}
+		}
+		Ok(edited)
+	}
+
+	/// Returns the line numbers of the release headings (i.e. level-2 headings
+	/// such as `## [v1.0.0] - 2021-07-18`) of the given changelog along with
+	/// their versions without the `v` prefix (e.g. `1.0.0`).
+	///
+	/// The version is the first word of the heading that contains a digit.
+	/// The lines in the fenced code blocks are not considered as headings.
+	fn release_headings(changelog: &str) -> Vec<(usize, Option<String>)> {
+		let mut headings = Vec::new();
+		let mut fence: Option<&str> = None;
+		for (i, line) in changelog.lines().map(str::trim_start).enumerate() {
+			let marker = ["```", "~~~"].into_iter().find(|v| line.starts_with(v));
+			match (fence, marker) {
+				(None, Some(marker)) => fence = Some(marker),
+				(Some(open), Some(marker)) if open == marker => fence = None,
+				(None, None) if line.starts_with("## ") => {
+					let version = line[3..]
+						.split(|c: char| {
+							c.is_whitespace() || "[]()<>`*,:".contains(c)
+						})
+						.find(|word| word.contains(|c: char| c.is_ascii_digit()))
+						.map(|word| word.trim_start_matches('v').to_string());
+					headings.push((i, version));
+				}
+				_ => {}
+			}
+		}
+		headings
+	}
+
+	/// Returns the versions in the release headings of the given changelog
+	/// for matching the versions of the releases.
+	fn heading_versions(changelog: &str) -> HashSet<String> {
+		Self::release_headings(changelog)
+			.into_iter()
+			.filter_map(|(_, version)| version)
+			.collect()
+	}
+
+	/// Removes the unreleased sections (i.e. the release headings without a
+	/// version before the first release) from the given changelog so that
+	/// they are replaced with the rendered ones.
+	fn strip_unreleased(changelog: &str) -> String {
+		let headings = Self::release_headings(changelog);
+		let end = headings
+			.iter()
+			.find(|(_, version)| version.is_some())
+			.map_or(usize::MAX, |(i, _)| *i);
+		let start = match headings.first() {
+			Some((i, None)) => *i,
+			_ => return changelog.to_string(),
+		};
+		changelog
+			.split_inclusive('\n')
+			.enumerate()
+			.filter(|(i, _)| *i < start || *i >= end)
+			.map(|(_, line)| line)
+			.collect()
+	}
+
+	/// Updates the notes of the published Github releases with the rendered
+	/// releases.
+	///
+	/// If `dry_run` is set, the changes are written to the given output as a
+	/// diff instead.
+	/// Returns the number of outdated releases.
+	pub async fn sync_releases<W: Write>(
+		&self,
+		dry_run: bool,
+		out: &mut W,
+	) -> Result<usize> {
+		debug!("Synchronizing the Github releases...");
+		let repo = self.github_repo.as_ref().ok_or_else(|| {
+			Error::ChangelogError(String::from(
+				"'repository' value is needed to synchronize Github releases",
+			))
+		})?;
+		let api_url = github::api_url(&self.config.github);
+		let mut count = 0;
+		for release in &self.releases {
+			let version = match &release.version {
+				Some(version) => version,
+				None => continue,
+			};
+			let github_release = match github::get_release_by_tag(
+				api_url,
+				&self.github_token,
+				repo,
+				version,
+			)
+			.await?
+			{
+				Some(github_release) => github_release,
+				None => {
+					trace!("Release is not published: {}", version);
+					continue;
+				}
+			};
+			let body = self.render_release(release)?;
+			let old_body = github_release.body.unwrap_or_default();
+			if body.trim() == old_body.trim() {
+				trace!("Release notes are up-to-date: {}", version);
+				continue;
+			}
+			count += 1;
+			if dry_run {
+				writeln!(out, "--- {}", github_release.tag_name)?;
+				writeln!(out, "+++ {}", github_release.tag_name)?;
+				for line in diff::lines(old_body.trim(), body.trim()) {
+					match line {
+						diff::Result::Left(v) => writeln!(out, "-{}", v)?,
+						diff::Result::Right(v) => writeln!(out, "+{}", v)?,
+						diff::Result::Both(v, _) => writeln!(out, " {}", v)?,
+					}
+				}
+			} else {
+				info!("Updating the release notes of {}", version);
+				github::update_release_body(
+					api_url,
+					&self.github_token,
+					repo,
+					github_release.id,
+					&body,
+				)
+				.await?;
+			}
+		}
+		Ok(count)
+	}
+
+	/// Creates or updates (if `overwrite` is set) the Github release of the
+	/// latest version and returns its URL.
+	pub async fn publish_release(
+		&self,
+		draft: bool,
+		prerelease: bool,
+		overwrite: bool,
+	) -> Result<Option<String>> {
+		debug!("Publishing the Github release...");
+		let repo = self.github_repo.as_ref().ok_or_else(|| {
+			Error::ChangelogError(String::from(
+				"'repository' value is needed to publish Github releases",
+			))
+		})?;
+		let (release, version) = self
+			.releases
+			.first()
+			.and_then(|release| Some((release, release.version.as_ref()?)))
+			.ok_or_else(|| {
+				Error::ChangelogError(String::from(
+					"The latest release does not have a version, use '--tag' or \
+					 '--bump'",
+				))
+			})?;
+		let api_url = github::api_url(&self.config.github);
+		let body = match &self.draft {
+			Some(draft) => draft.to_string(),
+			None => self.render_release(release)?,
+		};
+		let new_release = NewRelease {
+			tag_name: version,
+			name: version,
+			body: body.trim(),
+			draft,
+			prerelease,
+		};
+		let github_release = match github::get_release_by_tag(
+			api_url,
+			&self.github_token,
+			repo,
+			version,
+		)
+		.await?
+		{
+			Some(github_release) if overwrite => {
+				info!("Updating the release of {}", version);
+				github::update_release(
+					api_url,
+					&self.github_token,
+					repo,
+					github_release.id,
+					&new_release,
+				)
+				.await?
+			}
+			Some(_) => {
+				return Err(Error::ChangelogError(format!(
+					"Release already exists: {version}, use '--overwrite' to \
+					 update it"
+				)));
+			}
+			None => {
+				info!("Creating the release of {}", version);
+				github::create_release(
+					api_url,
+					&self.github_token,
+					repo,
+					&new_release,
+				)
+				.await?
+			}
+		};
+		Ok(github_release.html_url)
+	}
+
+	/// Checks the links in the rendered changelog and returns the broken ones.
+	pub async fn check_links(&self) -> Result<Vec<BrokenLink>> {
+		let urls = links::extract_urls(&self.render(true, None)?);
+		debug!("Checking {} link(s)...", urls.len());
+		LinkChecker::new(self.cache.clone()).check(&urls).await
 	}
 
 	/// Prints the changelog context to the given output.
 	pub fn write_context<W: Write>(&self, out: &mut W) -> Result<()> {
-		let output = Releases(&self.releases).as_json()?;
+		let releases = Releases(&self.releases);
+		let output = match &self.config.changelog.context_fields {
+			Some(fields) => releases.as_json_with_fields(fields)?,
+			None => releases.as_json()?,
+		};
 		writeln!(out, "{output}")?;
 		Ok(())
 	}
@@ -245,16 +1454,21 @@ mod test {
 		CommitParser,
 		CommitPreprocessor,
 		GitConfig,
+		GiteaConfig,
+		GithubConfig,
+		TagMatcher,
 	};
 	use git_cliff_core::regex::Regex;
+	use git_cliff_core::release::ReleaseStatistics;
 	use pretty_assertions::assert_eq;
+	use std::env;
 	use std::str;
 
 	fn get_test_data() -> (Config, Vec<Release<'static>>) {
 		let config = Config {
 			changelog: ChangelogConfig {
-				header: Some(String::from("# Changelog")),
-				body:   Some(String::from(
+				header:   Some(String::from("# Changelog")),
+				body:     Some(String::from(
 					r#"{% if version %}
 				## Release [{{ version }}] - {{ timestamp | date(format="%Y-%m-%d") }}
 				({{ commit_id }}){% else %}
@@ -265,77 +1479,149 @@ mod test {
 				- {{ commit.message }}{% endfor %}
 				{% endfor %}{% endfor %}"#,
 				)),
-				footer: Some(String::from("------------")),
-				trim:   Some(true),
+				footer:   Some(String::from("------------")),
+				trim:     Some(true),
+				pdf:      None,
+				unscoped: None,
+
+				max_entries_per_group:    None,
+				entry_priority:           None,
+				extra_context:            None,
+				allowed_env:              None,
+				previous_summary:         None,
+				render_always_unreleased: None,
+				artifacts:                None,
+				line_ending:              None,
+				scope_depth:              None,
+				context_fields:           None,
+				redact_emails:            None,
+				email_redaction:          None,
+				email_salt:               None,
+				fragments:                None,
+				header_path:              None,
+				body_path:                None,
+				footer_path:              None,
+				header_sha256:            None,
+				body_sha256:              None,
+				footer_sha256:            None,
+				layout:                   None,
+				tables:                   None,
+				format:                   None,
+				migration_markers:        None,
+				deprecations:             None,
+				api_diff:                 None,
+				metric_commands:          None,
+				detect_edits:             None,
+				locale:                   None,
 			},
 			git:       GitConfig {
-				conventional_commits:     Some(true),
-				filter_unconventional:    Some(false),
-				split_commits:            Some(false),
-				commit_preprocessors:     Some(vec![CommitPreprocessor {
+				conventional_commits:       Some(true),
+				filter_unconventional:      Some(false),
+				split_commits:              Some(false),
+				emojis:                     None,
+				commit_preprocessors:       Some(vec![CommitPreprocessor {
 					pattern:         Regex::new("<preprocess>").unwrap(),
 					replace:         Some(String::from(
 						"this commit is preprocessed",
 					)),
 					replace_command: None,
 				}]),
-				commit_parsers:           Some(vec![
+				commit_parsers:             Some(vec![
 					CommitParser {
 						message:       Regex::new(r".*merge.*").ok(),
 						body:          None,
+						breaking:      None,
+						merge:         None,
 						group:         None,
 						default_scope: None,
 						scope:         None,
 						skip:          Some(true),
+						priority:      None,
+						append:        None,
 					},
 					CommitParser {
 						message:       Regex::new("feat*").ok(),
 						body:          None,
+						breaking:      None,
+						merge:         None,
 						group:         Some(String::from("New features")),
 						default_scope: Some(String::from("other")),
 						scope:         None,
 						skip:          None,
+						priority:      None,
+						append:        None,
 					},
 					CommitParser {
 						message:       Regex::new("^fix*").ok(),
 						body:          None,
+						breaking:      None,
+						merge:         None,
 						group:         Some(String::from("Bug Fixes")),
 						default_scope: None,
 						scope:         None,
 						skip:          None,
+						priority:      None,
+						append:        None,
 					},
 					CommitParser {
 						message:       Regex::new("doc:").ok(),
 						body:          None,
+						breaking:      None,
+						merge:         None,
 						group:         Some(String::from("Documentation")),
 						default_scope: None,
 						scope:         Some(String::from("documentation")),
 						skip:          None,
+						priority:      None,
+						append:        None,
 					},
 					CommitParser {
 						message:       Regex::new(".*").ok(),
 						body:          None,
+						breaking:      None,
+						merge:         None,
 						group:         Some(String::from("Other")),
 						default_scope: Some(String::from("other")),
 						scope:         None,
 						skip:          None,
+						priority:      None,
+						append:        None,
 					},
 				]),
-				protect_breaking_commits: None,
-				filter_commits:           Some(false),
-				tag_pattern:              None,
-				skip_tags:                Regex::new("v3.*").ok(),
-				ignore_tags:              None,
-				date_order:               Some(false),
-				sort_commits:             Some(String::from("oldest")),
-				link_parsers:             None,
-				limit_commits:            None,
+				parser_match:               None,
+				protect_breaking_commits:   None,
+				protect_referenced_commits: None,
+				fixups:                     None,
+				filter_reverted:            None,
+				filter_commits:             Some(false),
+				entry_normalizer:           None,
+				tag_pattern:                None,
+				skip_tags:                  Regex::new("v3.*")
+					.ok()
+					.map(TagMatcher::Regex),
+				skip_tags_mode:             None,
+				ignore_tags:                None,
+				date_order:                 Some(false),
+				tag_date:                   None,
+				signatures:                 None,
+				cherry_picks:               None,
+				release_lines:              None,
+				merge_commits:              None,
+				diff_stats:                 None,
+				sort_by:                    None,
+				sort_commits:               Some(String::from("oldest")),
+				issue_parsers:              None,
+				link_parsers:               None,
+				field_parsers:              None,
+				limit_commits:              None,
+				scope_from_paths:           None,
+				translation_memory:         None,
 			},
 			..Default::default()
 		};
 		let test_release = Release {
-			version:   Some(String::from("v1.0.0")),
-			commits:   vec![
+			version:         Some(String::from("v1.0.0")),
+			commits:         vec![
 				Commit::new(
 					String::from("0bc123"),
 					String::from("feat(app): add cool features"),
@@ -373,23 +1659,38 @@ mod test {
 					String::from("feat!: support breaking commits"),
 				),
 			],
-			commit_id: Some(String::from("0bc123")),
-			timestamp: 50000000,
-			previous:  None,
+			commit_id:       Some(String::from("0bc123")),
+			timestamp:       50000000,
+			previous:        None,
+			channel:         None,
+			omitted_commits: Default::default(),
+			summary:         None,
+			artifacts:       Vec::new(),
+			remote:          None,
+			migration_notes: Vec::new(),
+			api_changes:     None,
+			tag_signed:      false,
+			tag_signature:   None,
+			metrics:         Default::default(),
+			edited:          false,
+			statistics:      ReleaseStatistics::default(),
+			contributors:    Vec::new(),
+			breaking:        Vec::new(),
+			tag:             None,
 		};
 		let releases = vec![
 			test_release.clone(),
 			Release {
-				version: Some(String::from("v3.0.0")),
-				commits: vec![Commit::new(
+				version:         Some(String::from("v3.0.0")),
+				commits:         vec![Commit::new(
 					String::from("n0thin"),
 					String::from("feat(xyz): skip commit"),
 				)],
 				..Release::default()
 			},
 			Release {
-				version:   None,
-				commits:   vec![
+				version:         None,
+				commits:         vec![
 					Commit::new(
 						String::from("abc123"),
 						String::from("feat(app): add xyz"),
@@ -412,9 +1713,24 @@ mod test {
 						String::from("chore(ui): do boring stuff"),
 					),
 				],
-				commit_id: None,
-				timestamp: 1000,
-				previous:  Some(Box::new(test_release)),
+				commit_id:       None,
+				timestamp:       1000,
+				previous:        Some(Box::new(test_release)),
+				channel:         None,
+				omitted_commits: Default::default(),
+				summary:         None,
+				artifacts:       Vec::new(),
+				remote:          None,
+				migration_notes: Vec::new(),
+				api_changes:     None,
+				tag_signed:      false,
+				tag_signature:   None,
+				metrics:         Default::default(),
+				edited:          false,
+				statistics:      ReleaseStatistics::default(),
+				contributors:    Vec::new(),
+				breaking:        Vec::new(),
+				tag:             None,
 			},
 		];
 		(config, releases)
@@ -423,7 +1739,7 @@ mod test {
 	#[tokio::test]
 	async fn changelog_generator() -> Result<()> {
 		let (config, releases) = get_test_data();
-		let changelog = Changelog::new(releases, &config, None, None).await?;
+		let mut changelog = Changelog::new(releases, &config, None, None).await?;
 		let mut out = Vec::new();
 		changelog.generate(&mut out)?;
 		assert_eq!(
@@ -480,9 +1796,505 @@ mod test {
 			.replace("			", ""),
 			str::from_utf8(&out).unwrap()
 		);
+		changelog.set_draft(String::from("\n## Edited draft\n"));
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert!(str::from_utf8(&out)
+			.unwrap()
+			.starts_with("# Changelog\n## Edited draft\n\n## Release [v1.0.0]"));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changelog_merge_skipped_tags() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.git.skip_tags_mode = Some(SkipTagsMode::Merge);
+		let changelog = Changelog::new(releases, &config, None, None).await?;
+		assert_eq!(
+			vec![None, Some("v1.0.0")],
+			changelog
+				.releases
+				.iter()
+				.map(|v| v.version.as_deref())
+				.collect::<Vec<Option<&str>>>()
+		);
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		let out = str::from_utf8(&out).unwrap();
+		assert!(out.contains("#### xyz\n- skip commit\n"));
+		assert!(
+			out.find("skip commit") < out.find("## Release [v1.0.0]"),
+			"{out}"
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changelog_bump_version() -> Result<()> {
+		let (config, releases) = get_test_data();
+		let mut changelog = Changelog::new(releases, &config, None, None).await?;
+		assert_eq!(
+			Some(String::from("v1.1.0")),
+			changelog.bump_version(Bump::Auto, Some("v1.0.0"))?
+		);
+		assert_eq!(Some("v1.1.0"), changelog.releases[0].version.as_deref());
+		assert_ne!(0, changelog.releases[0].timestamp);
+		assert_eq!(None, changelog.bump_version(Bump::Auto, Some("v1.0.0"))?);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changelog_backfill() -> Result<()> {
+		let (config, releases) = get_test_data();
+		let mut changelog = Changelog::new(releases, &config, None, None).await?;
+		let dir = env::temp_dir().join("git-cliff-backfill");
+		if dir.exists() {
+			fs::remove_dir_all(&dir)?;
+		}
+		assert_eq!(1, changelog.backfill(&dir)?);
+		assert_eq!(0, changelog.backfill(&dir)?);
+		assert!(fs::read_to_string(dir.join("v1.0.0.md"))?
+			.trim_start()
+			.starts_with("## Release [v1.0.0] - 1971-08-02"));
+		changelog.releases[0].version = Some(String::from("release/1.0.0"));
+		assert_eq!(1, changelog.backfill(&dir)?);
+		assert!(dir.join("release-1.0.0.md").is_file());
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changelog_fragments() -> Result<()> {
+		let (config, releases) = get_test_data();
+		let mut changelog = Changelog::new(releases, &config, None, None).await?;
+		let dir = env::temp_dir().join("git-cliff-fragments");
+		if dir.exists() {
+			fs::remove_dir_all(&dir)?;
+		}
+		assert_eq!(2, changelog.render_fragments(&dir)?);
+		assert_eq!(1, changelog.render_fragments(&dir)?);
+		fs::write(dir.join("v1.0.0.md"), "## Edited release\n")?;
+		assert_eq!(2, changelog.set_fragments(&dir)?);
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		let out = String::from_utf8(out).unwrap();
+		assert!(out.ends_with("## Edited release\n------------"));
+		changelog.releases.retain(|v| v.version.is_some());
+		assert_eq!(0, changelog.render_fragments(&dir)?);
+		assert!(!dir.join(UNRELEASED_FRAGMENT).exists());
+		fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changelog_deprecations() -> Result<()> {
+		let (mut config, _) = get_test_data();
+		config.git.commit_parsers = None;
+		config.changelog.body = Some(String::from("## {{ version }}\n"));
+		config.changelog.trim = Some(false);
+		config.changelog.footer = None;
+		config.changelog.deprecations = Some(true);
+		let releases = vec![
+			Release {
+				version: Some(String::from("v1.0.0")),
+				commits: vec![Commit::new(
+					String::from("abc123"),
+					String::from("deprecate(api): deprecate `xyz`"),
+				)],
+				..Release::default()
+			},
+			Release {
+				version: Some(String::from("v1.1.0")),
+				commits: vec![Commit::new(
+					String::from("abc124"),
+					String::from("fix: fix abc"),
+				)],
+				..Release::default()
+			},
+			Release {
+				version: None,
+				commits: vec![Commit::new(
+					String::from("abc125"),
+					String::from(
+						"feat: add abc\n\nDeprecated: `def` is replaced by `abc`",
+					),
+				)],
+				..Release::default()
+			},
+		];
+		let changelog = Changelog::new(releases, &config, None, None).await?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert!(str::from_utf8(&out).unwrap().ends_with(
+			"## v1.0.0\n## Deprecations\n\n- deprecate `xyz` (since v1.0.0)\n- \
+			 `def` is replaced by `abc` (unreleased)\n\n"
+		));
+		let mut out = Vec::new();
+		changelog.prepend(String::new(), &mut out)?;
+		assert!(!str::from_utf8(&out).unwrap().contains("## Deprecations"));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changelog_render_always_unreleased() -> Result<()> {
+		let (mut config, _) = get_test_data();
+		config.changelog.body = Some(String::from(
+			"## {{ version | default(value=\"Unreleased\") }}\n",
+		));
+		config.changelog.trim = Some(false);
+		config.changelog.footer = None;
+		let releases = vec![
+			Release {
+				version: Some(String::from("v1.0.0")),
+				commits: vec![Commit::new(
+					String::from("abc123"),
+					String::from("feat: add xyz"),
+				)],
+				..Release::default()
+			},
+			Release::default(),
+		];
+		let changelog =
+			Changelog::new(releases.clone(), &config, None, None).await?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert!(!str::from_utf8(&out).unwrap().contains("## Unreleased"));
+		config.changelog.render_always_unreleased = Some(true);
+		let changelog = Changelog::new(releases, &config, None, None).await?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert!(str::from_utf8(&out).unwrap().contains("## Unreleased\n"));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changelog_render_releases_in_order() -> Result<()> {
+		let (mut config, _) = get_test_data();
+		config.changelog.header = None;
+		config.changelog.body = Some(String::from("## {{ version }}\n"));
+		config.changelog.trim = Some(false);
+		config.changelog.footer = None;
+		let releases = (0..100)
+			.map(|i| Release {
+				version: Some(format!("v0.{i}.0")),
+				commits: vec![Commit::new(
+					format!("abc{i}"),
+					String::from("feat: add xyz"),
+				)],
+				..Release::default()
+			})
+			.collect::<Vec<Release>>();
+		let changelog = Changelog::new(releases, &config, None, None).await?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		assert_eq!(100, changelog.releases.len());
+		assert_eq!(
+			changelog
+				.releases
+				.iter()
+				.map(|v| format!(
+					"## {}\n",
+					v.version.as_deref().unwrap_or_default()
+				))
+				.collect::<String>(),
+			str::from_utf8(&out).unwrap()
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changelog_keepachangelog() -> Result<()> {
+		let (mut config, _) = get_test_data();
+		config.git.commit_parsers = None;
+		config.changelog.header = None;
+		config.changelog.body = None;
+		config.changelog.footer = None;
+		config.changelog.format = Some(ChangelogFormat::KeepAChangelog);
+		config.remote.url = Some(String::from("https://github.com/orhun/git-cliff"));
+		let previous = Release {
+			version: Some(String::from("v1.0.0")),
+			timestamp: 0,
+			commits: vec![Commit::new(
+				String::from("abc123"),
+				String::from("feat: add xyz"),
+			)],
+			..Release::default()
+		};
+		let releases = vec![previous.clone(), Release {
+			version: None,
+			commits: vec![
+				Commit::new(String::from("abc124"), String::from("fix: fix xyz")),
+				Commit::new(String::from("abc125"), String::from("chore: bump")),
+			],
+			previous: Some(Box::new(previous)),
+			..Release::default()
+		}];
+		let changelog =
+			Changelog::new(releases.clone(), &config, None, None).await?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		let url = "https://github.com/orhun/git-cliff";
+		assert_eq!(
+			format!(
+				"{}## [Unreleased]\n\n### Changed\n\n- Bump\n\n### Fixed\n\n- Fix \
+				 xyz\n\n## [1.0.0] - 1970-01-01\n\n### Added\n\n- Add \
+				 xyz\n\n[unreleased]: {url}/compare/v1.0.0..HEAD\n[1.0.0]: \
+				 {url}/commits/v1.0.0\n",
+				keepachangelog::HEADER
+			),
+			str::from_utf8(&out).unwrap()
+		);
+		config.changelog.body = Some(String::from("## {{ version }}\n"));
+		let changelog = Changelog::new(releases, &config, None, None).await?;
+		assert!(changelog.generate(&mut Vec::new()).is_err());
 		Ok(())
 	}
 
+	#[tokio::test]
+	async fn changelog_prepend_crlf() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.header = Some(String::from("# Changelog\n"));
+		let changelog = Changelog::new(releases, &config, None, None).await?;
+		let mut out = Vec::new();
+		changelog.prepend(
+			String::from("# Changelog\r\n## Release [v0.9.0]\r\n- old\r\n"),
+			&mut out,
+		)?;
+		let out = str::from_utf8(&out).unwrap();
+		assert_eq!(1, out.matches("# Changelog").count());
+		assert!(out.ends_with("------------## Release [v0.9.0]\r\n- old\r\n"));
+		assert_eq!(out.matches('\n').count(), out.matches("\r\n").count());
+
+		config.changelog.line_ending = Some(LineEnding::Lf);
+		let changelog = Changelog::new(Vec::new(), &config, None, None).await?;
+		let mut out = Vec::new();
+		changelog.prepend(String::from("# Changelog\r\n- old\r\n"), &mut out)?;
+		assert_eq!(
+			"# Changelog\n------------- old\n",
+			str::from_utf8(&out).unwrap()
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changelog_prepend_existing_releases() -> Result<()> {
+		let (mut config, releases) = get_test_data();
+		config.changelog.header = None;
+		config.changelog.body = Some(String::from(
+			"## {{ version | default(value=\"Unreleased\") }}\n",
+		));
+		config.changelog.footer = None;
+		config.changelog.trim = Some(false);
+		let changelog = Changelog::new(releases, &config, None, None).await?;
+		let mut out = Vec::new();
+		changelog.prepend(String::new(), &mut out)?;
+		let out = String::from_utf8(out).unwrap();
+		assert_eq!("## Unreleased\n## v1.0.0\n", out);
+		let mut out = Vec::new();
+		changelog.prepend(
+			String::from("```\n# 1.0.0\n```\n## [1.0.0] - 2021-07-18\n"),
+			&mut out,
+		)?;
+		assert_eq!(
+			"## Unreleased\n```\n# 1.0.0\n```\n## [1.0.0] - 2021-07-18\n",
+			str::from_utf8(&out).unwrap()
+		);
+		let mut rerun = Vec::new();
+		changelog.prepend(
+			String::from("## Unreleased\n- xyz\n## v1.0.0\n"),
+			&mut rerun,
+		)?;
+		assert_eq!(
+			"## Unreleased\n## v1.0.0\n",
+			str::from_utf8(&rerun).unwrap()
+		);
+		assert_eq!(
+			HashSet::from([String::from("1.0.0"), String::from("0.9.0")]),
+			Changelog::heading_versions(
+				"## Release [v1.0.0] - 2021-07-18\n- 0.9.0\n# 2.0.0\n## [0.9.0]\n"
+			)
+		);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changelog_detect_edits() -> Result<()> {
+		let (mut config, mut releases) = get_test_data();
+		config.changelog.body = Some(String::from(
+			"## {{ version | default(value=\"Unreleased\") }}\n{% for commit in \
+			 commits %}- {{ commit.message }}\n{% endfor %}",
+		));
+		releases[0].remote = Some(RemoteRelease {
+			body: Some(String::from("## v1.0.0\n- edited\n")),
+			..RemoteRelease::default()
+		});
+		let mut changelog = Changelog::new(releases, &config, None, None).await?;
+		assert_eq!(vec![String::from("v1.0.0")], changelog.detect_edits(None)?);
+		assert!(changelog.releases().iter().any(|v| v.edited));
+
+		let (_, releases) = get_test_data();
+		let mut changelog = Changelog::new(releases, &config, None, None).await?;
+		let mut out = Vec::new();
+		changelog.generate(&mut out)?;
+		let published = String::from_utf8(out).unwrap();
+		assert!(changelog.detect_edits(Some(&published))?.is_empty());
+		assert_eq!(
+			vec![String::from("v1.0.0")],
+			changelog.detect_edits(Some(&published.replace("- ", "* ")))?
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn remote_provider_resolve_authors() {
+		let repo = String::from("owner/repo");
+		for (resolve_authors, expected) in
+			[(None, true), (Some(false), false), (Some(true), true)]
+		{
+			let config = Config {
+				github: GithubConfig {
+					resolve_authors,
+					..GithubConfig::default()
+				},
+				..Config::default()
+			};
+			assert_eq!(
+				Some(expected),
+				Changelog::remote_provider(&config, Some(&repo), None, &[])
+					.map(|(_, resolve_authors)| resolve_authors),
+				"github: {resolve_authors:?}"
+			);
+			let config = Config {
+				gitea: GiteaConfig {
+					url: Some(String::from("https://codeberg.org")),
+					repository: Some(repo.clone()),
+					resolve_authors,
+					..GiteaConfig::default()
+				},
+				..Config::default()
+			};
+			assert_eq!(
+				Some(expected),
+				Changelog::remote_provider(&config, None, None, &[])
+					.map(|(_, resolve_authors)| resolve_authors),
+				"gitea: {resolve_authors:?}"
+			);
+		}
+	}
+
+	#[test]
+	fn parse_referenced_commits() {
+		let (mut config, _) = get_test_data();
+		let commits = vec![
+			Commit::new(String::from("1111111"), String::from("feat: merge xyz")),
+			Commit::new(
+				String::from("2222222"),
+				String::from(
+					"revert: undo the change\n\nThis reverts commit 1111111.",
+				),
+			),
+		];
+		let parse = |config: &Config| {
+			Changelog::parse_commits(&commits, config, None)
+				.into_iter()
+				.map(|commit| {
+					(
+						commit.id,
+						commit.group.map(String::from),
+						commit.hidden_references,
+					)
+				})
+				.collect::<Vec<_>>()
+		};
+		let other = Some(String::from("Other"));
+		assert_eq!(
+			vec![(String::from("2222222"), other.clone(), Vec::new())],
+			parse(&config)
+		);
+		config.git.protect_referenced_commits = Some(ReferenceProtection::Keep);
+		assert_eq!(
+			vec![
+				(String::from("1111111"), other.clone(), Vec::new()),
+				(String::from("2222222"), other.clone(), Vec::new()),
+			],
+			parse(&config)
+		);
+		config.git.protect_referenced_commits = Some(ReferenceProtection::Annotate);
+		assert_eq!(
+			vec![(String::from("2222222"), other, vec![String::from(
+				"1111111"
+			)])],
+			parse(&config)
+		);
+	}
+
+	#[test]
+	fn fold_fixups() {
+		let commits = vec![
+			Commit::new(String::from("1111111"), String::from("feat: add xyz")),
+			Commit::new(
+				String::from("2222222"),
+				String::from("fixup! feat: add xyz"),
+			),
+			Commit::new(
+				String::from("3333333"),
+				String::from("squash! fixup! feat: add xyz"),
+			),
+			Commit::new(
+				String::from("4444444"),
+				String::from("fixup! feat: add abc"),
+			),
+		];
+		let fold = |mode| {
+			Changelog::fold_fixups(&commits, mode)
+				.into_iter()
+				.map(|commit| (commit.id, commit.fixups))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(
+			vec![(String::from("1111111"), vec![
+				String::from("2222222"),
+				String::from("3333333")
+			])],
+			fold(FixupMode::Fold)
+		);
+		assert_eq!(
+			vec![(String::from("1111111"), Vec::new())],
+			fold(FixupMode::Drop)
+		);
+	}
+
+	#[test]
+	fn filter_reverted() {
+		let commits = vec![
+			Commit::new(String::from("1111111"), String::from("feat: add xyz")),
+			Commit::new(String::from("2222222"), String::from("feat: add abc")),
+			Commit::new(
+				String::from("3333333"),
+				String::from("Revert \"feat: add xyz\""),
+			),
+			Commit::new(
+				String::from("4444444"),
+				String::from("revert: undo abc\n\nThis reverts commit 2222222."),
+			),
+			Commit::new(
+				String::from("5555555"),
+				String::from("Revert \"Revert \"feat: add xyz\"\""),
+			),
+			Commit::new(
+				String::from("6666666"),
+				String::from("Revert \"fix: fix abc\""),
+			),
+		];
+		assert_eq!(
+			vec!["1111111", "6666666"],
+			Changelog::filter_reverted(&commits)
+				.iter()
+				.map(|commit| commit.id.as_str())
+				.collect::<Vec<&str>>()
+		);
+	}
+
 	#[tokio::test]
 	async fn changelog_generator_split_commits() -> Result<()> {
 		let (mut config, mut releases) = get_test_data();
@@ -2,11 +2,18 @@
 pub mod args;
 /// Changelog generator.
 pub mod changelog;
+/// Helpers for testing the changelog of a repository.
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
 #[macro_use]
 extern crate log;
 
 use args::{
+	Bump,
+	Command,
+	Format,
+	LineEnding,
 	Opt,
 	Sort,
 	Strip,
@@ -14,21 +21,59 @@ use args::{
 use changelog::Changelog;
 use clap::ArgEnum;
 use git_cliff_core::commit::Commit;
-use git_cliff_core::config::Config;
+use git_cliff_core::config::{
+	CacheBackend,
+	CherryPickMode,
+	Config,
+	LineEnding as ConfigLineEnding,
+	MergeCommits,
+	PackageConfig,
+	SkipTagsMode,
+	SortBy,
+};
 use git_cliff_core::embed::EmbeddedConfig;
 use git_cliff_core::error::{
 	Error,
 	Result,
 };
-use git_cliff_core::release::Release;
+use git_cliff_core::fragment;
+use git_cliff_core::github;
+use git_cliff_core::glob::Pattern;
+use git_cliff_core::intern::Symbol;
+use git_cliff_core::merge;
+use git_cliff_core::metrics::Metrics;
+use git_cliff_core::org::OrgChangelog;
+use git_cliff_core::release::{
+	Bump as ReleaseBump,
+	Release,
+	Tag,
+};
 use git_cliff_core::repo::Repository;
-use git_cliff_core::DEFAULT_CONFIG;
+use git_cliff_core::retry;
+use git_cliff_core::serde_json::{
+	self,
+	Value,
+};
+use git_cliff_core::signature::CryptoSignature;
+use git_cliff_core::{
+	ALTERNATIVE_CONFIGS,
+	DEFAULT_CONFIG,
+};
+use std::collections::HashMap;
 use std::env;
 use std::fs::{
 	self,
 	File,
 };
-use std::io;
+use std::io::{
+	self,
+	Write,
+};
+use std::path::{
+	Path,
+	PathBuf,
+};
+use std::time::Instant;
 
 /// Checks for a new version on crates.io
 #[cfg(feature = "update-informer")]
@@ -49,6 +94,26 @@ fn check_new_version() {
 	}
 }
 
+/// Exports the changelog as PDF to the given path.
+#[cfg(feature = "pdf")]
+fn export_pdf(changelog: &Changelog, config: &Config, path: &Path) -> Result<()> {
+	let mut markdown = Vec::new();
+	changelog.generate(&mut markdown)?;
+	git_cliff_core::pdf::export(
+		std::str::from_utf8(&markdown)?.to_string(),
+		&config.changelog.pdf.clone().unwrap_or_default(),
+		path,
+	)
+}
+
+/// Exports the changelog as PDF to the given path.
+#[cfg(not(feature = "pdf"))]
+fn export_pdf(_: &Changelog, _: &Config, _: &Path) -> Result<()> {
+	Err(Error::ArgumentError(String::from(
+		"PDF export requires the 'pdf' feature to be enabled",
+	)))
+}
+
 /// Runs `git-cliff`.
 pub async fn run(mut args: Opt) -> Result<()> {
 	// Check if there is a new version available.
@@ -56,15 +121,112 @@ pub async fn run(mut args: Opt) -> Result<()> {
 	check_new_version();
 
 	// Create the configuration file if init flag is given.
-	if args.init {
-		info!("Saving the configuration file to {:?}", DEFAULT_CONFIG);
-		fs::write(DEFAULT_CONFIG, EmbeddedConfig::get_config()?)?;
+	if let Some(preset) = &args.init {
+		info!(
+			"Saving the {preset} configuration file to {:?}",
+			DEFAULT_CONFIG
+		);
+		fs::write(DEFAULT_CONFIG, EmbeddedConfig::get_preset(preset)?)?;
 		return Ok(());
 	}
 
+	// Process the unreleased changes and skip the output if there are none.
+	if args.unreleased_only_if_changes {
+		args.unreleased = true;
+	}
+
+	// Render the unreleased changes without header and footer for drafts.
+	if let Some(Command::Draft { path }) = &args.command {
+		if args.prepend.is_some() || args.context || args.from_draft.is_some() {
+			return Err(Error::ArgumentError(String::from(
+				"'draft' cannot be used with '-p', '--context' or '--from-draft'",
+			)));
+		}
+		info!("Saving the draft to {:?}", path);
+		args.unreleased = true;
+		args.output = Some(path.clone());
+		args.strip = Some(Strip::All);
+	}
+
+	// Publish the latest release without writing the changelog.
+	if let Some(Command::Publish { .. }) = &args.command {
+		if args.prepend.is_some() || args.output.is_some() || args.context {
+			return Err(Error::ArgumentError(String::from(
+				"'publish' cannot be used with '-o', '-p' or '--context'",
+			)));
+		}
+	}
+
+	// Validate the commits without writing the changelog.
+	if let Some(Command::Lint) = &args.command {
+		if args.prepend.is_some() || args.output.is_some() || args.context {
+			return Err(Error::ArgumentError(String::from(
+				"'lint' cannot be used with '-o', '-p' or '--context'",
+			)));
+		}
+	}
+
+	// Process the whole history for writing the notes of every release.
+	if let Some(Command::Backfill { .. } | Command::SyncReleases { .. }) =
+		&args.command
+	{
+		if args.prepend.is_some() ||
+			args.output.is_some() ||
+			args.context ||
+			args.from_draft.is_some() ||
+			args.unreleased ||
+			args.latest ||
+			args.current ||
+			args.tag_only.is_some() ||
+			args.range.is_some() ||
+			args.ranges.is_some()
+		{
+			return Err(Error::ArgumentError(String::from(
+				"'backfill' and 'sync-releases' cannot be used with other output \
+				 or range arguments",
+			)));
+		}
+	}
+
+	// Split the rendering of the changelog into editable fragments.
+	match &args.command {
+		Some(Command::Render { .. })
+			if args.prepend.is_some() || args.output.is_some() || args.context =>
+		{
+			return Err(Error::ArgumentError(String::from(
+				"'render' cannot be used with '-o', '-p' or '--context'",
+			)));
+		}
+		Some(Command::Assemble { .. })
+			if args.context || args.from_draft.is_some() =>
+		{
+			return Err(Error::ArgumentError(String::from(
+				"'assemble' cannot be used with '--context' or '--from-draft'",
+			)));
+		}
+		_ => {}
+	}
+
+	// Merge the given changelogs without processing the repository.
+	if let Some(Command::MergeChangelogs { ours, theirs }) = &args.command {
+		if args.prepend.is_some() || args.context {
+			return Err(Error::ArgumentError(String::from(
+				"'merge-changelogs' cannot be used with '-p' or '--context'",
+			)));
+		}
+		let output =
+			merge::merge(&fs::read_to_string(ours)?, &fs::read_to_string(theirs)?);
+		return match &args.output {
+			Some(path) => Ok(fs::write(path, output)?),
+			None => Ok(io::stdout().write_all(output.as_bytes())?),
+		};
+	}
+
 	// Set the working directory.
 	if let Some(ref workdir) = args.workdir {
-		args.config = workdir.join(args.config);
+		if !is_url(&args.config) {
+			args.config = workdir.join(args.config);
+		}
 		args.repository = match args.repository {
 			Some(repository) => Some(workdir.join(repository)),
 			None => Some(workdir.clone()),
@@ -72,11 +234,37 @@ pub async fn run(mut args: Opt) -> Result<()> {
 		if let Some(changelog) = args.prepend {
 			args.prepend = Some(workdir.join(changelog));
 		}
+		if let Some(draft) = args.from_draft {
+			args.from_draft = Some(workdir.join(draft));
+		}
+		if let Some(metrics) = args.metrics {
+			args.metrics = Some(workdir.join(metrics));
+		}
+		match &mut args.command {
+			Some(
+				Command::Backfill { output_dir } | Command::Render { output_dir },
+			) => {
+				*output_dir = workdir.join(&output_dir);
+			}
+			Some(Command::Assemble { input_dir }) => {
+				*input_dir = workdir.join(&input_dir);
+			}
+			_ => {}
+		}
 	}
 
 	// Parse the configuration file.
 	let mut path = args.config.clone();
-	if !path.exists() {
+	if !path.exists() && path.ends_with(DEFAULT_CONFIG) {
+		if let Some(alternative) = ALTERNATIVE_CONFIGS
+			.iter()
+			.map(|name| path.with_file_name(name))
+			.find(|path| path.exists())
+		{
+			path = alternative;
+		}
+	}
+	if !path.exists() && !is_url(&path) {
 		if let Some(config_path) = dirs_next::config_dir()
 			.map(|dir| dir.join(env!("CARGO_PKG_NAME")).join(DEFAULT_CONFIG))
 		{
@@ -85,8 +273,16 @@ pub async fn run(mut args: Opt) -> Result<()> {
 	}
 
 	// Load the default configuration if necessary.
-	let mut config = if path.exists() {
-		Config::parse(&path)?
+	let config_cache = dirs_next::cache_dir()
+		.unwrap_or_else(env::temp_dir)
+		.join(env!("CARGO_PKG_NAME"))
+		.join("configs");
+	let mut config = if is_url(&path) {
+		let url = path.to_string_lossy();
+		debug!("Fetching the configuration from {}", url);
+		Config::fetch(&url, args.config_sha256.as_deref(), &config_cache).await?
+	} else if path.exists() {
+		Config::parse_async(&path, &config_cache).await?
 	} else {
 		if !args.context {
 			warn!(
@@ -101,6 +297,35 @@ pub async fn run(mut args: Opt) -> Result<()> {
 		config.changelog.body = EmbeddedConfig::parse()?.changelog.body;
 	}
 
+	// Resolve the relative paths in the configuration against the working
+	// directory.
+	if let Some(ref workdir) = args.workdir {
+		let fragments_path = config.changelog.fragments.as_mut().map(|v| {
+			v.path
+				.get_or_insert_with(|| fragment::DEFAULT_PATH.to_string())
+		});
+		for path in [
+			config.git.translation_memory.as_mut(),
+			config.cache.path.as_mut(),
+			fragments_path,
+		]
+		.into_iter()
+		.flatten()
+		{
+			if Path::new(path).is_relative() {
+				*path = workdir.join(&path).to_string_lossy().to_string();
+			}
+		}
+	}
+
+	// Apply the retry policy to the remote requests.
+	retry::set_policy(config.remote.retry.clone().unwrap_or_default());
+
+	// Keep the cache only in memory if it is disabled.
+	if args.no_cache {
+		config.cache.backend = Some(CacheBackend::Memory);
+	}
+
 	// Update the configuration based on command line arguments and vice versa.
 	match args.strip {
 		Some(Strip::Header) => {
@@ -117,15 +342,83 @@ pub async fn run(mut args: Opt) -> Result<()> {
 	}
 	if args.prepend.is_some() {
 		config.changelog.footer = None;
-		if !(args.unreleased || args.latest || args.range.is_some()) {
+		if !(args.unreleased ||
+			args.latest ||
+			args.range.is_some() ||
+			args.ranges.is_some() ||
+			args.tag_only.is_some())
+		{
 			return Err(Error::ArgumentError(String::from(
 				"'-u' or '-l' is not specified",
 			)));
 		}
 	}
+	if args.format == Format::Pdf &&
+		(args.output.is_none() || args.prepend.is_some() || args.context)
+	{
+		return Err(Error::ArgumentError(String::from(
+			"'--format pdf' can only be used with '-o'",
+		)));
+	}
 	if args.body.is_some() {
 		config.changelog.body = args.body.clone();
 	}
+	if args.github_api_url.is_some() {
+		config.github.api_url = args.github_api_url.clone();
+	}
+
+	// Render the releases of the organization via the Github API.
+	if let Some(Command::Org { name, repos, limit }) = &args.command {
+		if args.github_token.is_none() {
+			warn!("Github token is not set, the API requests may be rate limited.");
+		}
+		let changelog = OrgChangelog::fetch(
+			github::api_url(&config.github),
+			&args.github_token,
+			name,
+			repos.as_deref().unwrap_or_default(),
+			*limit,
+		)
+		.await?;
+		info!(
+			"Fetched {} release(s) of the {:?} organization",
+			changelog.releases.len(),
+			name
+		);
+		let output = if args.context {
+			serde_json::to_string_pretty(&changelog)? + "\n"
+		} else {
+			changelog.render()?
+		};
+		return match &args.output {
+			Some(path) => Ok(fs::write(path, output)?),
+			None => Ok(io::stdout().write_all(output.as_bytes())?),
+		};
+	}
+	if args.gitlab_token.is_some() {
+		config.gitlab.token = args.gitlab_token.clone();
+	}
+	if args.gitea_token.is_some() {
+		config.gitea.token = args.gitea_token.clone();
+	}
+	if let Some(line_ending) = args.line_ending {
+		config.changelog.line_ending = Some(match line_ending {
+			LineEnding::Lf => ConfigLineEnding::Lf,
+			LineEnding::Crlf => ConfigLineEnding::Crlf,
+			LineEnding::Native => ConfigLineEnding::Native,
+		});
+	}
+	if let Some(context_vars) = args.context_var.take() {
+		config
+			.changelog
+			.extra_context
+			.get_or_insert_with(HashMap::new)
+			.extend(
+				context_vars
+					.into_iter()
+					.map(|(key, value)| (key, Value::String(value))),
+			);
+	}
 	if args.sort == Sort::Oldest {
 		if let Some(ref sort_commits) = config.git.sort_commits {
 			args.sort = Sort::from_str(sort_commits, true)
@@ -138,26 +431,129 @@ pub async fn run(mut args: Opt) -> Result<()> {
 		}
 	}
 
+	// Generate the changelogs of the packages in the monorepo.
+	if args.package.is_some() || args.all_packages {
+		let packages = match &args.package {
+			Some(name) => vec![config
+				.packages
+				.iter()
+				.find(|package| &package.name == name)
+				.cloned()
+				.ok_or_else(|| {
+					Error::ArgumentError(format!("Package not found: {name}"))
+				})?],
+			None => config.packages.clone(),
+		};
+		for package in packages {
+			info!("Generating the changelog of {:?}", package.name);
+			let (args, config) = package_options(&args, &config, &package)?;
+			process_repository(args, config).await?;
+		}
+		return Ok(());
+	}
+	process_repository(args, config).await
+}
+
+/// Returns whether the given path of the configuration file is a URL.
+fn is_url(path: &Path) -> bool {
+	path.to_str().map_or(false, |path| {
+		path.starts_with("https://") || path.starts_with("http://")
+	})
+}
+
+/// Returns the arguments and configuration for generating the changelog of
+/// the given package.
+fn package_options(
+	args: &Opt,
+	config: &Config,
+	package: &PackageConfig,
+) -> Result<(Opt, Config)> {
+	let (mut args, mut config) = (args.clone(), config.clone());
+	let patterns = |paths: &Option<Vec<String>>| {
+		paths
+			.iter()
+			.flatten()
+			.map(|path| Ok(Pattern::new(path)?))
+			.collect::<Result<Vec<Pattern>>>()
+	};
+	for (args_paths, package_paths) in [
+		(&mut args.include_path, &package.include_paths),
+		(&mut args.exclude_path, &package.exclude_paths),
+	] {
+		let package_paths = patterns(package_paths)?;
+		if !package_paths.is_empty() {
+			args_paths
+				.get_or_insert_with(Vec::new)
+				.extend(package_paths);
+		}
+	}
+	if package.tag_pattern.is_some() {
+		config.git.tag_pattern = package.tag_pattern.clone();
+	}
+	if args.output.is_none() && args.prepend.is_none() && !args.context {
+		args.output = match &package.output {
+			Some(output) => Some(match &args.workdir {
+				Some(workdir) => workdir.join(output),
+				None => PathBuf::from(output),
+			}),
+			None if args.package.is_some() => None,
+			None => {
+				return Err(Error::ArgumentError(format!(
+					"'output' is not set for the package: {}",
+					package.name
+				)));
+			}
+		};
+	}
+	Ok((args, config))
+}
+
+/// Processes the repository and generates the changelog.
+async fn process_repository(args: Opt, mut config: Config) -> Result<()> {
+	let mut metrics = Metrics::default();
+	let started_at = Instant::now();
+
+	// Delete the consumed fragments if the changelog of a new release is
+	// written.
+	let consume_fragments = (args.tag.is_some() || args.bump.is_some()) &&
+		(args.output.is_some() || args.prepend.is_some()) &&
+		!args.context &&
+		args.command.is_none();
+
 	// Initialize the git repository.
 	let repository =
 		Repository::init(args.repository.clone().unwrap_or(env::current_dir()?))?;
 
 	// Parse tags.
-	let mut tags = repository.tags(&config.git.tag_pattern, args.date_order)?;
+	let tag_patterns = config
+		.git
+		.tag_pattern
+		.as_ref()
+		.map(|v| v.patterns())
+		.unwrap_or_default();
+	let sort_by = config.git.sort_by.unwrap_or(SortBy::CommitDate);
+	let merge_commits = config.git.merge_commits.unwrap_or(MergeCommits::Include);
+	let mut tags = repository.tags(&tag_patterns, args.date_order, sort_by)?;
 
 	// Skip tags.
-	config.git.skip_tags = config.git.skip_tags.filter(|r| !r.as_str().is_empty());
-	let skip_regex = config.git.skip_tags.as_ref();
-	let ignore_regex = config.git.ignore_tags.as_ref();
+	config.git.skip_tags = config.git.skip_tags.filter(|r| !r.is_empty());
+	let skip_matcher = config.git.skip_tags.as_ref();
+	let ignore_matcher = config.git.ignore_tags.as_ref();
+	let merge_skipped = config.git.skip_tags_mode == Some(SkipTagsMode::Merge);
 	tags = tags
 		.into_iter()
 		.filter(|(_, name)| {
-			// Keep skip tags to drop commits in the later stage.
-			let skip = skip_regex.map(|r| r.is_match(name)).unwrap_or_default();
+			// Keep skip tags to drop commits in the later stage, unless their
+			// commits are merged into the next release.
+			let skip = skip_matcher.map(|r| r.is_match(name)).unwrap_or_default();
+			if skip && merge_skipped {
+				trace!("Merging release into the next one: {}", name);
+				return false;
+			}
 
-			let ignore = ignore_regex
+			let ignore = ignore_matcher
 				.map(|r| {
-					if r.as_str().trim().is_empty() {
+					if r.is_empty() {
 						return false;
 					}
 
@@ -173,58 +569,133 @@ pub async fn run(mut args: Opt) -> Result<()> {
 		})
 		.collect();
 
+	// Link the releases to the previous ones on the same release line.
+	let lineage = match &config.git.release_lines {
+		Some(branches) => repository.release_lineage(branches, &tags)?,
+		None => HashMap::new(),
+	};
+
 	// Print debug information about configuration and arguments.
 	log::trace!("{:#?}", args);
 	log::trace!("{:#?}", config);
 
 	// Parse commits.
 	let mut commit_range = args.range;
-	if args.unreleased {
+	let mut previous_tag_index = tags.len().checked_sub(2);
+	let lineage_index = |index: usize| {
+		tags.get_index(index)
+			.and_then(|(id, _)| lineage.get(id))
+			.and_then(|id| tags.get_index_of(id))
+	};
+	if let Some(tag) = &args.tag_only {
+		let tag_index = tags
+			.values()
+			.position(|v| v == tag)
+			.ok_or_else(|| Error::ArgumentError(format!("Tag not found: {tag}")))?;
+		previous_tag_index =
+			lineage_index(tag_index).or_else(|| tag_index.checked_sub(1));
+		commit_range = match (
+			previous_tag_index
+				.and_then(|i| tags.get_index(i))
+				.map(|(k, _)| k),
+			tags.get_index(tag_index).map(|(k, _)| k),
+		) {
+			(Some(tag1), Some(tag2)) => Some(format!("{}..{}", tag1, tag2)),
+			(None, tag) => tag.cloned(),
+			_ => None,
+		};
+	} else if args.unreleased {
+		previous_tag_index = tags.len().checked_sub(1);
 		if let Some(last_tag) = tags.last().map(|(k, _)| k) {
 			commit_range = Some(format!("{}..HEAD", last_tag));
 		}
 	} else if args.latest || args.current {
-		if tags.len() < 2 {
-			let commits = repository.commits(None, None, None)?;
-			if let (Some(tag1), Some(tag2)) = (
-				commits.last().map(|c| c.id().to_string()),
-				tags.get_index(0).map(|(k, _)| k),
-			) {
-				commit_range = Some(format!("{}..{}", tag1, tag2));
-			}
+		let tag_index = if args.current {
+			let current_tag = repository.current_tag();
+			Some(
+				current_tag
+					.as_ref()
+					.and_then(|tag| tags.values().position(|v| v == tag))
+					.ok_or_else(|| {
+						Error::ChangelogError(String::from(
+							"No tag exists for the current commit",
+						))
+					})?,
+			)
 		} else {
-			let mut tag_index = tags.len() - 2;
-			if args.current {
-				if let Some(current_tag_index) =
-					repository.current_tag().as_ref().and_then(|tag| {
-						tags.iter()
-							.enumerate()
-							.find(|(_, (_, v))| v == &tag)
-							.map(|(i, _)| i)
-					}) {
-					tag_index = current_tag_index - 1;
-				} else {
-					return Err(Error::ChangelogError(String::from(
-						"No tag exists for the current commit",
-					)));
-				}
-			}
-			if let (Some(tag1), Some(tag2)) = (
+			tags.len().checked_sub(1)
+		};
+		// Process the commits of the tag (from the root commit if there is no
+		// previous tag) and leave the commits after it out.
+		if let Some(tag_index) = tag_index {
+			previous_tag_index =
+				lineage_index(tag_index).or_else(|| tag_index.checked_sub(1));
+			commit_range = match (
+				previous_tag_index
+					.and_then(|i| tags.get_index(i))
+					.map(|(k, _)| k),
 				tags.get_index(tag_index).map(|(k, _)| k),
-				tags.get_index(tag_index + 1).map(|(k, _)| k),
 			) {
-				commit_range = Some(format!("{}..{}", tag1, tag2));
-			}
+				(Some(tag1), Some(tag2)) => Some(format!("{}..{}", tag1, tag2)),
+				(None, tag) => tag.cloned(),
+				_ => None,
+			};
 		}
 	}
-	let mut commits =
-		repository.commits(commit_range, args.include_path, args.exclude_path)?;
+	let commit_ranges = commit_range
+		.into_iter()
+		.chain(args.ranges.into_iter().flatten())
+		.collect::<Vec<String>>();
+	let mut commits = repository.commits_in_ranges(
+		&commit_ranges,
+		args.include_path,
+		args.exclude_path,
+		sort_by,
+		merge_commits,
+	)?;
+	if commit_ranges.len() > 1 {
+		commits = repository.order_by_releases(commits, &tags)?;
+	}
 	if let Some(commit_limit_value) = config.git.limit_commits {
 		commits = commits.drain(..commit_limit_value).collect();
 	}
 
+	// Validate the commits against the conventional commits specification and
+	// the commit parsers.
+	if let Some(Command::Lint) = &args.command {
+		let mut invalid_commits = 0;
+		for commit in commits.iter().rev().map(Commit::from) {
+			let problems = commit.lint(&config.git);
+			for problem in &problems {
+				error!(
+					"{} - {} ({})",
+					&commit.id[..7],
+					problem,
+					commit.message.lines().next().unwrap_or_default().trim()
+				);
+			}
+			if !problems.is_empty() {
+				invalid_commits += 1;
+			}
+		}
+		if invalid_commits > 0 {
+			return Err(Error::ChangelogError(format!(
+				"Found {} invalid commit(s)",
+				invalid_commits
+			)));
+		}
+		info!("All of the {} commit(s) are valid", commits.len());
+		return Ok(());
+	}
+
+	// Detect the cherry-picks of the released commits.
+	let cherry_picks = match config.git.cherry_picks {
+		Some(_) => repository.cherry_picks(&commits)?,
+		None => HashMap::new(),
+	};
+
 	// Update tags.
-	if let Some(tag) = args.tag {
+	if let Some(tag) = args.tag.clone() {
 		if let Some(commit_id) = commits.first().map(|c| c.id().to_string()) {
 			match tags.get(&commit_id) {
 				Some(tag) => {
@@ -237,30 +708,130 @@ pub async fn run(mut args: Opt) -> Result<()> {
 		}
 	}
 
+	// Compile the rules for inferring the scopes from the changed paths.
+	let scope_rules = config
+		.git
+		.scope_from_paths
+		.iter()
+		.flatten()
+		.map(|rule| Ok((Pattern::new(&rule.path)?, Symbol::from(&rule.scope))))
+		.collect::<Result<Vec<(Pattern, Symbol)>>>()?;
+
+	// Parse the signatures of the commits and tags.
+	let parse_signature = |armored: String, kind: &str, object: &str| {
+		let mut signature = CryptoSignature::new(&armored);
+		if let Some(command) = config
+			.git
+			.signatures
+			.as_ref()
+			.and_then(|v| v.verify_command.as_ref())
+		{
+			signature.verify(command, kind, object);
+		}
+		signature
+	};
+
 	// Process releases.
+	let head_id = commits.first().map(|v| v.id().to_string());
 	let mut releases = vec![Release::default()];
 	let mut release_index = 0;
 	let mut previous_release = Release::default();
+	let mut tagged_releases = HashMap::new();
+	let mut author_dates = HashMap::new();
 	for git_commit in commits.into_iter().rev() {
-		let commit = Commit::from(&git_commit);
+		let mut commit = Commit::from(&git_commit);
 		let commit_id = commit.id.to_string();
-		if args.sort == Sort::Newest {
+		if sort_by == SortBy::AuthorDate {
+			author_dates.insert(commit_id.clone(), sort_by.timestamp(&git_commit));
+		}
+		if !scope_rules.is_empty() {
+			let paths = repository.changed_paths(&git_commit);
+			commit.default_scope = scope_rules
+				.iter()
+				.find(|(pattern, _)| paths.iter().any(|v| pattern.matches_path(v)))
+				.map(|(_, scope)| scope.clone());
+		}
+		if config.git.signatures.is_some() {
+			commit.signature = repository
+				.commit_signature(&git_commit)
+				.map(|v| parse_signature(v, "commit", &commit_id));
+		}
+		commit.cherry_pick_of = cherry_picks.get(&commit_id).cloned();
+		if let (Some(original), Some(CherryPickMode::Drop)) =
+			(&commit.cherry_pick_of, config.git.cherry_picks)
+		{
+			trace!("Dropping the cherry-pick of {}: {}", original, commit_id);
+		} else if args.sort == Sort::Newest {
 			releases[release_index].commits.insert(0, commit);
 		} else {
 			releases[release_index].commits.push(commit);
 		}
 		if let Some(tag) = tags.get(&commit_id) {
 			releases[release_index].version = Some(tag.to_string());
-			releases[release_index].commit_id = Some(commit_id);
-			releases[release_index].timestamp = git_commit.time().seconds();
+			releases[release_index].channel = config
+				.git
+				.tag_pattern
+				.as_ref()
+				.and_then(|v| v.channel(tag))
+				.map(String::from);
+			releases[release_index].commit_id = Some(commit_id.clone());
+			releases[release_index].tag = Some(match &args.tag_message {
+				Some(message) if args.tag.as_ref() == Some(tag) => Tag {
+					name:    tag.to_string(),
+					message: Some(message.to_string()),
+					tagger:  None,
+				},
+				_ => repository.tag(tag),
+			});
+			if config.git.signatures.is_some() {
+				releases[release_index].tag_signature = repository
+					.tag_signature(tag)
+					.map(|v| parse_signature(v, "tag", tag));
+				releases[release_index].tag_signed =
+					releases[release_index].tag_signature.is_some();
+			}
+			releases[release_index].timestamp =
+				if config.git.tag_date.unwrap_or(false) {
+					repository
+						.tag_timestamp(tag)
+						.unwrap_or_else(|| sort_by.timestamp(&git_commit))
+				} else {
+					sort_by.timestamp(&git_commit)
+				};
 			previous_release.previous = None;
+			if let Some(previous_id) = lineage.get(&commit_id) {
+				previous_release = tagged_releases
+					.get(previous_id)
+					.cloned()
+					.unwrap_or_else(|| Release {
+						commit_id: Some(previous_id.to_string()),
+						version: tags.get(previous_id).cloned(),
+						..Release::default()
+					});
+			}
 			releases[release_index].previous = Some(Box::new(previous_release));
 			previous_release = releases[release_index].clone();
+			tagged_releases.insert(commit_id, previous_release.clone());
 			releases.push(Release::default());
 			release_index += 1;
 		}
 	}
 
+	// Sort the commits of each release by the author date while keeping the
+	// topological order for splitting the releases.
+	if !author_dates.is_empty() {
+		for release in releases.iter_mut() {
+			release.commits.sort_by_key(|commit| {
+				let date = author_dates.get(&commit.id).copied().unwrap_or_default();
+				if args.sort == Sort::Newest {
+					-date
+				} else {
+					date
+				}
+			});
+		}
+	}
+
 	// Add custom commit messages to the latest release.
 	if let Some(custom_commits) = args.with_commit {
 		if let Some(latest_release) = releases.iter_mut().last() {
@@ -270,24 +841,205 @@ pub async fn run(mut args: Opt) -> Result<()> {
 		}
 	}
 
+	// Add the changelog fragments to the unreleased changes.
+	let mut fragments = Vec::new();
+	if let (Some(fragments_config), Some(latest_release)) =
+		(&config.changelog.fragments, releases.iter_mut().last())
+	{
+		if latest_release.version.is_none() || latest_release.version == args.tag {
+			fragments = fragment::load(fragments_config)?;
+			debug!("Adding {} fragment(s) to the changelog", fragments.len());
+			for fragment in &fragments {
+				latest_release
+					.commits
+					.push(Commit::from(fragment.to_message(fragments_config)));
+			}
+		}
+	}
+
 	// Set the previous release if needed.
-	if let Some((commit_id, version)) = tags.len().checked_sub(2).and_then(|v| tags.get_index(v)) {
-		let previous_release = Release {
+	if let Some((commit_id, version)) = releases[0]
+		.commit_id
+		.as_ref()
+		.and_then(|id| lineage.get(id))
+		.and_then(|id| tags.get_key_value(id))
+		.or_else(|| previous_tag_index.and_then(|v| tags.get_index(v)))
+	{
+		let mut previous_release = Release {
 			commit_id: Some(commit_id.to_string()),
 			version: Some(version.to_string()),
 			..Release::default()
 		};
+		// Collect the commits of the previous release for summarizing them.
+		if config.changelog.previous_summary.unwrap_or(false) {
+			let range = match lineage.get(commit_id).or_else(|| {
+				tags.get_index_of(commit_id)
+					.and_then(|i| i.checked_sub(1))
+					.and_then(|i| tags.get_index(i))
+					.map(|(k, _)| k)
+			}) {
+				Some(previous_id) => format!("{}..{}", previous_id, commit_id),
+				None => commit_id.to_string(),
+			};
+			previous_release.commits = repository
+				.commits_in_ranges(&[range], None, None, sort_by, merge_commits)?
+				.iter()
+				.map(Commit::from)
+				.collect();
+		}
 		releases[0].previous = Some(Box::new(previous_release));
 	}
 
+	// Compute the changes of the files since the previous releases.
+	if config.git.diff_stats.unwrap_or(false) {
+		let mut previous_id = None;
+		for release in releases.iter_mut() {
+			let from = release
+				.previous
+				.as_ref()
+				.and_then(|v| v.commit_id.clone())
+				.or(previous_id);
+			previous_id = release.commit_id.clone();
+			if let Some(to) = release
+				.commit_id
+				.as_ref()
+				.or(head_id.as_ref())
+				.filter(|_| !release.commits.is_empty())
+			{
+				let stats = repository.diff_stats(from.as_deref(), to)?;
+				release.statistics.files_changed = stats.files_changed();
+				release.statistics.insertions = stats.insertions();
+				release.statistics.deletions = stats.deletions();
+			}
+		}
+	}
+
+	// Set the contributors of the releases, knowing the contributors of the
+	// history until the start of the range.
+	let boundaries = match releases[0]
+		.previous
+		.as_ref()
+		.and_then(|v| v.commit_id.as_deref())
+	{
+		Some(previous_id) => vec![previous_id],
+		None => commit_ranges
+			.iter()
+			.filter_map(|range| range.split_once(".."))
+			.map(|(start, _)| start)
+			.filter(|start| !start.is_empty())
+			.collect(),
+	};
+	let mut contributors = repository.contributors_until(&boundaries)?;
+	for release in releases.iter_mut() {
+		release.set_contributors(&mut contributors);
+	}
+
+	// Leave the unreleased changes out of the tagged releases.
+	if (args.latest || args.current || args.tag_only.is_some()) &&
+		releases.len() > 1 &&
+		releases
+			.last()
+			.map_or(false, |v| v.version.is_none() && v.commits.is_empty())
+	{
+		releases.pop();
+	}
+
+	metrics.commits = releases.iter().map(|v| v.commits.len()).sum();
+	metrics.set_duration("parse", started_at.elapsed());
+
 	// Generate changelog.
-	let changelog = Changelog::new(
+	let started_at = Instant::now();
+	let mut changelog = Changelog::new(
 		releases,
 		&config,
 		repository.remote_urls().ok(),
 		args.github_token.clone(),
 	).await?;
-	if let Some(path) = args.prepend {
+	metrics.set_duration("process", started_at.elapsed());
+	if args.unreleased_only_if_changes &&
+		changelog.releases().iter().all(|v| v.commits.is_empty())
+	{
+		info!("There are no unreleased changes, skipping the output");
+		return Ok(());
+	}
+	if let Some(bump) = args.bump {
+		let bump = match bump {
+			Bump::Major => ReleaseBump::Major,
+			Bump::Minor => ReleaseBump::Minor,
+			Bump::Patch => ReleaseBump::Patch,
+			Bump::Auto => ReleaseBump::Auto,
+		};
+		let latest_tag = tags.last().map(|(_, v)| v.as_str());
+		match changelog.bump_version(bump, latest_tag)? {
+			Some(version) => info!("Bumping the version to {}", version),
+			None => warn!("There are no unreleased changes to bump the version"),
+		}
+	}
+	if let Some(path) = &args.from_draft {
+		debug!("Using the draft at {:?}", path);
+		changelog.set_draft(fs::read_to_string(path)?);
+	}
+	if let Some(Command::Assemble { input_dir }) = &args.command {
+		let count = changelog.set_fragments(input_dir)?;
+		debug!("Assembling {} fragment(s) from {:?}", count, input_dir);
+	}
+	if config.changelog.detect_edits.unwrap_or(false) {
+		let published = args
+			.prepend
+			.as_ref()
+			.or(args.output.as_ref())
+			.and_then(|path| fs::read_to_string(path).ok());
+		changelog.detect_edits(published.as_deref())?;
+	}
+	if args.check_links {
+		let broken_links = changelog.check_links().await?;
+		for link in &broken_links {
+			error!("Broken link: {} ({})", link.url, link.reason);
+		}
+		if !broken_links.is_empty() {
+			return Err(Error::ChangelogError(format!(
+				"Found {} broken link(s)",
+				broken_links.len()
+			)));
+		}
+	}
+	let started_at = Instant::now();
+	let result = if let Some(Command::Backfill { output_dir }) = &args.command {
+		let count = changelog.backfill(output_dir)?;
+		info!(
+			"Saved the notes of {} release(s) to {:?}",
+			count, output_dir
+		);
+		Ok(())
+	} else if let Some(Command::Render { output_dir }) = &args.command {
+		let count = changelog.render_fragments(output_dir)?;
+		info!("Saved {} fragment(s) to {:?}", count, output_dir);
+		Ok(())
+	} else if let Some(Command::Publish {
+		draft,
+		prerelease,
+		overwrite,
+	}) = &args.command
+	{
+		match changelog
+			.publish_release(*draft, *prerelease, *overwrite)
+			.await?
+		{
+			Some(url) => info!("Published the release: {}", url),
+			None => info!("Published the release"),
+		}
+		Ok(())
+	} else if let Some(Command::SyncReleases { dry_run }) = &args.command {
+		let count = changelog.sync_releases(*dry_run, &mut io::stdout()).await?;
+		if *dry_run {
+			info!("{} release(s) are outdated", count);
+		} else {
+			info!("Updated the notes of {} release(s)", count);
+		}
+		Ok(())
+	} else if let (Format::Pdf, Some(path)) = (args.format, &args.output) {
+		export_pdf(&changelog, &config, path)
+	} else if let Some(path) = args.prepend {
 		changelog.prepend(fs::read_to_string(&path)?, &mut File::create(path)?)
 	} else if let Some(path) = args.output {
 		let mut output = File::create(path)?;
@@ -300,5 +1052,24 @@ pub async fn run(mut args: Opt) -> Result<()> {
 		changelog.write_context(&mut io::stdout())
 	} else {
 		changelog.generate(&mut io::stdout())
+	};
+	metrics.set_duration("render", started_at.elapsed());
+	if result.is_ok() && consume_fragments && !fragments.is_empty() {
+		fragment::remove(&fragments)?;
+		info!("Deleted {} consumed fragment(s)", fragments.len());
+	}
+
+	// Export metrics.
+	if let Some(path) = &args.metrics {
+		metrics.releases = changelog.releases().len();
+		metrics.entries = changelog.releases().iter().map(|v| v.commits.len()).sum();
+		metrics.collect_counters();
+		let output = match path.extension().and_then(|v| v.to_str()) {
+			Some("json") => metrics.to_json()?,
+			_ => metrics.to_prometheus()?,
+		};
+		fs::write(path, output)?;
+		debug!("Saved the metrics to {:?}", path);
 	}
+	result
 }
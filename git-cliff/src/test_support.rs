@@ -0,0 +1,71 @@
+use crate::args::Opt;
+use clap::Parser;
+use git_cliff_core::config::Config;
+use git_cliff_core::error::Result;
+use std::fs;
+use tokio::runtime::Runtime;
+
+pub use git_cliff_core::test_support::{
+	assert_golden,
+	SampleRepository,
+};
+
+/// Renders the changelog of the repository with the given configuration.
+///
+/// The changelog is generated the same way as `git cliff --output` does,
+/// i.e. the commits are read from the repository, processed and rendered by
+/// the changelog.
+pub fn render(config: &Config, repository: &SampleRepository) -> Result<String> {
+	let output = repository.path().join(".git").join("CHANGELOG.md");
+	let args = Opt::parse_from([
+		"git-cliff".as_ref(),
+		"--repository".as_ref(),
+		repository.path().as_os_str(),
+		"--output".as_ref(),
+		output.as_os_str(),
+	]);
+	Runtime::new()?.block_on(crate::process_repository(args, config.clone()))?;
+	Ok(fs::read_to_string(output)?)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use git_cliff_core::config::ChangelogConfig;
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn render_sample_repository() -> Result<()> {
+		let mut repository = SampleRepository::new()?;
+		repository
+			.commit("feat: add xyz")?
+			.tag("v1.0.0")?
+			.commit("fix: fix xyz")?
+			.commit("feat: add abc")?
+			.commit("not conventional")?
+			.tag("v1.1.0")?
+			.commit("docs: update readme")?;
+		let config = Config {
+			changelog: ChangelogConfig {
+				header: Some(String::from("# Changelog\n")),
+				body: Some(String::from(
+					"## {{ version | default(value=\"unreleased\") }}\n{% for \
+					 commit in commits %}- {{ commit.message }}\n{% endfor %}",
+				)),
+				..ChangelogConfig::default()
+			},
+			..Config::default()
+		};
+		let output = render(&config, &repository)?;
+		assert_eq!(
+			"# Changelog\n## unreleased\n- update readme\n## v1.1.0\n- fix xyz\n- \
+			 add abc\n## v1.0.0\n- add xyz\n",
+			output
+		);
+
+		let golden = repository.path().join("CHANGELOG.md");
+		fs::write(&golden, &output)?;
+		assert_golden(&golden, &output);
+		Ok(())
+	}
+}